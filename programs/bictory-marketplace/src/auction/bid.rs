@@ -0,0 +1,218 @@
+use anchor_lang::prelude::*;
+use anchor_lang::solana_program::clock::Clock;
+use anchor_spl::token::{Mint, Token};
+
+use crate::{constant::*, states::*, utils::*, error::*};
+
+/// Place an escrow-backed bid on an auction, releasing the previously outbid bidder reservation.
+pub fn bid(ctx: Context<Bid>, amount: u64, deposit_shortfall: bool) -> Result<()> {
+    let auction_account = &ctx.accounts.auction_account;
+
+    // Check auction is running
+    let now = Clock::get()?.unix_timestamp as u64;
+    require!(
+        auction_account.kind == AuctionKind::English &&
+        auction_account.start_time <= now && now <= auction_account.end_time,
+        MarketplaceError::AuctionNotActive,
+    );
+
+    // Check bid beats the reserve price or the highest bid by the minimum increment
+    let has_bid = auction_account.highest_bidder != Pubkey::default();
+    let min_bid = if has_bid {
+        auction_account
+            .highest_bid
+            .checked_add(auction_account.min_bid_increment)
+            .ok_or(MarketplaceError::NumericalOverflow)?
+    } else {
+        auction_account.reserve_price
+    };
+    require!(amount > 0 && amount >= min_bid, MarketplaceError::BidTooLow);
+
+    let is_native = ctx.accounts.treasury_mint.key() == spl_token::native_mint::id();
+    let ah_key = ctx.accounts.auction_house.key();
+    let wallet_key = ctx.accounts.bidder.key();
+    let previous_bidder = auction_account.highest_bidder;
+    let previous_bid = auction_account.highest_bid;
+
+    // If first time to use the escrow ledger
+    if is_zero_account(&ctx.accounts.escrow_state.to_account_info()) {
+        ctx.accounts.escrow_state.wallet = wallet_key;
+        ctx.accounts.escrow_state.auction_house = ah_key;
+    }
+    let balance = escrow_balance(&ctx.accounts.escrow_payment_account.to_account_info(), is_native)?;
+    ctx.accounts.escrow_state.sync(balance)?;
+
+    if has_bid && previous_bidder == wallet_key {
+        // Raising own bid, release the previous reservation
+        ctx.accounts.escrow_state.release(previous_bid)?;
+    } else if has_bid {
+        release_previous_bidder(&ctx, previous_bidder, previous_bid)?;
+    }
+
+    // Check free escrow covers the bid, topping it up first if requested
+    let free = ctx.accounts.escrow_state.free;
+    if free < amount {
+        require!(deposit_shortfall, MarketplaceError::InsufficientEscrow);
+
+        let escrow_signer_seeds = [
+            PREFIX,
+            ah_key.as_ref(),
+            wallet_key.as_ref(),
+            &[bump(&[PREFIX, ah_key.as_ref(), wallet_key.as_ref()], ctx.program_id)],
+        ];
+
+        transfer_to_escrow(
+            &ctx.accounts.bidder.to_account_info(),
+            &ctx.accounts.payment_account,
+            &ctx.accounts.escrow_payment_account,
+            &ctx.accounts.treasury_mint,
+            &ctx.accounts.auction_house.to_account_info(),
+            &ctx.accounts.token_program,
+            &ctx.accounts.system_program,
+            &ctx.accounts.rent,
+            &escrow_signer_seeds,
+            amount - free,
+            is_native,
+        )?;
+        ctx.accounts.escrow_state.deposit(amount - free)?;
+    }
+    ctx.accounts.escrow_state.commit(amount)?;
+
+    // Update highest bid
+    let auction_house = &ctx.accounts.auction_house;
+    let auction_account = &mut ctx.accounts.auction_account;
+    auction_account.highest_bidder = wallet_key;
+    auction_account.highest_bid = amount;
+
+    // Log bid detail
+    msg!("{{\"bidder\": \"{}\", \"amount\": \"{}\"}}", wallet_key, amount);
+
+    // Extend auction on bids close to the end, up to the maximum extension
+    if auction_account.end_time - now < auction_house.extension_window {
+        let max_end_time = auction_account
+            .initial_end_time
+            .checked_add(auction_house.max_extension)
+            .ok_or(MarketplaceError::NumericalOverflow)?;
+        let end_time = std::cmp::min(
+            auction_account
+                .end_time
+                .checked_add(auction_house.extension_period)
+                .ok_or(MarketplaceError::NumericalOverflow)?,
+            max_end_time,
+        );
+
+        if end_time > auction_account.end_time {
+            auction_account.end_time = end_time;
+            msg!("{{\"end_time\": {}}}", end_time);
+        }
+    }
+
+    Ok(())
+}
+
+/// Release the outbid reservation in the previous bidder escrow ledger.
+///
+/// Funds stay in the escrow, free to be withdrawn or reused, so a closed receipt account cannot block new bids.
+fn release_previous_bidder(ctx: &Context<Bid>, previous_bidder: Pubkey, previous_bid: u64) -> Result<()> {
+    let previous_state_info = ctx.accounts.previous_bidder_escrow_state.to_account_info();
+
+    let mut previous_state: Account<BuyerEscrowState> = Account::try_from(&previous_state_info)?;
+    previous_state.release(previous_bid)?;
+    previous_state.exit(ctx.program_id)?;
+
+    msg!("{{\"released\": \"{}\", \"amount\": \"{}\"}}", previous_bidder, previous_bid);
+
+    Ok(())
+}
+
+#[derive(Accounts)]
+pub struct Bid<'info> {
+    /// Bidder account.
+    #[account(mut)]
+    pub bidder: Signer<'info>,
+
+    /// Treasury mint account, either native SOL mint or a SPL token mint.
+    pub treasury_mint: Account<'info, Mint>,
+
+    /// Authority key for the Auction House.
+    /// CHECK: Validated as a auction house signer.
+    pub authority: UncheckedAccount<'info>,
+
+    /// Auction House instance PDA account.
+    #[account( 
+        seeds=[PREFIX, auction_house.creator.as_ref(), treasury_mint.key().as_ref()], 
+        bump, 
+        has_one=authority, 
+        has_one=treasury_mint,
+    )]
+    pub auction_house: Box<Account<'info, AuctionHouse>>,
+
+    /// CHECK: Validated in transfer_to_escrow.
+    /// Bidder SOL or SPL account to top up the escrow from when `deposit_shortfall` is set.
+    #[account(mut)]
+    pub payment_account: UncheckedAccount<'info>,
+
+    /// CHECK: Not dangerous. Account seeds checked in constraint.
+    /// Bidder escrow payment account PDA.
+    #[account(
+        mut,
+        seeds = [
+            PREFIX,
+            auction_house.key().as_ref(),
+            bidder.key().as_ref()
+        ],
+        bump
+    )]
+    pub escrow_payment_account: UncheckedAccount<'info>,
+
+    /// Bidder escrow ledger PDA account.
+    #[account(
+        init_if_needed,
+        seeds = [
+            PREFIX,
+            auction_house.key().as_ref(),
+            bidder.key().as_ref(),
+            ESCROW_STATE
+        ],
+        bump,
+        space = 8 + std::mem::size_of::<BuyerEscrowState>(),
+        payer = bidder
+    )]
+    pub escrow_state: Box<Account<'info, BuyerEscrowState>>,
+
+    /// NFT mint account
+    /// CHECK: Validated as a nft account.
+    pub nft_mint: UncheckedAccount<'info>,
+
+    /// Auction PDA account
+    #[account(
+        mut,
+        seeds=[
+            PREFIX,
+            auction_house.key().as_ref(),
+            nft_mint.key().as_ref(),
+            AUCTION
+        ],
+        bump,
+        constraint = auction_account.nft_mint == nft_mint.key(),
+    )]
+    pub auction_account: Box<Account<'info, AuctionAccount>>,
+
+    /// CHECK: Not dangerous. Account seeds checked in constraint, deserialized in release_previous_bidder.
+    /// Previous highest bidder escrow ledger PDA account.
+    #[account(
+        mut,
+        seeds = [
+            PREFIX,
+            auction_house.key().as_ref(),
+            auction_account.highest_bidder.as_ref(),
+            ESCROW_STATE
+        ],
+        bump
+    )]
+    pub previous_bidder_escrow_state: UncheckedAccount<'info>,
+
+    pub token_program: Program<'info, Token>,
+    pub system_program: Program<'info, System>,
+    pub rent: Sysvar<'info, Rent>,
+}
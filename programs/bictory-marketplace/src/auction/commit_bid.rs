@@ -0,0 +1,176 @@
+use anchor_lang::prelude::*;
+use anchor_lang::solana_program::clock::Clock;
+use anchor_spl::token::{Mint, Token};
+
+use crate::{constant::*, states::*, utils::*, error::*};
+
+/// Commit a hidden bid on a sealed-bid auction, reserving a deposit in escrow that caps the revealed amount.
+pub fn commit_bid(
+    ctx: Context<CommitBid>,
+    commitment: [u8; 32],
+    deposit: u64,
+    deposit_shortfall: bool,
+) -> Result<()> {
+    let auction_account = &ctx.accounts.auction_account;
+
+    // Check auction is in its bid phase
+    let now = Clock::get()?.unix_timestamp as u64;
+    require!(
+        auction_account.kind != AuctionKind::English &&
+        auction_account.start_time <= now && now <= auction_account.end_time,
+        MarketplaceError::AuctionNotActive,
+    );
+
+    // Check deposit can back a bid at the reserve price
+    require!(
+        deposit > 0 && deposit >= auction_account.reserve_price,
+        MarketplaceError::BidTooLow,
+    );
+
+    let is_native = ctx.accounts.treasury_mint.key() == spl_token::native_mint::id();
+    let ah_key = ctx.accounts.auction_house.key();
+    let wallet_key = ctx.accounts.bidder.key();
+
+    // If first time to use the escrow ledger
+    if is_zero_account(&ctx.accounts.escrow_state.to_account_info()) {
+        ctx.accounts.escrow_state.wallet = wallet_key;
+        ctx.accounts.escrow_state.auction_house = ah_key;
+    }
+    let balance = escrow_balance(&ctx.accounts.escrow_payment_account.to_account_info(), is_native)?;
+    ctx.accounts.escrow_state.sync(balance)?;
+
+    // Check free escrow covers the deposit, topping it up first if requested
+    let free = ctx.accounts.escrow_state.free;
+    if free < deposit {
+        require!(deposit_shortfall, MarketplaceError::InsufficientEscrow);
+
+        let escrow_signer_seeds = [
+            PREFIX,
+            ah_key.as_ref(),
+            wallet_key.as_ref(),
+            &[bump(&[PREFIX, ah_key.as_ref(), wallet_key.as_ref()], ctx.program_id)],
+        ];
+
+        transfer_to_escrow(
+            &ctx.accounts.bidder.to_account_info(),
+            &ctx.accounts.payment_account,
+            &ctx.accounts.escrow_payment_account,
+            &ctx.accounts.treasury_mint,
+            &ctx.accounts.auction_house.to_account_info(),
+            &ctx.accounts.token_program,
+            &ctx.accounts.system_program,
+            &ctx.accounts.rent,
+            &escrow_signer_seeds,
+            deposit - free,
+            is_native,
+        )?;
+        ctx.accounts.escrow_state.deposit(deposit - free)?;
+    }
+    ctx.accounts.escrow_state.commit(deposit)?;
+
+    // Fill sealed bid account
+    let bid_account = &mut ctx.accounts.bid_account;
+    bid_account.bidder = wallet_key;
+    bid_account.auction = ctx.accounts.auction_account.key();
+    bid_account.auction_nonce = ctx.accounts.auction_account.nonce;
+    bid_account.commitment = commitment;
+    bid_account.deposit = deposit;
+
+    // Log bid detail
+    msg!("{{\"bidder\": \"{}\", \"deposit\": \"{}\"}}", wallet_key, deposit);
+
+    Ok(())
+}
+
+#[derive(Accounts)]
+pub struct CommitBid<'info> {
+    /// Bidder account.
+    #[account(mut)]
+    pub bidder: Signer<'info>,
+
+    /// Treasury mint account, either native SOL mint or a SPL token mint.
+    pub treasury_mint: Account<'info, Mint>,
+
+    /// Authority key for the Auction House.
+    /// CHECK: Validated as a auction house signer.
+    pub authority: UncheckedAccount<'info>,
+
+    /// Auction House instance PDA account.
+    #[account( 
+        seeds=[PREFIX, auction_house.creator.as_ref(), treasury_mint.key().as_ref()], 
+        bump, 
+        has_one=authority, 
+        has_one=treasury_mint,
+    )]
+    pub auction_house: Box<Account<'info, AuctionHouse>>,
+
+    /// CHECK: Validated in transfer_to_escrow.
+    /// Bidder SOL or SPL account to top up the escrow from when `deposit_shortfall` is set.
+    #[account(mut)]
+    pub payment_account: UncheckedAccount<'info>,
+
+    /// CHECK: Not dangerous. Account seeds checked in constraint.
+    /// Bidder escrow payment account PDA.
+    #[account(
+        mut,
+        seeds = [
+            PREFIX,
+            auction_house.key().as_ref(),
+            bidder.key().as_ref()
+        ],
+        bump
+    )]
+    pub escrow_payment_account: UncheckedAccount<'info>,
+
+    /// Bidder escrow ledger PDA account.
+    #[account(
+        init_if_needed,
+        seeds = [
+            PREFIX,
+            auction_house.key().as_ref(),
+            bidder.key().as_ref(),
+            ESCROW_STATE
+        ],
+        bump,
+        space = 8 + std::mem::size_of::<BuyerEscrowState>(),
+        payer = bidder
+    )]
+    pub escrow_state: Box<Account<'info, BuyerEscrowState>>,
+
+    /// NFT mint account
+    /// CHECK: Validated as a nft account.
+    pub nft_mint: UncheckedAccount<'info>,
+
+    /// Auction PDA account
+    #[account(
+        seeds=[
+            PREFIX,
+            auction_house.key().as_ref(),
+            nft_mint.key().as_ref(),
+            AUCTION
+        ],
+        bump,
+        constraint = auction_account.nft_mint == nft_mint.key(),
+    )]
+    pub auction_account: Box<Account<'info, AuctionAccount>>,
+
+    /// Sealed bid PDA account
+    #[account(
+        init,
+        seeds=[
+            PREFIX,
+            auction_account.key().as_ref(),
+            auction_account.nonce.to_le_bytes().as_ref(),
+            bidder.key().as_ref(),
+            BID
+        ],
+        bump,
+        space=8 + std::mem::size_of::<SealedBidAccount>(),
+        payer=bidder
+    )]
+    pub bid_account: Box<Account<'info, SealedBidAccount>>,
+
+    pub token_program: Program<'info, Token>,
+    pub system_program: Program<'info, System>,
+    pub rent: Sysvar<'info, Rent>,
+}
@@ -0,0 +1,176 @@
+use anchor_lang::prelude::*;
+use anchor_lang::solana_program::clock::Clock;
+use anchor_spl::token::{self, Mint, Token, TokenAccount, SetAuthority};
+use spl_token::instruction::AuthorityType;
+
+use crate::{constant::*, states::*, utils::*, error::*};
+
+/// Put NFT on an ascending auction between start and end time.
+pub fn create_auction(
+    ctx: Context<CreateAuction>,
+    start_time: u64,
+    end_time: u64,
+    reserve_price: u64,
+    min_bid_increment: u64,
+) -> Result<()> {
+    // Check a bid must raise the highest bid
+    require!(min_bid_increment > 0, MarketplaceError::InvalidAmount);
+
+    ctx.accounts.fill_auction(start_time, end_time, reserve_price)?;
+
+    let auction_account = &mut ctx.accounts.auction_account;
+    auction_account.min_bid_increment = min_bid_increment;
+    auction_account.kind = AuctionKind::English;
+
+    // Log auction detail
+    msg!(
+        "{{\"start_time\": {}, \"end_time\": {}, \"reserve_price\": \"{}\", \"min_bid_increment\": \"{}\"}}",
+        start_time,
+        end_time,
+        reserve_price,
+        min_bid_increment
+    );
+
+    Ok(())
+}
+
+/// Put NFT on a sealed-bid auction, bids are committed until end time and revealed until reveal end time.
+pub fn create_sealed_auction(
+    ctx: Context<CreateAuction>,
+    start_time: u64,
+    end_time: u64,
+    reveal_end_time: u64,
+    reserve_price: u64,
+    second_price: bool,
+) -> Result<()> {
+    require!(reveal_end_time > end_time, MarketplaceError::InvalidExpiry);
+
+    ctx.accounts.fill_auction(start_time, end_time, reserve_price)?;
+
+    let auction_account = &mut ctx.accounts.auction_account;
+    auction_account.reveal_end_time = reveal_end_time;
+    auction_account.kind = if second_price {
+        AuctionKind::SealedSecondPrice
+    } else {
+        AuctionKind::SealedFirstPrice
+    };
+
+    // Log auction detail
+    msg!(
+        "{{\"start_time\": {}, \"end_time\": {}, \"reveal_end_time\": {}, \"reserve_price\": \"{}\", \"second_price\": {}}}",
+        start_time,
+        end_time,
+        reveal_end_time,
+        reserve_price,
+        second_price
+    );
+
+    Ok(())
+}
+
+#[derive(Accounts)]
+pub struct CreateAuction<'info> {
+    /// Seller account.
+    #[account(mut)]
+    pub seller: Signer<'info>,
+
+    /// Treasury mint account, either native SOL mint or a SPL token mint.
+    pub treasury_mint: Account<'info, Mint>,
+
+    /// Authority key for the Auction House.
+    /// CHECK: Validated as a auction house signer.
+    pub authority: UncheckedAccount<'info>,
+
+    /// Auction House treasury PDA account.
+    /// CHECK: Not dangerous. Account seeds checked in constraint.
+    #[account(seeds=[PREFIX, auction_house.key().as_ref(), TREASURY], bump)]
+    pub auction_house_treasury: UncheckedAccount<'info>,
+
+    /// Auction House instance PDA account.
+    #[account( 
+        seeds=[PREFIX, auction_house.creator.as_ref(), treasury_mint.key().as_ref()], 
+        bump, 
+        has_one=authority, 
+        has_one=treasury_mint, 
+        has_one=auction_house_treasury
+    )]
+    pub auction_house: Account<'info, AuctionHouse>,
+    
+    /// NFT mint account
+    /// CHECK: Validated as a nft account.
+    pub nft_mint: UncheckedAccount<'info>,
+    
+    /// NFT token account
+    #[account(mut,
+        constraint = nft_account.mint == nft_mint.key()
+    )]
+    pub nft_account: Box<Account<'info, TokenAccount>>,
+
+    /// Auction PDA account
+    #[account(
+        init,
+        seeds=[
+            PREFIX,
+            auction_house.key().as_ref(),
+            nft_mint.key().as_ref(),
+            AUCTION
+        ],
+        bump, 
+        space=8 + std::mem::size_of::<AuctionAccount>(),
+        payer=seller
+    )]
+    pub auction_account: Account<'info, AuctionAccount>,
+
+    pub token_program: Program<'info, Token>,
+    pub system_program: Program<'info, System>,
+    pub rent: Sysvar<'info, Rent>,
+}
+impl<'info> CreateAuction<'info> {
+    /// Hand the NFT over to the treasury and fill the auction window and reserve price.
+    pub fn fill_auction(&mut self, start_time: u64, end_time: u64, reserve_price: u64) -> Result<()> {
+        // Check auction window
+        let now = Clock::get()?.unix_timestamp as u64;
+        require!(end_time > start_time && end_time > now, MarketplaceError::InvalidExpiry);
+
+        // Check NFT balance
+        require!(
+            self.nft_account.amount > 0,
+            MarketplaceError::InvalidAmount,
+        );
+
+        // Check NFT is held in the seller ATA, where settlement returns or takes it from
+        assert_is_ata(
+            &self.nft_account.to_account_info(),
+            &self.seller.key(),
+            &self.nft_mint.key(),
+        )?;
+
+        // Set nft's authority to treasury account
+        token::set_authority(
+            self.set_authority_context(),
+            AuthorityType::AccountOwner,
+            Some(self.auction_house_treasury.key()),
+        )?;
+
+        // Fill auction account
+        let auction_account = &mut self.auction_account;
+        auction_account.owner = self.seller.key();
+        auction_account.nft_mint = self.nft_mint.key();
+        auction_account.auction_house = self.auction_house.key();
+        auction_account.start_time = start_time;
+        auction_account.end_time = end_time;
+        auction_account.initial_end_time = end_time;
+        auction_account.reserve_price = reserve_price;
+        auction_account.nonce = Clock::get()?.slot;
+
+        Ok(())
+    }
+
+    fn set_authority_context(&self) -> CpiContext<'_, '_, '_, 'info, SetAuthority<'info>> {
+        let cpi_account = SetAuthority {
+            current_authority: self.seller.to_account_info().clone(),
+            account_or_mint: self.nft_account.to_account_info().clone(),
+        };
+        CpiContext::new(self.token_program.to_account_info().clone(), cpi_account)
+    }
+}
@@ -0,0 +1,8 @@
+pub mod bid;
+pub mod commit_bid;
+pub mod create_auction;
+pub mod refund_sealed_bid;
+pub mod reveal_bid;
+pub mod settle_auction;
+
+pub use {bid::*, commit_bid::*, create_auction::*, refund_sealed_bid::*, reveal_bid::*, settle_auction::*};
@@ -0,0 +1,158 @@
+use anchor_lang::prelude::*;
+use anchor_lang::solana_program::clock::Clock;
+use anchor_spl::token::{Mint, Token};
+
+use crate::{constant::*, states::*, utils::*, error::*};
+
+/// Return a losing or unrevealed sealed bid deposit from escrow to the bidder once reveals are over.
+pub fn refund_sealed_bid(ctx: Context<RefundSealedBid>) -> Result<()> {
+    let bidder = &ctx.accounts.bidder;
+    let receipt_account = &ctx.accounts.receipt_account;
+    let escrow_payment_account = &ctx.accounts.escrow_payment_account;
+    let auction_house = &ctx.accounts.auction_house;
+    let auction_info = ctx.accounts.auction_account.to_account_info();
+    let bid_account = &ctx.accounts.bid_account;
+
+    // A settled or relaunched auction no longer holds this bid, otherwise wait for reveals to end
+    if !auction_info.data_is_empty() {
+        let auction_account: Account<AuctionAccount> = Account::try_from(&auction_info)?;
+        if auction_account.nonce == bid_account.auction_nonce {
+            let now = Clock::get()?.unix_timestamp as u64;
+            require!(now > auction_account.reveal_end_time, MarketplaceError::AuctionNotEnded);
+            require!(
+                auction_account.highest_bidder != bidder.key(),
+                MarketplaceError::InvalidOwner,
+            );
+        }
+    }
+
+    let is_native = ctx.accounts.treasury_mint.key() == spl_token::native_mint::id();
+    if is_native {
+        assert_keys_equal(receipt_account.key(), bidder.key())?;
+    } else {
+        assert_is_ata(receipt_account, &bidder.key(), &ctx.accounts.treasury_mint.key())?;
+    }
+
+    let ah_key = auction_house.key();
+    let wallet_key = bidder.key();
+    let escrow_signer_seeds = [
+        PREFIX,
+        ah_key.as_ref(),
+        wallet_key.as_ref(),
+        &[bump(&[PREFIX, ah_key.as_ref(), wallet_key.as_ref()], ctx.program_id)],
+    ];
+    let ah_seeds = [
+        PREFIX,
+        auction_house.creator.as_ref(),
+        auction_house.treasury_mint.as_ref(),
+        &[bump(&[PREFIX, auction_house.creator.as_ref(), auction_house.treasury_mint.as_ref()], ctx.program_id)],
+    ];
+
+    transfer_from_escrow(
+        &escrow_payment_account.to_account_info(),
+        &receipt_account.to_account_info(),
+        &auction_house.to_account_info(),
+        &ctx.accounts.token_program.to_account_info(),
+        &ctx.accounts.system_program.to_account_info(),
+        if is_native { &escrow_signer_seeds } else { &ah_seeds },
+        bid_account.deposit,
+        is_native,
+    )?;
+
+    let deposit = bid_account.deposit;
+    ctx.accounts.escrow_state.spend(deposit, deposit)?;
+
+    msg!("{{\"refunded\": \"{}\", \"amount\": \"{}\"}}", wallet_key, deposit);
+
+    Ok(())
+}
+
+#[derive(Accounts)]
+pub struct RefundSealedBid<'info> {
+    /// Bidder account.
+    #[account(mut)]
+    pub bidder: Signer<'info>,
+
+    /// CHECK: Validated in refund_sealed_bid.
+    /// Bidder SOL or SPL account to refund to.
+    #[account(mut)]
+    pub receipt_account: UncheckedAccount<'info>,
+
+    /// Treasury mint account, either native SOL mint or a SPL token mint.
+    pub treasury_mint: Account<'info, Mint>,
+
+    /// Authority key for the Auction House.
+    /// CHECK: Validated as a auction house signer.
+    pub authority: UncheckedAccount<'info>,
+
+    /// Auction House instance PDA account.
+    #[account( 
+        seeds=[PREFIX, auction_house.creator.as_ref(), treasury_mint.key().as_ref()], 
+        bump, 
+        has_one=authority, 
+        has_one=treasury_mint,
+    )]
+    pub auction_house: Box<Account<'info, AuctionHouse>>,
+
+    /// CHECK: Not dangerous. Account seeds checked in constraint.
+    /// Bidder escrow payment account PDA.
+    #[account(
+        mut,
+        seeds = [
+            PREFIX,
+            auction_house.key().as_ref(),
+            bidder.key().as_ref()
+        ],
+        bump
+    )]
+    pub escrow_payment_account: UncheckedAccount<'info>,
+
+    /// Bidder escrow ledger PDA account.
+    #[account(
+        mut,
+        seeds = [
+            PREFIX,
+            auction_house.key().as_ref(),
+            bidder.key().as_ref(),
+            ESCROW_STATE
+        ],
+        bump
+    )]
+    pub escrow_state: Box<Account<'info, BuyerEscrowState>>,
+
+    /// NFT mint account
+    /// CHECK: Validated as a nft account.
+    pub nft_mint: UncheckedAccount<'info>,
+
+    /// CHECK: Not dangerous. Account seeds checked in constraint, may already be closed by settlement.
+    /// Auction PDA account
+    #[account(
+        seeds=[
+            PREFIX,
+            auction_house.key().as_ref(),
+            nft_mint.key().as_ref(),
+            AUCTION
+        ],
+        bump,
+    )]
+    pub auction_account: UncheckedAccount<'info>,
+
+    /// Sealed bid PDA account
+    #[account(
+        mut,
+        seeds=[
+            PREFIX,
+            auction_account.key().as_ref(),
+            bid_account.auction_nonce.to_le_bytes().as_ref(),
+            bidder.key().as_ref(),
+            BID
+        ],
+        bump,
+        close = bidder,
+        constraint = bid_account.bidder == bidder.key(),
+    )]
+    pub bid_account: Box<Account<'info, SealedBidAccount>>,
+
+    pub token_program: Program<'info, Token>,
+    pub system_program: Program<'info, System>,
+}
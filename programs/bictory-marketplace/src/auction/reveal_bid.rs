@@ -0,0 +1,88 @@
+use anchor_lang::prelude::*;
+use anchor_lang::solana_program::{clock::Clock, hash::hashv};
+
+use crate::{constant::*, states::*, error::*};
+
+/// Reveal a committed sealed bid, ranking it against the other revealed bids.
+pub fn reveal_bid(ctx: Context<RevealBid>, amount: u64, salt: [u8; 32]) -> Result<()> {
+    let auction_account = &mut ctx.accounts.auction_account;
+    let bid_account = &mut ctx.accounts.bid_account;
+
+    // Check auction is in its reveal phase
+    let now = Clock::get()?.unix_timestamp as u64;
+    require!(
+        auction_account.kind != AuctionKind::English &&
+        auction_account.end_time < now && now <= auction_account.reveal_end_time,
+        MarketplaceError::AuctionNotActive,
+    );
+
+    // Check bid belongs to this auction and matches its commitment
+    require!(
+        !bid_account.revealed &&
+        bid_account.auction_nonce == auction_account.nonce &&
+        hashv(&[&amount.to_le_bytes(), &salt]).to_bytes() == bid_account.commitment,
+        MarketplaceError::InvalidReveal,
+    );
+    require!(amount <= bid_account.deposit, MarketplaceError::InvalidReveal);
+    require!(amount > 0 && amount >= auction_account.reserve_price, MarketplaceError::BidTooLow);
+
+    bid_account.revealed = true;
+    bid_account.amount = amount;
+
+    // Rank bid, earlier reveals win ties
+    if auction_account.highest_bidder == Pubkey::default() || amount > auction_account.highest_bid {
+        auction_account.second_bid = auction_account.highest_bid;
+        auction_account.highest_bidder = bid_account.bidder;
+        auction_account.highest_bid = amount;
+    } else if amount > auction_account.second_bid {
+        auction_account.second_bid = amount;
+    }
+
+    // Log bid detail
+    msg!("{{\"bidder\": \"{}\", \"amount\": \"{}\"}}", bid_account.bidder, amount);
+
+    Ok(())
+}
+
+#[derive(Accounts)]
+pub struct RevealBid<'info> {
+    /// Bidder account.
+    pub bidder: Signer<'info>,
+
+    /// CHECK: Not dangerous. Used in auction account seeds.
+    /// Auction House instance account.
+    pub auction_house: UncheckedAccount<'info>,
+
+    /// NFT mint account
+    /// CHECK: Validated as a nft account.
+    pub nft_mint: UncheckedAccount<'info>,
+
+    /// Auction PDA account
+    #[account(
+        mut,
+        seeds=[
+            PREFIX,
+            auction_house.key().as_ref(),
+            nft_mint.key().as_ref(),
+            AUCTION
+        ],
+        bump,
+        constraint = auction_account.nft_mint == nft_mint.key(),
+    )]
+    pub auction_account: Box<Account<'info, AuctionAccount>>,
+
+    /// Sealed bid PDA account
+    #[account(
+        mut,
+        seeds=[
+            PREFIX,
+            auction_account.key().as_ref(),
+            auction_account.nonce.to_le_bytes().as_ref(),
+            bidder.key().as_ref(),
+            BID
+        ],
+        bump,
+        constraint = bid_account.bidder == bidder.key(),
+    )]
+    pub bid_account: Box<Account<'info, SealedBidAccount>>,
+}
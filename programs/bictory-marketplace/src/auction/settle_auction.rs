@@ -0,0 +1,303 @@
+use anchor_lang::{prelude::*, AccountsClose};
+use anchor_lang::solana_program::clock::Clock;
+use anchor_spl::associated_token::AssociatedToken;
+use anchor_spl::token::{self, SetAuthority, Token, TokenAccount, Transfer};
+use spl_token::instruction::AuthorityType;
+
+use crate::{constant::*, error::*, states::*, utils::*};
+
+#[derive(Accounts)]
+pub struct SettleAuction<'info> {
+    /// Account paying for the settlement, may be anyone once the auction has ended.
+    #[account(mut)]
+    pub payer: Signer<'info>,
+
+    /// CHECK: Validated as the auction owner.
+    /// Seller user wallet account.
+    #[account(mut, constraint = seller.key() == auction_account.owner)]
+    pub seller: UncheckedAccount<'info>,
+
+    /// CHECK: Validated as the auction highest bidder.
+    /// Winning bidder wallet account.
+    #[account(mut, constraint = winner.key() == auction_account.highest_bidder)]
+    pub winner: UncheckedAccount<'info>,
+
+    /// CHECK: Validated in settle_auction.
+    /// Auction House treasury mint account.
+    pub treasury_mint: UncheckedAccount<'info>,
+
+    /// CHECK: Not dangerous. Account seeds checked in constraint.
+    /// Winner escrow payment account PDA.
+    #[account(
+        mut,
+        seeds = [
+            PREFIX,
+            auction_house.key().as_ref(),
+            winner.key().as_ref()
+        ],
+        bump
+    )]
+    pub escrow_payment_account: UncheckedAccount<'info>,
+
+    /// CHECK: Not dangerous. Account seeds checked in constraint, deserialized when there is a winner.
+    /// Winner escrow ledger PDA account.
+    #[account(
+        mut,
+        seeds = [
+            PREFIX,
+            auction_house.key().as_ref(),
+            winner.key().as_ref(),
+            ESCROW_STATE
+        ],
+        bump
+    )]
+    pub escrow_state: UncheckedAccount<'info>,
+
+    /// CHECK: Validated in settle_auction.
+    /// Seller SOL or SPL account to receive payment at.
+    #[account(mut)]
+    pub seller_payment_receipt_account: UncheckedAccount<'info>,
+
+    /// CHECK: Validated in settle_auction.
+    /// Winner SPL token account to receive purchased item at.
+    #[account(mut)]
+    pub winner_receipt_token_account: UncheckedAccount<'info>,
+
+    /// Auction House instance authority account.
+    /// CHECK: Validated as a auction house signer.
+    pub authority: UncheckedAccount<'info>,
+
+    /// Auction House treasury PDA account.
+    /// CHECK: Not dangerous. Account seeds checked in constraint.
+    #[account(mut, seeds=[PREFIX, auction_house.key().as_ref(), TREASURY], bump)]
+    pub auction_house_treasury: UncheckedAccount<'info>,
+
+    /// Auction House instance PDA account.
+    #[account(
+        seeds = [
+            PREFIX,
+            auction_house.creator.as_ref(),
+            auction_house.treasury_mint.as_ref()
+        ],
+        bump,
+        has_one=authority,
+        has_one=treasury_mint,
+        has_one=auction_house_treasury
+    )]
+    pub auction_house: Box<Account<'info, AuctionHouse>>,
+
+    /// CHECK: Validated in settle_auction.
+    /// NFT mint account
+    pub nft_mint: UncheckedAccount<'info>,
+
+    /// NFT token account
+    #[account(mut,
+        constraint = nft_account.mint == nft_mint.key()
+    )]
+    pub nft_account: Box<Account<'info, TokenAccount>>,
+
+    /// CHECK: Validated in settle_auction.
+    /// Metaplex metadata account decorating SPL mint account.
+    pub metadata: UncheckedAccount<'info>,
+
+    /// Auction PDA account
+    #[account(
+        mut,
+        seeds=[
+            PREFIX,
+            auction_house.key().as_ref(),
+            nft_mint.key().as_ref(),
+            AUCTION
+        ],
+        bump,
+        close = seller,
+        constraint = auction_account.nft_mint == nft_mint.key(),
+    )]
+    pub auction_account: Box<Account<'info, AuctionAccount>>,
+
+    /// CHECK: Not dangerous. Account seeds checked in constraint, only used by sealed-bid auctions.
+    /// Winner sealed bid PDA account.
+    #[account(
+        mut,
+        seeds=[
+            PREFIX,
+            auction_account.key().as_ref(),
+            auction_account.nonce.to_le_bytes().as_ref(),
+            winner.key().as_ref(),
+            BID
+        ],
+        bump,
+    )]
+    pub winner_bid_account: UncheckedAccount<'info>,
+
+    pub token_program: Program<'info, Token>,
+    pub system_program: Program<'info, System>,
+    pub ata_program: Program<'info, AssociatedToken>,
+    pub rent: Sysvar<'info, Rent>,
+}
+
+/// Settle an ended auction, selling the NFT to the highest bidder or returning it to the seller.
+pub fn settle_auction<'info>(ctx: Context<'_, '_, '_, 'info, SettleAuction<'info>>) -> Result<()> {
+    let payer = &ctx.accounts.payer;
+    let seller = &ctx.accounts.seller;
+    let winner = &ctx.accounts.winner;
+    let auction_house = &ctx.accounts.auction_house;
+    let auction_house_treasury = &ctx.accounts.auction_house_treasury;
+    let auction_account = &ctx.accounts.auction_account;
+    let treasury_mint = &ctx.accounts.treasury_mint;
+    let nft_mint = &ctx.accounts.nft_mint;
+    let nft_account = &ctx.accounts.nft_account;
+    let winner_receipt_token_account = &ctx.accounts.winner_receipt_token_account;
+    let escrow_payment_account = &ctx.accounts.escrow_payment_account;
+    let system_program = &ctx.accounts.system_program;
+    let ata_program = &ctx.accounts.ata_program;
+    let token_program = &ctx.accounts.token_program;
+    let rent = &ctx.accounts.rent;
+
+    // Check auction has ended, sealed-bid auctions also need their reveal phase over
+    let is_sealed = auction_account.kind != AuctionKind::English;
+    let now = Clock::get()?.unix_timestamp as u64;
+    require!(now > auction_account.end_time, MarketplaceError::AuctionNotEnded);
+    require!(
+        !is_sealed || now > auction_account.reveal_end_time,
+        MarketplaceError::AuctionNotEnded,
+    );
+
+    // Check NFT token ownership (needs to be in treasury account)
+    assert_is_ata2(
+        &nft_account.to_account_info(),
+        &seller.key(),
+        &nft_mint.key(),
+        &auction_house_treasury.key(),
+    )?;
+
+    let ah_key = auction_house.key();
+    let treasury_signer_seeds = &[
+        PREFIX,
+        ah_key.as_ref(),
+        TREASURY,
+        &[bump(&[PREFIX, ah_key.as_ref(), TREASURY], ctx.program_id)],
+    ];
+    let treasury_signer = &[&treasury_signer_seeds[..]];
+
+    // Without bids, give NFT back to seller
+    if auction_account.highest_bidder == Pubkey::default() {
+        let cpi_account = SetAuthority {
+            current_authority: auction_house_treasury.to_account_info(),
+            account_or_mint: nft_account.to_account_info(),
+        };
+        let cpi_ctx = CpiContext::new_with_signer(
+            token_program.to_account_info(),
+            cpi_account,
+            treasury_signer,
+        );
+        token::set_authority(cpi_ctx, AuthorityType::AccountOwner, Some(seller.key()))?;
+
+        msg!("{{\"winner\": null}}");
+        return Ok(());
+    }
+
+    let price = auction_account.clearing_price();
+    let is_native = treasury_mint.key() == spl_token::native_mint::id();
+
+    // Check metadata is correct
+    assert_metadata(&ctx.accounts.metadata.to_account_info(), &nft_mint.key())?;
+
+    let winner_key = winner.key();
+    let escrow_signer_seeds = [
+        PREFIX,
+        ah_key.as_ref(),
+        winner_key.as_ref(),
+        &[bump(&[PREFIX, ah_key.as_ref(), winner_key.as_ref()], ctx.program_id)],
+    ];
+    let ah_seeds = [
+        PREFIX,
+        auction_house.creator.as_ref(),
+        auction_house.treasury_mint.as_ref(),
+        &[bump(&[PREFIX, auction_house.creator.as_ref(), auction_house.treasury_mint.as_ref()], ctx.program_id)],
+    ];
+
+    // with the native account, the escrow is its own owner,
+    // whereas with token, it is the auction house that is owner.
+    let signer_seeds_for_royalties = if is_native {
+        escrow_signer_seeds
+    } else {
+        ah_seeds
+    };
+
+    let remaining_accounts = &mut ctx.remaining_accounts.iter();
+
+    pay_sale_proceeds(
+        remaining_accounts,
+        auction_house,
+        &auction_house_treasury.to_account_info(),
+        &ctx.accounts.metadata.to_account_info(),
+        &escrow_payment_account.to_account_info(),
+        winner,
+        seller,
+        &ctx.accounts.seller_payment_receipt_account.to_account_info(),
+        payer,
+        treasury_mint,
+        &ata_program.to_account_info(),
+        &token_program.to_account_info(),
+        &system_program.to_account_info(),
+        &rent.to_account_info(),
+        &signer_seeds_for_royalties,
+        price,
+        is_native,
+    )?;
+
+    // Check winner NFT ATA is valid
+    if winner_receipt_token_account.data_is_empty() {
+        make_ata(
+            winner_receipt_token_account.to_account_info(),
+            winner.to_account_info(),
+            nft_mint.to_account_info(),
+            payer.to_account_info(),
+            ata_program.to_account_info(),
+            token_program.to_account_info(),
+            system_program.to_account_info(),
+            rent.to_account_info(),
+            &[],
+        )?;
+    }
+
+    let winner_rec_acct = assert_is_ata(winner_receipt_token_account, &winner.key(), &nft_mint.key())?;
+
+    // make sure you cant get rugged
+    if winner_rec_acct.delegate.is_some() {
+        return Err(MarketplaceError::BuyerATACannotHaveDelegate.into());
+    }
+
+    // Transfer NFT to winner
+    let cpi_account = Transfer {
+        from: nft_account.to_account_info(),
+        to: winner_receipt_token_account.to_account_info(),
+        authority: auction_house_treasury.to_account_info(),
+    };
+    let cpi_ctx = CpiContext::new_with_signer(
+        token_program.to_account_info(),
+        cpi_account,
+        treasury_signer,
+    );
+    token::transfer(cpi_ctx, 1)?;
+
+    // Settle the winning bid reservation, sealed bids reserved their whole deposit
+    let committed = if is_sealed {
+        let bid_info = ctx.accounts.winner_bid_account.to_account_info();
+        let bid_account: Account<SealedBidAccount> = Account::try_from(&bid_info)?;
+        let deposit = bid_account.deposit;
+        bid_account.close(winner.to_account_info())?;
+        deposit
+    } else {
+        price
+    };
+    let escrow_state_info = ctx.accounts.escrow_state.to_account_info();
+    let mut escrow_state: Account<BuyerEscrowState> = Account::try_from(&escrow_state_info)?;
+    escrow_state.spend(committed, price)?;
+    escrow_state.exit(ctx.program_id)?;
+
+    msg!("{{\"winner\": \"{}\", \"price\": \"{}\"}}", winner_key, price);
+
+    Ok(())
+}
@@ -7,7 +7,18 @@ use anchor_spl::{
 use crate::{constant::*, states::*, utils::*, error::*};
 
 /// Create a new Auction House instance.
-pub fn create_auction_house(ctx: Context<CreateAuctionHouse>, seller_fee_basis_points: u16, discount_collection: Pubkey, discount_basis_points: u16) -> Result<()> {
+#[allow(clippy::too_many_arguments)]
+pub fn create_auction_house(
+    ctx: Context<CreateAuctionHouse>,
+    seller_fee_basis_points: u16,
+    discount_collection: Pubkey,
+    discount_basis_points: u16,
+    extension_window: u64,
+    extension_period: u64,
+    max_extension: u64,
+    cancel_fee_basis_points: u16,
+    cancel_cooldown: u64,
+) -> Result<()> {
     let treasury_mint = &ctx.accounts.treasury_mint;
     let payer = &ctx.accounts.payer;
     let authority = &ctx.accounts.authority;
@@ -22,6 +33,7 @@ pub fn create_auction_house(ctx: Context<CreateAuctionHouse>, seller_fee_basis_p
 
     require!(seller_fee_basis_points <= 10000, MarketplaceError::InvalidAmount);
     require!(discount_basis_points <= seller_fee_basis_points, MarketplaceError::InvalidAmount);
+    require!(cancel_fee_basis_points <= 10000, MarketplaceError::InvalidAmount);
 
     auction_house.creator = authority.key();
     auction_house.authority = authority.key();
@@ -31,6 +43,11 @@ pub fn create_auction_house(ctx: Context<CreateAuctionHouse>, seller_fee_basis_p
     auction_house.seller_fee_basis_points = seller_fee_basis_points;
     auction_house.discount_collection = discount_collection;
     auction_house.discount_basis_points = discount_basis_points;
+    auction_house.extension_window = extension_window;
+    auction_house.extension_period = extension_period;
+    auction_house.max_extension = max_extension;
+    auction_house.cancel_fee_basis_points = cancel_fee_basis_points;
+    auction_house.cancel_cooldown = cancel_cooldown;
 
     let is_native = treasury_mint.key() == spl_token::native_mint::id();
 
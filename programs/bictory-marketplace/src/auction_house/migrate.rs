@@ -0,0 +1,57 @@
+use anchor_lang::prelude::*;
+use anchor_lang::solana_program::{program::invoke, system_instruction};
+
+use crate::{error::*, states::*, utils::*};
+
+/// Grow an Auction House created before the auction and cancellation settings to the current layout.
+pub fn migrate_auction_house(ctx: Context<MigrateAuctionHouse>) -> Result<()> {
+    let authority = &ctx.accounts.authority;
+    let auction_house_info = ctx.accounts.auction_house.to_account_info();
+    let system_program = &ctx.accounts.system_program;
+
+    assert_owned_by(&auction_house_info, ctx.program_id)?;
+
+    let previous_size = auction_house_info.data_len();
+    let size = 8 + std::mem::size_of::<AuctionHouse>();
+    if previous_size < size {
+        // Top up rent for the larger account
+        let required_lamports = Rent::get()?
+            .minimum_balance(size)
+            .saturating_sub(auction_house_info.lamports());
+        if required_lamports > 0 {
+            invoke(
+                &system_instruction::transfer(&authority.key(), &auction_house_info.key(), required_lamports),
+                &[
+                    authority.to_account_info(),
+                    auction_house_info.clone(),
+                    system_program.to_account_info(),
+                ],
+            )?;
+        }
+
+        // New settings are appended to the layout, so the zeroed tail reads as disabled
+        auction_house_info.realloc(size, false)?;
+    }
+
+    // Check the account is an Auction House of this authority
+    let auction_house: Account<AuctionHouse> = Account::try_from(&auction_house_info)?;
+    require!(auction_house.authority == authority.key(), MarketplaceError::Unauthorized);
+
+    msg!("{{\"previous_size\": {}, \"size\": {}}}", previous_size, size);
+
+    Ok(())
+}
+
+#[derive(Accounts)]
+pub struct MigrateAuctionHouse<'info> {
+    /// Authority key for the Auction House, paying the extra rent.
+    #[account(mut)]
+    pub authority: Signer<'info>,
+
+    /// CHECK: Owner checked in migrate_auction_house, deserialized once grown to the current layout.
+    /// Auction House instance PDA account.
+    #[account(mut)]
+    pub auction_house: UncheckedAccount<'info>,
+
+    pub system_program: Program<'info, System>,
+}
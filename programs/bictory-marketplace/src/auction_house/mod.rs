@@ -1,5 +1,6 @@
 pub mod create;
+pub mod migrate;
 pub mod update;
 pub mod withdraw;
 
-pub use {create::*, update::*, withdraw::*};
+pub use {create::*, migrate::*, update::*, withdraw::*};
@@ -7,11 +7,17 @@ use anchor_spl::{
 use crate::{constant::*, error::*, states::*, utils::*};
 
 /// Update Auction House values such as seller fee basis points, update authority, treasury account, etc.
+#[allow(clippy::too_many_arguments)]
 pub fn update_auction_house(
     ctx: Context<UpdateAuctionHouse>,
     seller_fee_basis_points: Option<u16>,
     discount_collection: Option<Pubkey>,
     discount_basis_points: Option<u16>,
+    extension_window: Option<u64>,
+    extension_period: Option<u64>,
+    max_extension: Option<u64>,
+    cancel_fee_basis_points: Option<u16>,
+    cancel_cooldown: Option<u64>,
 ) -> Result<()> {
     let treasury_mint = &ctx.accounts.treasury_mint;
     let payer = &ctx.accounts.payer;
@@ -38,6 +44,23 @@ pub fn update_auction_house(
 
         auction_house.discount_basis_points = discount_points;
     }
+    if let Some(window) = extension_window {
+        auction_house.extension_window = window;
+    }
+    if let Some(period) = extension_period {
+        auction_house.extension_period = period;
+    }
+    if let Some(max) = max_extension {
+        auction_house.max_extension = max;
+    }
+    if let Some(cfbp) = cancel_fee_basis_points {
+        require!(cfbp <= 10000, MarketplaceError::InvalidAmount);
+
+        auction_house.cancel_fee_basis_points = cfbp;
+    }
+    if let Some(cooldown) = cancel_cooldown {
+        auction_house.cancel_cooldown = cooldown;
+    }
 
     auction_house.authority = new_authority.key();
     auction_house.treasury_withdrawal_destination = treasury_withdrawal_destination.key();
@@ -0,0 +1,312 @@
+use anchor_lang::prelude::*;
+use anchor_lang::solana_program::clock::Clock;
+use anchor_lang::solana_program::account_info::next_account_info;
+use anchor_spl::associated_token::AssociatedToken;
+use anchor_spl::token::{self, Mint, Token, Transfer};
+
+use crate::{constant::*, error::*, states::*, utils::*};
+
+#[derive(Accounts)]
+pub struct BuyBundle<'info> {
+    /// Buyer user wallet account.
+    #[account(mut)]
+    pub buyer: Signer<'info>,
+
+    /// CHECK: Validated as the listing owner.
+    /// Seller user wallet account.
+    #[account(mut, constraint = seller.key() == listing_account.owner)]
+    pub seller: UncheckedAccount<'info>,
+
+    /// Treasury mint account, either native SOL mint or a SPL token mint.
+    pub treasury_mint: Box<Account<'info, Mint>>,
+
+    /// CHECK: Validated in transfer_to_escrow.
+    /// Buyer SOL or SPL account to top up the escrow from when `deposit_shortfall` is set.
+    #[account(mut)]
+    pub payment_account: UncheckedAccount<'info>,
+
+    /// CHECK: Not dangerous. Account seeds checked in constraint.
+    /// Buyer escrow payment account PDA.
+    #[account(
+        mut,
+        seeds = [
+            PREFIX,
+            auction_house.key().as_ref(),
+            buyer.key().as_ref()
+        ],
+        bump
+    )]
+    pub escrow_payment_account: UncheckedAccount<'info>,
+
+    /// Buyer escrow ledger PDA account.
+    #[account(
+        init_if_needed,
+        seeds = [
+            PREFIX,
+            auction_house.key().as_ref(),
+            buyer.key().as_ref(),
+            ESCROW_STATE
+        ],
+        bump,
+        space = 8 + std::mem::size_of::<BuyerEscrowState>(),
+        payer = buyer
+    )]
+    pub escrow_state: Box<Account<'info, BuyerEscrowState>>,
+
+    /// CHECK: Validated in buy_bundle.
+    /// Seller SOL or SPL account to receive payment at.
+    #[account(mut)]
+    pub seller_payment_receipt_account: UncheckedAccount<'info>,
+
+    /// Auction House instance authority account.
+    /// CHECK: Validated as a auction house signer.
+    pub authority: UncheckedAccount<'info>,
+
+    /// Auction House treasury PDA account.
+    /// CHECK: Not dangerous. Account seeds checked in constraint.
+    #[account(mut, seeds=[PREFIX, auction_house.key().as_ref(), TREASURY], bump)]
+    pub auction_house_treasury: UncheckedAccount<'info>,
+
+    /// Auction House instance PDA account.
+    #[account(
+        seeds = [
+            PREFIX,
+            auction_house.creator.as_ref(),
+            auction_house.treasury_mint.as_ref()
+        ],
+        bump,
+        has_one=authority,
+        has_one=treasury_mint,
+        has_one=auction_house_treasury
+    )]
+    pub auction_house: Box<Account<'info, AuctionHouse>>,
+
+    /// Bundle listing PDA account
+    #[account(
+        mut,
+        seeds=[
+            PREFIX,
+            auction_house.key().as_ref(),
+            seller.key().as_ref(),
+            &listing_account.bundle_id.to_le_bytes(),
+            BUNDLE
+        ],
+        bump,
+        close=seller
+    )]
+    pub listing_account: Box<Account<'info, BundleListingAccount>>,
+
+    pub token_program: Program<'info, Token>,
+    pub system_program: Program<'info, System>,
+    pub ata_program: Program<'info, AssociatedToken>,
+    pub rent: Sysvar<'info, Rent>,
+}
+
+/// Buy every NFT of a bundle listing at its total price, paid from the buyer escrow.
+/// Remaining accounts hold, per item in listing order, (nft_mint, metadata, nft_account, buyer_receipt_token_account)
+/// followed by the item creators, then the optional discount NFT accounts.
+pub fn buy_bundle<'info>(
+    ctx: Context<'_, '_, '_, 'info, BuyBundle<'info>>,
+    deposit_shortfall: bool,
+) -> Result<()> {
+    let listing_account = &ctx.accounts.listing_account;
+
+    // Check listing is not expired
+    let now = Clock::get()?.unix_timestamp as u64;
+    require!(
+        listing_account.expiry == 0 || now <= listing_account.expiry,
+        MarketplaceError::Expired,
+    );
+
+    let price = listing_account.price;
+    let is_native = ctx.accounts.treasury_mint.key() == spl_token::native_mint::id();
+    let ah_key = ctx.accounts.auction_house.key();
+    let wallet_key = ctx.accounts.buyer.key();
+    let escrow_signer_seeds = [
+        PREFIX,
+        ah_key.as_ref(),
+        wallet_key.as_ref(),
+        &[bump(&[PREFIX, ah_key.as_ref(), wallet_key.as_ref()], ctx.program_id)],
+    ];
+
+    // If first time to use the escrow ledger
+    if is_zero_account(&ctx.accounts.escrow_state.to_account_info()) {
+        ctx.accounts.escrow_state.wallet = wallet_key;
+        ctx.accounts.escrow_state.auction_house = ah_key;
+    }
+    let balance = escrow_balance(&ctx.accounts.escrow_payment_account.to_account_info(), is_native)?;
+    ctx.accounts.escrow_state.sync(balance)?;
+
+    // Check free escrow covers the purchase, topping it up first if requested
+    let free = ctx.accounts.escrow_state.free;
+    if free < price {
+        require!(deposit_shortfall, MarketplaceError::InsufficientEscrow);
+
+        transfer_to_escrow(
+            &ctx.accounts.buyer.to_account_info(),
+            &ctx.accounts.payment_account,
+            &ctx.accounts.escrow_payment_account,
+            &ctx.accounts.treasury_mint,
+            &ctx.accounts.auction_house.to_account_info(),
+            &ctx.accounts.token_program,
+            &ctx.accounts.system_program,
+            &ctx.accounts.rent,
+            &escrow_signer_seeds,
+            price - free,
+            is_native,
+        )?;
+        ctx.accounts.escrow_state.deposit(price - free)?;
+    }
+
+    let buyer = &ctx.accounts.buyer;
+    let seller = &ctx.accounts.seller;
+    let listing_account = &ctx.accounts.listing_account;
+    let auction_house = &ctx.accounts.auction_house;
+    let auction_house_treasury = &ctx.accounts.auction_house_treasury;
+    let escrow_payment_account = &ctx.accounts.escrow_payment_account.to_account_info();
+    let treasury_mint = &ctx.accounts.treasury_mint.to_account_info();
+    let system_program = &ctx.accounts.system_program;
+    let ata_program = &ctx.accounts.ata_program;
+    let token_program = &ctx.accounts.token_program;
+    let rent = &ctx.accounts.rent;
+
+    let ah_seeds = [
+        PREFIX,
+        auction_house.creator.as_ref(),
+        auction_house.treasury_mint.as_ref(),
+        &[bump(&[PREFIX, auction_house.creator.as_ref(), auction_house.treasury_mint.as_ref()], ctx.program_id)],
+    ];
+
+    // with the native account, the escrow is its own owner,
+    // whereas with token, it is the auction house that is owner.
+    let signer_seeds_for_royalties = if is_native {
+        escrow_signer_seeds
+    } else {
+        ah_seeds
+    };
+
+    let treasury_signer_seeds = &[
+        PREFIX,
+        ah_key.as_ref(),
+        TREASURY,
+        &[bump(&[PREFIX, ah_key.as_ref(), TREASURY], ctx.program_id)],
+    ];
+    let treasury_signer = &[&treasury_signer_seeds[..]];
+
+    let remaining_accounts = &mut ctx.remaining_accounts.iter();
+    let mut buyer_leftover_after_royalties = 0u64;
+
+    for index in 0..listing_account.count as usize {
+        let nft_mint = next_account_info(remaining_accounts)?;
+        let metadata = next_account_info(remaining_accounts)?;
+        let nft_account = next_account_info(remaining_accounts)?;
+        let buyer_receipt_token_account = next_account_info(remaining_accounts)?;
+
+        // Check item belongs to the bundle and its metadata is correct
+        assert_keys_equal(nft_mint.key(), listing_account.mints[index])?;
+        assert_keys_equal(nft_account.key(), listing_account.token_accounts[index])?;
+        assert_metadata(metadata, nft_mint.key)?;
+
+        // Royalties pro-rated by the item allocation
+        buyer_leftover_after_royalties = buyer_leftover_after_royalties
+            .checked_add(pay_creator_fees(
+                remaining_accounts,
+                metadata,
+                escrow_payment_account,
+                &auction_house.to_account_info(),
+                &buyer.to_account_info(),
+                treasury_mint,
+                &ata_program.to_account_info(),
+                &token_program.to_account_info(),
+                &system_program.to_account_info(),
+                &rent.to_account_info(),
+                &signer_seeds_for_royalties,
+                &[],
+                listing_account.item_price(index)?,
+                is_native,
+            )?)
+            .ok_or(MarketplaceError::NumericalOverflow)?;
+
+        // Check buyer ATA is valid
+        if buyer_receipt_token_account.data_is_empty() {
+            make_ata(
+                buyer_receipt_token_account.to_account_info(),
+                buyer.to_account_info(),
+                nft_mint.to_account_info(),
+                buyer.to_account_info(),
+                ata_program.to_account_info(),
+                token_program.to_account_info(),
+                system_program.to_account_info(),
+                rent.to_account_info(),
+                &[],
+            )?;
+        }
+
+        let buyer_rec_acct = assert_is_ata(buyer_receipt_token_account, &wallet_key, nft_mint.key)?;
+
+        // make sure you cant get rugged
+        if buyer_rec_acct.delegate.is_some() {
+            return Err(MarketplaceError::BuyerATACannotHaveDelegate.into());
+        }
+
+        // Transfer NFT to buyer
+        let cpi_account = Transfer {
+            from: nft_account.clone(),
+            to: buyer_receipt_token_account.clone(),
+            authority: auction_house_treasury.to_account_info(),
+        };
+        token::transfer(
+            CpiContext::new_with_signer(token_program.to_account_info(), cpi_account, treasury_signer),
+            1,
+        )?;
+    }
+
+    // Check discount NFT metadata
+    let is_discount = check_discount(remaining_accounts, buyer.key, auction_house)?;
+
+    msg!("Discount: {}", is_discount);
+
+    let auction_house_fee_paid = pay_auction_house_fees(
+        auction_house,
+        &auction_house_treasury.to_account_info(),
+        escrow_payment_account,
+        &token_program.to_account_info(),
+        &system_program.to_account_info(),
+        &signer_seeds_for_royalties,
+        price,
+        is_native,
+        is_discount,
+    )?;
+
+    let buyer_leftover_after_royalties_and_house_fee = buyer_leftover_after_royalties
+        .checked_sub(auction_house_fee_paid)
+        .ok_or(MarketplaceError::NumericalOverflow)?;
+
+    pay_seller(
+        &auction_house.to_account_info(),
+        escrow_payment_account,
+        seller,
+        &ctx.accounts.seller_payment_receipt_account,
+        &buyer.to_account_info(),
+        treasury_mint,
+        &ata_program.to_account_info(),
+        &token_program.to_account_info(),
+        &system_program.to_account_info(),
+        &rent.to_account_info(),
+        &signer_seeds_for_royalties,
+        buyer_leftover_after_royalties_and_house_fee,
+        is_native,
+    )?;
+
+    // Purchase is paid straight out of free escrow
+    ctx.accounts.escrow_state.withdraw(price)?;
+
+    msg!(
+        "{{\"buyer\": \"{}\", \"bundle_id\": {}, \"price\": \"{}\"}}",
+        wallet_key,
+        listing_account.bundle_id,
+        price
+    );
+
+    Ok(())
+}
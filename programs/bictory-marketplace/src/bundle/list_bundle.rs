@@ -0,0 +1,145 @@
+use anchor_lang::prelude::*;
+use anchor_lang::solana_program::clock::Clock;
+use anchor_spl::token::{self, Mint, Token, TokenAccount, SetAuthority};
+use spl_token::instruction::AuthorityType;
+
+use crate::{constant::*, states::*, utils::*, error::*};
+
+/// List several NFTs as one bundle with a total price, handing each of them over to the treasury.
+/// Remaining accounts hold (nft_mint, nft_account, metadata, edition) per item, in the order of `allocations`.
+pub fn list_bundle<'info>(
+    ctx: Context<'_, '_, '_, 'info, ListBundle<'info>>,
+    bundle_id: u64,
+    price: u64,
+    allocations: Vec<u16>,
+    seller_expiry: Option<u64>,
+) -> Result<()> {
+    // Check bundle size and allocations
+    let count = allocations.len();
+    require!(
+        count > 0 && count <= MAX_BUNDLE_ITEMS && ctx.remaining_accounts.len() == count * 4,
+        MarketplaceError::InvalidBundle,
+    );
+    require!(
+        allocations.iter().map(|bp| *bp as u64).sum::<u64>() == 10000,
+        MarketplaceError::InvalidBundle,
+    );
+
+    // Check expiry date
+    let mut _expiry = 0;
+    if let Some(expiry) = seller_expiry {
+        let now = Clock::get()?.unix_timestamp as u64;
+        require!(expiry >= now, MarketplaceError::InvalidExpiry);
+
+        _expiry = expiry;
+    }
+
+    let seller = &ctx.accounts.seller;
+    let listing_account = &mut ctx.accounts.listing_account;
+
+    for (index, item) in ctx.remaining_accounts.chunks(4).enumerate() {
+        let nft_mint_info = &item[0];
+        let nft_account_info = &item[1];
+
+        // Check item is a master edition NFT
+        let nft_mint: Account<Mint> = Account::try_from(nft_mint_info)?;
+        assert_nft_mint(&nft_mint, &item[2], &item[3])?;
+
+        // Check NFT is held by the seller and not already in the bundle
+        let nft_account: Account<TokenAccount> = Account::try_from(nft_account_info)?;
+        require!(
+            nft_account.mint == nft_mint.key() &&
+            nft_account.owner == seller.key() &&
+            nft_account.amount == 1,
+            MarketplaceError::InvalidBundle,
+        );
+        require!(
+            !listing_account.mints[..index].contains(&nft_mint.key()),
+            MarketplaceError::InvalidBundle,
+        );
+
+        // Set nft's authority to treasury account
+        let cpi_account = SetAuthority {
+            current_authority: seller.to_account_info(),
+            account_or_mint: nft_account_info.clone(),
+        };
+        token::set_authority(
+            CpiContext::new(ctx.accounts.token_program.to_account_info(), cpi_account),
+            AuthorityType::AccountOwner,
+            Some(ctx.accounts.auction_house_treasury.key()),
+        )?;
+
+        listing_account.mints[index] = nft_mint.key();
+        listing_account.token_accounts[index] = nft_account_info.key();
+        listing_account.allocations[index] = allocations[index];
+    }
+
+    // Fill listing account
+    listing_account.owner = seller.key();
+    listing_account.auction_house = ctx.accounts.auction_house.key();
+    listing_account.bundle_id = bundle_id;
+    listing_account.price = price;
+    listing_account.expiry = _expiry;
+    listing_account.count = count as u8;
+
+    // Log listing detail
+    msg!(
+        "{{\"bundle_id\": {}, \"price\": \"{}\", \"items\": {}, \"seller_expiry\": {}}}",
+        bundle_id,
+        price,
+        count,
+        _expiry
+    );
+
+    Ok(())
+}
+
+#[derive(Accounts)]
+#[instruction(bundle_id: u64)]
+pub struct ListBundle<'info> {
+    /// Seller account.
+    #[account(mut)]
+    pub seller: Signer<'info>,
+
+    /// Treasury mint account, either native SOL mint or a SPL token mint.
+    pub treasury_mint: Account<'info, Mint>,
+
+    /// Authority key for the Auction House.
+    /// CHECK: Validated as a auction house signer.
+    pub authority: UncheckedAccount<'info>,
+
+    /// Auction House treasury PDA account.
+    /// CHECK: Not dangerous. Account seeds checked in constraint.
+    #[account(seeds=[PREFIX, auction_house.key().as_ref(), TREASURY], bump)]
+    pub auction_house_treasury: UncheckedAccount<'info>,
+
+    /// Auction House instance PDA account.
+    #[account( 
+        seeds=[PREFIX, auction_house.creator.as_ref(), treasury_mint.key().as_ref()], 
+        bump, 
+        has_one=authority, 
+        has_one=treasury_mint, 
+        has_one=auction_house_treasury
+    )]
+    pub auction_house: Box<Account<'info, AuctionHouse>>,
+
+    /// Bundle listing PDA account
+    #[account(
+        init,
+        seeds=[
+            PREFIX,
+            auction_house.key().as_ref(),
+            seller.key().as_ref(),
+            &bundle_id.to_le_bytes(),
+            BUNDLE
+        ],
+        bump, 
+        space=8 + std::mem::size_of::<BundleListingAccount>(),
+        payer=seller
+    )]
+    pub listing_account: Box<Account<'info, BundleListingAccount>>,
+
+    pub token_program: Program<'info, Token>,
+    pub system_program: Program<'info, System>,
+    pub rent: Sysvar<'info, Rent>,
+}
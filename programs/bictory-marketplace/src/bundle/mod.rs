@@ -0,0 +1,5 @@
+pub mod buy_bundle;
+pub mod list_bundle;
+pub mod unlist_bundle;
+
+pub use {buy_bundle::*, list_bundle::*, unlist_bundle::*};
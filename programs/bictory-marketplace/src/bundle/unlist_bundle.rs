@@ -0,0 +1,91 @@
+use anchor_lang::prelude::*;
+use anchor_spl::token::{self, Mint, Token, SetAuthority};
+use spl_token::instruction::AuthorityType;
+
+use crate::{constant::*, states::*, utils::*, error::*};
+
+/// Hand every NFT of a bundle back to the seller and close the bundle listing.
+/// Remaining accounts hold the nft_account of each item, in listing order.
+pub fn unlist_bundle<'info>(ctx: Context<'_, '_, '_, 'info, UnlistBundle<'info>>) -> Result<()> {
+    let listing_account = &ctx.accounts.listing_account;
+    let count = listing_account.count as usize;
+    require!(
+        ctx.remaining_accounts.len() == count,
+        MarketplaceError::InvalidBundle,
+    );
+
+    let ah_key = ctx.accounts.auction_house.key();
+    let treasury_signer_seeds = &[
+        PREFIX,
+        ah_key.as_ref(),
+        TREASURY,
+        &[bump(&[PREFIX, ah_key.as_ref(), TREASURY], ctx.program_id)],
+    ];
+    let treasury_signer = &[&treasury_signer_seeds[..]];
+
+    for (index, nft_account_info) in ctx.remaining_accounts.iter().enumerate() {
+        // Check NFT account is the one the item was listed from
+        assert_keys_equal(nft_account_info.key(), listing_account.token_accounts[index])?;
+
+        // Set nft's authority to owner
+        let cpi_account = SetAuthority {
+            current_authority: ctx.accounts.auction_house_treasury.to_account_info(),
+            account_or_mint: nft_account_info.clone(),
+        };
+        token::set_authority(
+            CpiContext::new_with_signer(ctx.accounts.token_program.to_account_info(), cpi_account, treasury_signer),
+            AuthorityType::AccountOwner,
+            Some(ctx.accounts.seller.key()),
+        )?;
+    }
+
+    msg!("{{\"bundle_id\": {}}}", listing_account.bundle_id);
+
+    Ok(())
+}
+
+#[derive(Accounts)]
+pub struct UnlistBundle<'info> {
+    /// Seller account.
+    #[account(mut)]
+    pub seller: Signer<'info>,
+
+    /// Treasury mint account, either native SOL mint or a SPL token mint.
+    pub treasury_mint: Account<'info, Mint>,
+
+    /// Authority key for the Auction House.
+    /// CHECK: Validated as a auction house signer.
+    pub authority: UncheckedAccount<'info>,
+
+    /// Auction House treasury PDA account.
+    /// CHECK: Not dangerous. Account seeds checked in constraint.
+    #[account(seeds=[PREFIX, auction_house.key().as_ref(), TREASURY], bump)]
+    pub auction_house_treasury: UncheckedAccount<'info>,
+
+    /// Auction House instance PDA account.
+    #[account( 
+        seeds=[PREFIX, auction_house.creator.as_ref(), treasury_mint.key().as_ref()], 
+        bump, 
+        has_one=authority, 
+        has_one=treasury_mint, 
+        has_one=auction_house_treasury
+    )]
+    pub auction_house: Box<Account<'info, AuctionHouse>>,
+
+    /// Bundle listing PDA account
+    #[account(
+        mut,
+        seeds=[
+            PREFIX,
+            auction_house.key().as_ref(),
+            seller.key().as_ref(),
+            &listing_account.bundle_id.to_le_bytes(),
+            BUNDLE
+        ],
+        bump,
+        close=seller
+    )]
+    pub listing_account: Box<Account<'info, BundleListingAccount>>,
+
+    pub token_program: Program<'info, Token>,
+}
@@ -0,0 +1,177 @@
+use anchor_lang::prelude::*;
+use anchor_lang::solana_program::clock::Clock;
+use anchor_spl::token::{Mint, Token};
+
+use crate::{constant::*, states::*, utils::*, error::*};
+
+/// Offer to buy `quantity` NFTs of a verified collection at `price` each, reserving the total from the buyer escrow.
+pub fn make_collection_offer(
+    ctx: Context<MakeCollectionOffer>,
+    price: u64,
+    quantity: u64,
+    buyer_expiry: Option<u64>,
+    deposit_shortfall: bool,
+) -> Result<()> {
+    require!(price > 0 && quantity > 0, MarketplaceError::InvalidAmount);
+
+    // Check expiry date
+    let mut _expiry = 0;
+    if let Some(expiry) = buyer_expiry {
+        let now = Clock::get()?.unix_timestamp as u64;
+        require!(expiry >= now, MarketplaceError::InvalidExpiry);
+
+        _expiry = expiry;
+    }
+
+    let is_native = ctx.accounts.treasury_mint.key() == spl_token::native_mint::id();
+
+    let ah_key = ctx.accounts.auction_house.key();
+    let wallet_key = ctx.accounts.buyer.key();
+
+    // If first time to use the escrow ledger
+    if is_zero_account(&ctx.accounts.escrow_state.to_account_info()) {
+        ctx.accounts.escrow_state.wallet = wallet_key;
+        ctx.accounts.escrow_state.auction_house = ah_key;
+    }
+    let balance = escrow_balance(&ctx.accounts.escrow_payment_account.to_account_info(), is_native)?;
+    ctx.accounts.escrow_state.sync(balance)?;
+
+    // If first time to offer
+    if is_zero_account(&ctx.accounts.offer_account.to_account_info()) {
+
+        // Fill offer account
+        ctx.accounts.offer_account.buyer = wallet_key;
+        ctx.accounts.offer_account.auction_house = ah_key;
+        ctx.accounts.offer_account.collection = ctx.accounts.collection.key();
+    } else {
+        // Release the previous reservation before committing the new total
+        let previous = ctx.accounts.offer_account.escrow_amount()?;
+        ctx.accounts.escrow_state.release(previous)?;
+    }
+
+    ctx.accounts.offer_account.price = price;
+    ctx.accounts.offer_account.quantity = quantity;
+    ctx.accounts.offer_account.expiry = _expiry;
+    let total = ctx.accounts.offer_account.escrow_amount()?;
+
+    // Check free escrow covers the offer, topping it up first if requested
+    let free = ctx.accounts.escrow_state.free;
+    if free < total {
+        require!(deposit_shortfall, MarketplaceError::InsufficientEscrow);
+
+        let escrow_signer_seeds = [
+            PREFIX,
+            ah_key.as_ref(),
+            wallet_key.as_ref(),
+            &[bump(&[PREFIX, ah_key.as_ref(), wallet_key.as_ref()], ctx.program_id)],
+        ];
+
+        transfer_to_escrow(
+            &ctx.accounts.buyer.to_account_info(),
+            &ctx.accounts.payment_account,
+            &ctx.accounts.escrow_payment_account,
+            &ctx.accounts.treasury_mint,
+            &ctx.accounts.auction_house.to_account_info(),
+            &ctx.accounts.token_program,
+            &ctx.accounts.system_program,
+            &ctx.accounts.rent,
+            &escrow_signer_seeds,
+            total - free,
+            is_native,
+        )?;
+        ctx.accounts.escrow_state.deposit(total - free)?;
+    }
+    ctx.accounts.escrow_state.commit(total)?;
+
+    // Log offer detail
+    msg!(
+        "{{\"collection\": \"{}\", \"price\": \"{}\", \"quantity\": {}, \"buyer_expiry\": {}, \"escrow_amount\": \"{}\"}}",
+        ctx.accounts.collection.key(),
+        price,
+        quantity,
+        _expiry,
+        total
+    );
+
+    Ok(())
+}
+
+#[derive(Accounts)]
+pub struct MakeCollectionOffer<'info> {
+    /// Buyer account.
+    #[account(mut)]
+    pub buyer: Signer<'info>,
+
+    /// Treasury mint account, either native SOL mint or a SPL token mint.
+    pub treasury_mint: Account<'info, Mint>,
+
+    /// Authority key for the Auction House.
+    /// CHECK: Validated as a auction house signer.
+    pub authority: UncheckedAccount<'info>,
+
+    /// Auction House instance PDA account.
+    #[account( 
+        seeds=[PREFIX, auction_house.creator.as_ref(), treasury_mint.key().as_ref()], 
+        bump, 
+        has_one=authority, 
+        has_one=treasury_mint,
+    )]
+    pub auction_house: Box<Account<'info, AuctionHouse>>,
+
+    /// CHECK: Validated in transfer_to_escrow.
+    /// Buyer SOL or SPL account to top up the escrow from when `deposit_shortfall` is set.
+    #[account(mut)]
+    pub payment_account: UncheckedAccount<'info>,
+
+    /// CHECK: Not dangerous. Account seeds checked in constraint.
+    /// Buyer escrow payment account PDA.
+    #[account(
+        mut,
+        seeds = [
+            PREFIX,
+            auction_house.key().as_ref(),
+            buyer.key().as_ref()
+        ],
+        bump
+    )]
+    pub escrow_payment_account: UncheckedAccount<'info>,
+
+    /// Buyer escrow ledger PDA account.
+    #[account(
+        init_if_needed,
+        seeds = [
+            PREFIX,
+            auction_house.key().as_ref(),
+            buyer.key().as_ref(),
+            ESCROW_STATE
+        ],
+        bump,
+        space = 8 + std::mem::size_of::<BuyerEscrowState>(),
+        payer = buyer
+    )]
+    pub escrow_state: Box<Account<'info, BuyerEscrowState>>,
+
+    /// CHECK: Not dangerous. Collection key matched against verified NFT metadata on sale.
+    /// Metaplex collection mint account.
+    pub collection: UncheckedAccount<'info>,
+
+    /// Collection offer PDA account
+    #[account(
+        init_if_needed,
+        seeds=[
+            PREFIX,
+            auction_house.key().as_ref(),
+            collection.key().as_ref(),
+            buyer.key().as_ref(),
+            COLLECTION_OFFER
+        ],
+        bump, 
+        space=8 + std::mem::size_of::<CollectionOfferAccount>(),
+        payer=buyer,
+    )]
+    pub offer_account: Box<Account<'info, CollectionOfferAccount>>,
+
+    pub token_program: Program<'info, Token>,
+    pub system_program: Program<'info, System>,
+    pub rent: Sysvar<'info, Rent>,
+}
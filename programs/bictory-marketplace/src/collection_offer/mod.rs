@@ -0,0 +1,5 @@
+pub mod cancel_collection_offer;
+pub mod make_collection_offer;
+pub mod sell_into_collection_offer;
+
+pub use {cancel_collection_offer::*, make_collection_offer::*, sell_into_collection_offer::*};
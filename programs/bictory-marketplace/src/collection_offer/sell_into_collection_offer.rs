@@ -0,0 +1,257 @@
+use anchor_lang::{prelude::*, AccountsClose};
+use anchor_lang::solana_program::clock::Clock;
+use anchor_spl::associated_token::AssociatedToken;
+use anchor_spl::token::{self, Token, TokenAccount, Transfer};
+use mpl_token_metadata::state::Metadata;
+
+use crate::{constant::*, error::*, states::*, utils::*};
+
+#[derive(Accounts)]
+pub struct SellIntoCollectionOffer<'info> {
+    /// Seller user wallet account.
+    #[account(mut)]
+    pub seller: Signer<'info>,
+
+    /// CHECK: Validated as the collection offer buyer.
+    /// Buyer user wallet account.
+    #[account(mut, constraint = buyer.key() == offer_account.buyer)]
+    pub buyer: UncheckedAccount<'info>,
+
+    /// CHECK: Validated in sell_into_collection_offer.
+    /// Auction House treasury mint account.
+    pub treasury_mint: UncheckedAccount<'info>,
+
+    /// CHECK: Not dangerous. Account seeds checked in constraint.
+    /// Buyer escrow payment account PDA.
+    #[account(
+        mut,
+        seeds = [
+            PREFIX,
+            auction_house.key().as_ref(),
+            buyer.key().as_ref()
+        ],
+        bump
+    )]
+    pub escrow_payment_account: UncheckedAccount<'info>,
+
+    /// Buyer escrow ledger PDA account.
+    #[account(
+        mut,
+        seeds = [
+            PREFIX,
+            auction_house.key().as_ref(),
+            buyer.key().as_ref(),
+            ESCROW_STATE
+        ],
+        bump
+    )]
+    pub escrow_state: Box<Account<'info, BuyerEscrowState>>,
+
+    /// CHECK: Validated in sell_into_collection_offer.
+    /// Seller SOL or SPL account to receive payment at.
+    #[account(mut)]
+    pub seller_payment_receipt_account: UncheckedAccount<'info>,
+
+    /// CHECK: Validated in sell_into_collection_offer.
+    /// Buyer SPL token account to receive purchased item at.
+    #[account(mut)]
+    pub buyer_receipt_token_account: UncheckedAccount<'info>,
+
+    /// Auction House instance authority account.
+    /// CHECK: Validated as a auction house signer.
+    pub authority: UncheckedAccount<'info>,
+
+    /// Auction House treasury PDA account.
+    /// CHECK: Not dangerous. Account seeds checked in constraint.
+    #[account(mut, seeds=[PREFIX, auction_house.key().as_ref(), TREASURY], bump)]
+    pub auction_house_treasury: UncheckedAccount<'info>,
+
+    /// Auction House instance PDA account.
+    #[account(
+        seeds = [
+            PREFIX,
+            auction_house.creator.as_ref(),
+            auction_house.treasury_mint.as_ref()
+        ],
+        bump,
+        has_one=authority,
+        has_one=treasury_mint,
+        has_one=auction_house_treasury
+    )]
+    pub auction_house: Box<Account<'info, AuctionHouse>>,
+
+    /// CHECK: Not dangerous. Used in offer account seeds, matched against the NFT metadata.
+    /// Metaplex collection mint account.
+    pub collection: UncheckedAccount<'info>,
+
+    /// Collection offer PDA account
+    #[account(
+        mut,
+        seeds=[
+            PREFIX,
+            auction_house.key().as_ref(),
+            collection.key().as_ref(),
+            buyer.key().as_ref(),
+            COLLECTION_OFFER
+        ],
+        bump,
+    )]
+    pub offer_account: Box<Account<'info, CollectionOfferAccount>>,
+
+    /// CHECK: Validated in sell_into_collection_offer.
+    /// NFT mint account
+    pub nft_mint: UncheckedAccount<'info>,
+
+    /// Seller NFT token account
+    #[account(mut,
+        constraint = nft_account.mint == nft_mint.key(),
+        constraint = nft_account.owner == seller.key(),
+    )]
+    pub nft_account: Box<Account<'info, TokenAccount>>,
+
+    /// CHECK: Validated in sell_into_collection_offer.
+    /// Metaplex metadata account decorating SPL mint account.
+    pub metadata: UncheckedAccount<'info>,
+
+    pub token_program: Program<'info, Token>,
+    pub system_program: Program<'info, System>,
+    pub ata_program: Program<'info, AssociatedToken>,
+    pub rent: Sysvar<'info, Rent>,
+}
+
+/// Sell a verified collection NFT into a collection offer at the offer price.
+pub fn sell_into_collection_offer<'info>(
+    ctx: Context<'_, '_, '_, 'info, SellIntoCollectionOffer<'info>>,
+) -> Result<()> {
+    let seller = &ctx.accounts.seller;
+    let buyer = &ctx.accounts.buyer;
+    let auction_house = &ctx.accounts.auction_house;
+    let offer_account = &ctx.accounts.offer_account;
+    let treasury_mint = &ctx.accounts.treasury_mint;
+    let nft_mint = &ctx.accounts.nft_mint;
+    let nft_account = &ctx.accounts.nft_account;
+    let metadata = &ctx.accounts.metadata;
+    let buyer_receipt_token_account = &ctx.accounts.buyer_receipt_token_account;
+    let system_program = &ctx.accounts.system_program;
+    let ata_program = &ctx.accounts.ata_program;
+    let token_program = &ctx.accounts.token_program;
+    let rent = &ctx.accounts.rent;
+
+    // Check offer is still open
+    let now = Clock::get()?.unix_timestamp as u64;
+    require!(
+        offer_account.expiry == 0 || now <= offer_account.expiry,
+        MarketplaceError::Expired,
+    );
+    require!(offer_account.quantity > 0, MarketplaceError::InvalidAmount);
+
+    // Check NFT balance
+    require!(nft_account.amount > 0, MarketplaceError::InvalidAmount);
+
+    // Check NFT is a verified member of the offer collection
+    assert_metadata(&metadata.to_account_info(), &nft_mint.key())?;
+    let nft_metadata = Metadata::from_account_info(&metadata.to_account_info())?;
+    match nft_metadata.collection {
+        Some(collection) => require!(
+            collection.verified && collection.key == offer_account.collection,
+            MarketplaceError::InvalidCollection
+        ),
+        None => return Err(MarketplaceError::InvalidCollection.into()),
+    }
+
+    let price = offer_account.price;
+    let is_native = treasury_mint.key() == spl_token::native_mint::id();
+
+    let ah_key = auction_house.key();
+    let buyer_key = buyer.key();
+    let escrow_signer_seeds = [
+        PREFIX,
+        ah_key.as_ref(),
+        buyer_key.as_ref(),
+        &[bump(&[PREFIX, ah_key.as_ref(), buyer_key.as_ref()], ctx.program_id)],
+    ];
+    let ah_seeds = [
+        PREFIX,
+        auction_house.creator.as_ref(),
+        auction_house.treasury_mint.as_ref(),
+        &[bump(&[PREFIX, auction_house.creator.as_ref(), auction_house.treasury_mint.as_ref()], ctx.program_id)],
+    ];
+
+    // with the native account, the escrow is its own owner,
+    // whereas with token, it is the auction house that is owner.
+    let signer_seeds_for_royalties = if is_native {
+        escrow_signer_seeds
+    } else {
+        ah_seeds
+    };
+
+    let remaining_accounts = &mut ctx.remaining_accounts.iter();
+
+    pay_sale_proceeds(
+        remaining_accounts,
+        auction_house,
+        &ctx.accounts.auction_house_treasury.to_account_info(),
+        &metadata.to_account_info(),
+        &ctx.accounts.escrow_payment_account.to_account_info(),
+        buyer,
+        seller,
+        &ctx.accounts.seller_payment_receipt_account.to_account_info(),
+        seller,
+        treasury_mint,
+        &ata_program.to_account_info(),
+        &token_program.to_account_info(),
+        &system_program.to_account_info(),
+        &rent.to_account_info(),
+        &signer_seeds_for_royalties,
+        price,
+        is_native,
+    )?;
+
+    // Check buyer NFT ATA is valid
+    if buyer_receipt_token_account.data_is_empty() {
+        make_ata(
+            buyer_receipt_token_account.to_account_info(),
+            buyer.to_account_info(),
+            nft_mint.to_account_info(),
+            seller.to_account_info(),
+            ata_program.to_account_info(),
+            token_program.to_account_info(),
+            system_program.to_account_info(),
+            rent.to_account_info(),
+            &[],
+        )?;
+    }
+
+    let buyer_rec_acct = assert_is_ata(buyer_receipt_token_account, &buyer_key, &nft_mint.key())?;
+
+    // make sure you cant get rugged
+    if buyer_rec_acct.delegate.is_some() {
+        return Err(MarketplaceError::BuyerATACannotHaveDelegate.into());
+    }
+
+    // Transfer NFT to buyer
+    let cpi_account = Transfer {
+        from: nft_account.to_account_info(),
+        to: buyer_receipt_token_account.to_account_info(),
+        authority: seller.to_account_info(),
+    };
+    token::transfer(CpiContext::new(token_program.to_account_info(), cpi_account), 1)?;
+
+    // Pay one unit of the reservation and close the offer once filled
+    ctx.accounts.escrow_state.spend(price, price)?;
+    let offer_account = &mut ctx.accounts.offer_account;
+    offer_account.quantity -= 1;
+
+    msg!(
+        "{{\"nft_mint\": \"{}\", \"price\": \"{}\", \"remaining_quantity\": {}}}",
+        nft_mint.key(),
+        price,
+        offer_account.quantity
+    );
+
+    if offer_account.quantity == 0 {
+        offer_account.close(buyer.to_account_info())?;
+    }
+
+    Ok(())
+}
@@ -4,4 +4,33 @@ pub const TREASURY: &[u8] = b"treasury";
 
 pub const LISTING: &[u8] = b"listing";
 
+pub const QUANTITY_LISTING: &[u8] = b"quantity_listing";
+
+pub const VAULT: &[u8] = b"vault";
+
+pub const BUNDLE: &[u8] = b"bundle";
+
+/// Maximum number of NFTs held by a single bundle listing.
+pub const MAX_BUNDLE_ITEMS: usize = 5;
+
 pub const OFFER: &[u8] = b"offer";
+
+pub const SWAP: &[u8] = b"swap";
+
+/// Maximum number of NFTs on each side of a swap offer.
+pub const MAX_SWAP_ITEMS: usize = 3;
+
+pub const COLLECTION_OFFER: &[u8] = b"collection_offer";
+
+pub const SET_OFFER: &[u8] = b"set_offer";
+
+pub const COUNTER_OFFER: &[u8] = b"counter_offer";
+
+/// Maximum number of counter-offers exchanged on a single offer.
+pub const MAX_COUNTER_ROUNDS: u8 = 6;
+
+pub const AUCTION: &[u8] = b"auction";
+
+pub const BID: &[u8] = b"bid";
+
+pub const ESCROW_STATE: &[u8] = b"escrow_state";
@@ -1,10 +1,7 @@
-use anchor_lang::{
-    prelude::*,
-    solana_program::{program::invoke, system_instruction},
-};
+use anchor_lang::prelude::*;
 use anchor_spl::token::{Mint, Token};
 
-use crate::{constant::*, error::*, states::*, utils::*};
+use crate::{constant::*, states::*, utils::*};
 
 #[derive(Accounts)]
 pub struct Deposit<'info> {
@@ -33,6 +30,21 @@ pub struct Deposit<'info> {
     )]
     pub escrow_payment_account: UncheckedAccount<'info>,
 
+    /// Buyer escrow ledger PDA account.
+    #[account(
+        init_if_needed,
+        seeds = [
+            PREFIX,
+            auction_house.key().as_ref(),
+            wallet.key().as_ref(),
+            ESCROW_STATE
+        ],
+        bump,
+        space = 8 + std::mem::size_of::<BuyerEscrowState>(),
+        payer = wallet
+    )]
+    pub escrow_state: Box<Account<'info, BuyerEscrowState>>,
+
     /// Auction House instance authority account.
     /// CHECK: Validated as a auction house signer.
     pub authority: UncheckedAccount<'info>,
@@ -80,56 +92,25 @@ pub fn handle(ctx: Context<Deposit>, amount: u64) -> Result<()> {
 
     let is_native = treasury_mint.key() == spl_token::native_mint::id();
 
-    create_program_token_account_if_not_present(
+    transfer_to_escrow(
+        &wallet.to_account_info(),
+        payment_account,
         escrow_payment_account,
-        system_program,
-        wallet,
-        token_program,
         treasury_mint,
         &auction_house.to_account_info(),
+        token_program,
+        system_program,
         rent,
         &escrow_signer_seeds,
-        &[],
+        amount,
         is_native,
     )?;
 
-    if !is_native {
-        assert_is_ata(payment_account, &wallet.key(), &treasury_mint.key())?;
-        invoke(
-            &spl_token::instruction::transfer(
-                token_program.key,
-                &payment_account.key(),
-                &escrow_payment_account.key(),
-                &wallet.key(),
-                &[],
-                amount,
-            )?,
-            &[
-                escrow_payment_account.to_account_info(),
-                payment_account.to_account_info(),
-                token_program.to_account_info(),
-                wallet.to_account_info(),
-            ],
-        )?;
-    } else {
-        assert_keys_equal(payment_account.key(), wallet.key())?;
-
-        // Reach rental exemption and then add deposit amount.
-        let checked_amount = rent_checked_add(escrow_payment_account.to_account_info(), 0)?
-            .checked_add(amount)
-            .ok_or(MarketplaceError::NumericalOverflow)?;
-        invoke(
-            &system_instruction::transfer(
-                &payment_account.key(),
-                &escrow_payment_account.key(),
-                checked_amount,
-            ),
-            &[
-                escrow_payment_account.to_account_info(),
-                payment_account.to_account_info(),
-                system_program.to_account_info(),
-            ],
-        )?;
+    // If first time to deposit
+    let escrow_state = &mut ctx.accounts.escrow_state;
+    if is_zero_account(&escrow_state.to_account_info()) {
+        escrow_state.wallet = wallet_key;
+        escrow_state.auction_house = ah_key;
     }
-    Ok(())
+    escrow_state.deposit(amount)
 }
@@ -36,4 +36,70 @@ pub enum MarketplaceError {
     #[msg("Invalid discount account")]
     InvalidDiscountAccount,
 
+    #[msg("Insufficient escrow balance")]
+    InsufficientEscrow,
+
+    #[msg("Auction is not active")]
+    AuctionNotActive,
+
+    #[msg("Auction has not ended")]
+    AuctionNotEnded,
+
+    #[msg("Bid is too low")]
+    BidTooLow,
+
+    #[msg("Bid does not match commitment")]
+    InvalidReveal,
+
+    #[msg("Price exceeds buyer maximum")]
+    PriceExceedsMaximum,
+
+    #[msg("NFT is not a verified member of the collection")]
+    InvalidCollection,
+
+    #[msg("NFT is not in the offer set")]
+    InvalidMerkleProof,
+
+    #[msg("Waiting on the other party of the negotiation")]
+    NotYourTurn,
+
+    #[msg("Maximum counter-offer rounds reached")]
+    MaxCounterRounds,
+
+    #[msg("Bundle items or allocations are invalid")]
+    InvalidBundle,
+
+    #[msg("Swap items do not match the offer")]
+    InvalidSwap,
+
+    #[msg("Listed NFT is no longer delegated to the treasury")]
+    InvalidDelegate,
+
+    #[msg("Vault listings are unlisted with unlist_vaulted")]
+    VaultListing,
+
+    #[msg("Listing is reserved for another buyer")]
+    ReservedListing,
+
+    #[msg("Listing or offer is not active yet")]
+    NotYetActive,
+
+    #[msg("Listing or offer has expired")]
+    Expired,
+
+    #[msg("Listing or offer cannot be cancelled yet")]
+    CancelCooldown,
+
+    #[msg("Listing belongs to another auction house")]
+    InvalidAuctionHouse,
+
+    #[msg("Mint is not an NFT with metadata and a master edition")]
+    InvalidNftMint,
+
+    #[msg("Total cost exceeds buyer maximum")]
+    TotalCostExceedsMaximum,
+
+    #[msg("Seller proceeds below minimum")]
+    SellerProceedsBelowMinimum,
+
 }
@@ -0,0 +1,336 @@
+use anchor_lang::{prelude::*, AccountsClose};
+use anchor_spl::token::{self, Token, TokenAccount, Transfer};
+use anchor_spl::associated_token::AssociatedToken;
+
+use crate::{constant::*, error::*, states::*, utils::*};
+
+#[derive(Accounts)]
+pub struct AcceptOffer<'info> {
+    /// Seller user wallet account.
+    #[account(mut)]
+    pub seller: Signer<'info>,
+
+    /// CHECK: Validated as the offer buyer.
+    /// Buyer user wallet account.
+    #[account(mut, constraint = buyer.key() == offer_account.buyer)]
+    pub buyer: UncheckedAccount<'info>,
+
+    /// CHECK: Validated in accept_offer.
+    /// Auction House treasury mint account.
+    pub treasury_mint: UncheckedAccount<'info>,
+
+    /// CHECK: Not dangerous. Account seeds checked in constraint.
+    /// Buyer escrow payment account PDA.
+    #[account(
+        mut,
+        seeds = [
+            PREFIX,
+            auction_house.key().as_ref(),
+            buyer.key().as_ref()
+        ],
+        bump
+    )]
+    pub escrow_payment_account: UncheckedAccount<'info>,
+
+    /// Buyer escrow ledger PDA account.
+    #[account(
+        mut,
+        seeds = [
+            PREFIX,
+            auction_house.key().as_ref(),
+            buyer.key().as_ref(),
+            ESCROW_STATE
+        ],
+        bump
+    )]
+    pub escrow_state: Box<Account<'info, BuyerEscrowState>>,
+
+    /// CHECK: Validated in accept_offer.
+    /// Seller SOL or SPL account to receive payment at.
+    #[account(mut)]
+    pub seller_payment_receipt_account: UncheckedAccount<'info>,
+
+    /// CHECK: Validated in accept_offer.
+    /// Buyer SPL token account to receive purchased item at.
+    #[account(mut)]
+    pub buyer_receipt_token_account: UncheckedAccount<'info>,
+
+    /// Auction House instance authority account.
+    /// CHECK: Validated as a auction house signer.
+    pub authority: UncheckedAccount<'info>,
+
+    /// Auction House treasury PDA account.
+    /// CHECK: Not dangerous. Account seeds checked in constraint.
+    #[account(mut, seeds=[PREFIX, auction_house.key().as_ref(), TREASURY], bump)]
+    pub auction_house_treasury: UncheckedAccount<'info>,
+
+    /// Auction House instance PDA account.
+    #[account(
+        seeds = [
+            PREFIX,
+            auction_house.creator.as_ref(),
+            auction_house.treasury_mint.as_ref()
+        ],
+        bump,
+        has_one=authority,
+        has_one=treasury_mint,
+        has_one=auction_house_treasury
+    )]
+    pub auction_house: Box<Account<'info, AuctionHouse>>,
+
+    /// CHECK: Validated in accept_offer.
+    /// NFT mint account
+    pub nft_mint: UncheckedAccount<'info>,
+
+    /// NFT token account, either listed in the treasury or still held by the seller.
+    #[account(mut,
+        constraint = nft_account.mint == nft_mint.key()
+    )]
+    pub nft_account: Box<Account<'info, TokenAccount>>,
+
+    /// CHECK: Validated in accept_offer.
+    /// Metaplex metadata account decorating SPL mint account.
+    pub metadata: UncheckedAccount<'info>,
+
+    /// CHECK: Validated by Token Metadata when thawing.
+    /// Metaplex master edition account of the NFT mint, used by frozen delegated listings.
+    pub edition: UncheckedAccount<'info>,
+
+    /// CHECK: Not dangerous. Account seeds checked in constraint, empty when the NFT is not listed.
+    /// Listing PDA account
+    #[account(
+        mut,
+        seeds=[
+            PREFIX,
+            auction_house.key().as_ref(),
+            nft_mint.key().as_ref(),
+            LISTING
+        ],
+        bump,
+    )]
+    pub listing_account: UncheckedAccount<'info>,
+
+    /// Offer PDA account
+    #[account(
+        mut,
+        seeds=[
+            PREFIX,
+            auction_house.key().as_ref(),
+            nft_mint.key().as_ref(),
+            buyer.key().as_ref(),
+            OFFER
+        ],
+        bump, 
+        close = buyer,
+        constraint = offer_account.nft_mint == nft_mint.key(),
+    )]
+    pub offer_account: Box<Account<'info, OfferAccount>>,
+
+    /// CHECK: Not dangerous. Account seeds checked in constraint.
+    /// Counter-offer PDA account, reset when the offer closes.
+    #[account(
+        mut,
+        seeds=[
+            PREFIX,
+            offer_account.key().as_ref(),
+            COUNTER_OFFER
+        ],
+        bump
+    )]
+    pub counter_account: UncheckedAccount<'info>,
+
+    /// CHECK: Account address checked in constraint.
+    /// Metaplex Token Metadata program.
+    #[account(address = mpl_token_metadata::id())]
+    pub token_metadata_program: UncheckedAccount<'info>,
+
+    pub token_program: Program<'info, Token>,
+    pub system_program: Program<'info, System>,
+    pub ata_program: Program<'info, AssociatedToken>,
+    pub rent: Sysvar<'info, Rent>,
+}
+
+/// Seller accepts an offer at the offer price, whether or not the NFT is listed.
+pub fn accept_offer<'info>(ctx: Context<'_, '_, '_, 'info, AcceptOffer<'info>>) -> Result<()> {
+    let buyer = &ctx.accounts.buyer;
+    let seller = &ctx.accounts.seller;
+    let auction_house = &ctx.accounts.auction_house;
+    let auction_house_treasury = &ctx.accounts.auction_house_treasury;
+    let metadata = &ctx.accounts.metadata;
+    let treasury_mint = &ctx.accounts.treasury_mint;
+    let listing_info = ctx.accounts.listing_account.to_account_info();
+    let offer_account = &ctx.accounts.offer_account;
+    let nft_mint = &ctx.accounts.nft_mint;
+    let nft_account = &ctx.accounts.nft_account;
+    let buyer_receipt_token_account = &ctx.accounts.buyer_receipt_token_account;
+    let system_program = &ctx.accounts.system_program;
+    let ata_program = &ctx.accounts.ata_program;
+    let token_program = &ctx.accounts.token_program;
+    let rent = &ctx.accounts.rent;
+
+    let is_native = treasury_mint.key() == spl_token::native_mint::id();
+
+    // Check offer is within its fill window
+    let now = Clock::get()?.unix_timestamp as u64;
+    offer_account.assert_fillable(now)?;
+
+    // Listed NFTs sit in the treasury or are delegated to it, unlisted ones in the seller wallet
+    let listing = if listing_info.data_is_empty() {
+        require!(nft_account.owner == seller.key(), MarketplaceError::InvalidOwner);
+        None
+    } else {
+        let listing_account: Account<ListingAccount> = Account::try_from(&listing_info)?;
+        require!(listing_account.owner == seller.key(), MarketplaceError::InvalidOwner);
+        assert_listed_nft(
+            ctx.program_id,
+            &nft_account.to_account_info(),
+            &listing_account.key(),
+            &seller.key(),
+            &nft_mint.key(),
+            &auction_house_treasury.key(),
+            listing_account.custody,
+        )?;
+        Some(listing_account)
+    };
+
+    // Check NFT account balance
+    require!(nft_account.amount > 0, MarketplaceError::InvalidAmount);
+
+    // Check metadata is correct
+    assert_metadata(&metadata.to_account_info(), &nft_mint.key())?;
+
+    let price = offer_account.price;
+
+    let ah_key = auction_house.key();
+    let wallet_key = buyer.key();
+    let escrow_signer_seeds = [
+        PREFIX,
+        ah_key.as_ref(),
+        wallet_key.as_ref(),
+        &[bump(&[PREFIX, ah_key.as_ref(), wallet_key.as_ref()], ctx.program_id)]
+    ];
+
+    let ah_seeds = [
+        PREFIX,
+        auction_house.creator.as_ref(),
+        auction_house.treasury_mint.as_ref(),
+        &[bump(&[PREFIX, auction_house.creator.as_ref(), auction_house.treasury_mint.as_ref()], ctx.program_id)]
+    ];
+
+    // with the native account, the escrow is its own owner,
+    // whereas with token, it is the auction house that is owner.
+    let signer_seeds_for_royalties = if is_native {
+        escrow_signer_seeds
+    } else {
+        ah_seeds
+    };
+
+    let remaining_accounts = &mut ctx.remaining_accounts.iter();
+
+    pay_sale_proceeds(
+        remaining_accounts,
+        auction_house,
+        &auction_house_treasury.to_account_info(),
+        &metadata.to_account_info(),
+        &ctx.accounts.escrow_payment_account.to_account_info(),
+        buyer,
+        seller,
+        &ctx.accounts.seller_payment_receipt_account.to_account_info(),
+        seller,
+        treasury_mint,
+        &ata_program.to_account_info(),
+        &token_program.to_account_info(),
+        &system_program.to_account_info(),
+        &rent.to_account_info(),
+        &signer_seeds_for_royalties,
+        price,
+        is_native,
+    )?;
+
+    // Check buyer NFT ATA is valid
+    if buyer_receipt_token_account.data_is_empty() {
+        make_ata(
+            buyer_receipt_token_account.to_account_info(),
+            buyer.to_account_info(),
+            nft_mint.to_account_info(),
+            seller.to_account_info(),
+            ata_program.to_account_info(),
+            token_program.to_account_info(),
+            system_program.to_account_info(),
+            rent.to_account_info(),
+            &[],
+        )?;
+    }
+
+    let buyer_rec_acct = assert_is_ata(buyer_receipt_token_account, &buyer.key(), &nft_mint.key())?;
+
+    // make sure you cant get rugged
+    if buyer_rec_acct.delegate.is_some() {
+        return Err(MarketplaceError::BuyerATACannotHaveDelegate.into());
+    }
+
+    // Transfer NFT to buyer, out of the treasury for listed NFTs
+    if let Some(listing_account) = listing {
+        let treasury_signer_seeds = &[
+            PREFIX,
+            ah_key.as_ref(),
+            TREASURY,
+            &[bump(&[PREFIX, ah_key.as_ref(), TREASURY], ctx.program_id)],
+        ];
+        let treasury_signer = &[&treasury_signer_seeds[..]];
+
+        if listing_account.custody == ListingCustody::Vault {
+            // Hand the vault content to the buyer and refund its rent to the seller
+            close_vault(
+                nft_account,
+                &buyer_receipt_token_account.to_account_info(),
+                &seller.to_account_info(),
+                &auction_house_treasury.to_account_info(),
+                token_program,
+                treasury_signer,
+            )?;
+        } else {
+            thaw_listed_nft(
+                listing_account.custody,
+                &nft_account.to_account_info(),
+                &ctx.accounts.edition,
+                nft_mint,
+                &auction_house_treasury.to_account_info(),
+                &ctx.accounts.token_metadata_program,
+                &token_program.to_account_info(),
+                treasury_signer_seeds,
+            )?;
+
+            let cpi_account = Transfer {
+                from: nft_account.to_account_info(),
+                to: buyer_receipt_token_account.to_account_info(),
+                authority: auction_house_treasury.to_account_info(),
+            };
+            let cpi_ctx = CpiContext::new_with_signer(
+                token_program.to_account_info(),
+                cpi_account,
+                treasury_signer,
+            );
+            token::transfer(cpi_ctx, 1)?;
+        }
+
+        listing_account.close(seller.to_account_info())?;
+    } else {
+        let cpi_account = Transfer {
+            from: nft_account.to_account_info(),
+            to: buyer_receipt_token_account.to_account_info(),
+            authority: seller.to_account_info(),
+        };
+        token::transfer(CpiContext::new(token_program.to_account_info(), cpi_account), 1)?;
+    }
+
+    // Settle the offer reservation against the paid price
+    ctx.accounts.escrow_state.spend(offer_account.escrow_amount, price)?;
+
+    // End any negotiation on the offer
+    reset_counter_offer(&ctx.accounts.counter_account.to_account_info(), ctx.program_id)?;
+
+    msg!("{{\"buyer\": \"{}\", \"price\": \"{}\"}}", wallet_key, price);
+
+    Ok(())
+}
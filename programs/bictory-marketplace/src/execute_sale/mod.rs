@@ -1,13 +1,16 @@
 use anchor_lang::{
     prelude::*,
-    solana_program::{program::invoke, program::invoke_signed, system_instruction},
+    solana_program::{program::invoke, system_instruction},
 };
 use anchor_spl::token::{self, Token, TokenAccount, Transfer};
 use anchor_spl::associated_token::AssociatedToken;
-use mpl_token_metadata::state::Metadata;
 
 use crate::{constant::*, error::*, states::*, utils::*};
 
+pub mod accept_offer;
+
+pub use accept_offer::*;
+
 #[derive(Accounts)]
 pub struct ExecuteSale<'info> {
     /// Buyer user wallet account.
@@ -37,6 +40,19 @@ pub struct ExecuteSale<'info> {
     )]
     pub escrow_payment_account: UncheckedAccount<'info>,
 
+    /// Buyer escrow ledger PDA account.
+    #[account(
+        mut,
+        seeds = [
+            PREFIX,
+            auction_house.key().as_ref(),
+            buyer.key().as_ref(),
+            ESCROW_STATE
+        ],
+        bump
+    )]
+    pub escrow_state: Box<Account<'info, BuyerEscrowState>>,
+
     /// CHECK: Validated in execute_sale_logic.
     /// Seller SOL or SPL account to receive payment at.
     #[account(mut)]
@@ -84,11 +100,16 @@ pub struct ExecuteSale<'info> {
     /// Metaplex metadata account decorating SPL mint account.
     pub metadata: UncheckedAccount<'info>,
 
+    /// CHECK: Validated by Token Metadata when thawing.
+    /// Metaplex master edition account of the NFT mint, used by frozen delegated listings.
+    pub edition: UncheckedAccount<'info>,
+
     /// Listing PDA account
     #[account(
         mut,
         seeds=[
             PREFIX,
+            auction_house.key().as_ref(),
             nft_mint.key().as_ref(),
             LISTING
         ],
@@ -96,6 +117,7 @@ pub struct ExecuteSale<'info> {
         close = seller,
         constraint = listing_account.owner == seller.key(),
         constraint = listing_account.nft_mint == nft_mint.key(),
+        constraint = listing_account.auction_house == auction_house.key() @ MarketplaceError::InvalidAuctionHouse,
     )]
     pub listing_account: Account<'info, ListingAccount>,
 
@@ -104,6 +126,7 @@ pub struct ExecuteSale<'info> {
         mut,
         seeds=[
             PREFIX,
+            auction_house.key().as_ref(),
             nft_mint.key().as_ref(),
             buyer.key().as_ref(),
             OFFER
@@ -112,16 +135,38 @@ pub struct ExecuteSale<'info> {
         close = buyer,
         constraint = offer_account.buyer == buyer.key(),
         constraint = offer_account.nft_mint == nft_mint.key(),
+        constraint = offer_account.auction_house == auction_house.key() @ MarketplaceError::InvalidAuctionHouse,
     )]
     pub offer_account: Account<'info, OfferAccount>,
 
+    /// CHECK: Not dangerous. Account seeds checked in constraint.
+    /// Counter-offer PDA account, reset when the offer closes.
+    #[account(
+        mut,
+        seeds=[
+            PREFIX,
+            offer_account.key().as_ref(),
+            COUNTER_OFFER
+        ],
+        bump
+    )]
+    pub counter_account: UncheckedAccount<'info>,
+
+    /// CHECK: Account address checked in constraint.
+    /// Metaplex Token Metadata program.
+    #[account(address = mpl_token_metadata::id())]
+    pub token_metadata_program: UncheckedAccount<'info>,
+
     pub token_program: Program<'info, Token>,
     pub system_program: Program<'info, System>,
     pub ata_program: Program<'info, AssociatedToken>,
     pub rent: Sysvar<'info, Rent>,
 }
 
-pub fn handle<'info>(ctx: Context<'_, '_, '_, 'info, ExecuteSale<'info>>) -> Result<()> {
+pub fn handle<'info>(
+    ctx: Context<'_, '_, '_, 'info, ExecuteSale<'info>>,
+    max_total_cost: Option<u64>,
+) -> Result<()> {
 
     let buyer = &ctx.accounts.buyer;
     let seller = &ctx.accounts.seller;
@@ -143,7 +188,6 @@ pub fn handle<'info>(ctx: Context<'_, '_, '_, 'info, ExecuteSale<'info>>) -> Res
 
     let metadata_clone = metadata.to_account_info();
     let escrow_clone = escrow_payment_account.to_account_info();
-    let auction_house_clone = auction_house.to_account_info();
     let ata_clone = ata_program.to_account_info();
     let token_clone = token_program.to_account_info();
     let sys_clone = system_program.to_account_info();
@@ -152,57 +196,49 @@ pub fn handle<'info>(ctx: Context<'_, '_, '_, 'info, ExecuteSale<'info>>) -> Res
     let buyer_receipt_clone = buyer_receipt_token_account.to_account_info();
     let token_account_clone = nft_account.to_account_info();
 
-    let price = listing_account.price;
-
     let is_native = treasury_mint.key() == spl_token::native_mint::id();
 
-    // Check expired offer or listing
+    // Check offer and listing sale windows are open and a private listing is filled by its reserved buyer
     let now = Clock::get()?.unix_timestamp as u64;
-    require!(
-        listing_account.expiry == 0 ||
-        listing_account.expiry < now,
-        MarketplaceError::InvalidExpiry,
-    );
-    require!(
-        offer_account.expiry == 0 ||
-        offer_account.expiry < now,
-        MarketplaceError::InvalidExpiry,
-    );
-
-    // Check offer and listing price is same
-    require!(
-        listing_account.price == offer_account.price,
-        MarketplaceError::InvalidAmount,
-    );
-
-    // Check NFT token ownership (needs to be in treasury account)
-    assert_is_ata2(
+    listing_account.assert_fillable_by(&buyer.key(), now)?;
+    offer_account.assert_fillable(now)?;
+
+    // Check offer matches the listing price, a declining price is charged at its current value
+    // as long as it does not exceed the offer price
+    let price = listing_account.current_price(now)?;
+    if listing_account.dutch.is_some() {
+        require!(
+            price <= offer_account.price,
+            MarketplaceError::PriceExceedsMaximum,
+        );
+    } else {
+        require!(
+            listing_account.price == offer_account.price,
+            MarketplaceError::InvalidAmount,
+        );
+    }
+
+    // Check NFT token ownership (needs to be in treasury account or delegated to it)
+    assert_listed_nft(
+        ctx.program_id,
         &nft_account.to_account_info(),
+        &listing_account.key(),
         &seller.key(),
         &nft_mint.key(),
         &auction_house_treasury.key(),
+        listing_account.custody,
     )?;
 
     // Check NFT account balance
     require!(nft_account.amount > 0, MarketplaceError::InvalidAmount);
 
     // Check metadata is correct
-    assert_derivation(
-        &mpl_token_metadata::id(),
-        &metadata.to_account_info(),
-        &[
-            mpl_token_metadata::state::PREFIX.as_bytes(),
-            mpl_token_metadata::id().as_ref(),
-            nft_mint.key().as_ref()
-        ],
-    )?;
+    assert_metadata(&metadata.to_account_info(), &nft_mint.key())?;
 
-    if metadata.data_is_empty() {
-        return Err(MarketplaceError::InvalidAccountInput.into());
-    }
     // For native purchases, verify that the amount in escrow is sufficient to actually purchase the token.
     // This is intended to cover the migration from pre-rent-exemption checked accounts to rent-exemption checked accounts.
     // The fee payer makes up the shortfall up to the amount of rent for an empty account.
+    let mut escrow_top_up = 0;
     if is_native {
         let diff = rent_checked_sub(escrow_payment_account.to_account_info(), price)?;
         if diff != price {
@@ -221,6 +257,7 @@ pub fn handle<'info>(ctx: Context<'_, '_, '_, 'info, ExecuteSale<'info>>) -> Res
                     system_program.to_account_info(),
                 ]
             )?;
+            escrow_top_up = shortfall;
         }
     }
 
@@ -250,11 +287,15 @@ pub fn handle<'info>(ctx: Context<'_, '_, '_, 'info, ExecuteSale<'info>>) -> Res
 
     let remaining_accounts = &mut ctx.remaining_accounts.iter();
 
-    let buyer_leftover_after_royalties = pay_creator_fees(
+    let seller_proceeds = pay_sale_proceeds(
         remaining_accounts,
+        auction_house,
+        &treasury_clone,
         &metadata_clone,
         &escrow_clone,
-        &auction_house_clone,
+        buyer,
+        seller,
+        seller_payment_receipt_account,
         buyer,
         treasury_mint,
         &ata_clone,
@@ -262,135 +303,12 @@ pub fn handle<'info>(ctx: Context<'_, '_, '_, 'info, ExecuteSale<'info>>) -> Res
         &sys_clone,
         &rent_clone,
         &signer_seeds_for_royalties,
-        &[],
-        price,
-        is_native,
-    )?;
-
-    // Check discount NFT metadata
-    let mut is_discount = false;
-    let remain_discount_mint = next_account_info(remaining_accounts).ok();    
-
-    if let Some(discount_mint) = remain_discount_mint {
-        let discount_account = next_account_info(remaining_accounts)?;
-        let discount_metadata = next_account_info(remaining_accounts)?;
-
-        if discount_metadata.data_is_empty() {
-            return Err(MarketplaceError::InvalidDiscountAccount.into());
-        }
-        else {
-            // Check discount NFT is valid        
-            let discount_ata = assert_is_ata(
-                &discount_account.to_account_info(),
-                &buyer.key(),
-                &discount_mint.key(),
-            )?;
-    
-            // Check discount ATA balance
-            if discount_ata.amount < 1 {
-                return Err(MarketplaceError::InvalidDiscountAccount.into());
-            }
-    
-            // Check discount metadata
-            assert_derivation(
-                &mpl_token_metadata::id(),
-                &discount_metadata.to_account_info(),
-                &[
-                    mpl_token_metadata::state::PREFIX.as_bytes(),
-                    mpl_token_metadata::id().as_ref(),
-                    discount_mint.key().as_ref()
-                ],
-            )?;
-    
-            // Check discount account is valid collection
-            let _discount_metadata = Metadata::from_account_info(discount_metadata)?;
-            if let Some(collection) = _discount_metadata.collection {
-                require!(collection.verified && collection.key == auction_house.discount_collection, MarketplaceError::InvalidDiscountAccount);
-                is_discount = true;
-            }
-            else {
-                return Err(MarketplaceError::InvalidDiscountAccount.into());
-            }
-        }
-    }
-
-    msg!("Discount: {}", is_discount);
-
-    let auction_house_fee_paid = pay_auction_house_fees(
-        auction_house,
-        &treasury_clone,
-        &escrow_clone,
-        &token_clone,
-        &sys_clone,
-        &signer_seeds_for_royalties,
         price,
         is_native,
-        is_discount
     )?;
 
-    let buyer_leftover_after_royalties_and_house_fee = buyer_leftover_after_royalties
-        .checked_sub(auction_house_fee_paid)
-        .ok_or(MarketplaceError::NumericalOverflow)?;
-
-    if !is_native {
-        if seller_payment_receipt_account.data_is_empty() {
-            make_ata(
-                seller_payment_receipt_account.to_account_info(),
-                seller.to_account_info(),
-                treasury_mint.to_account_info(),
-                buyer.to_account_info(),
-                ata_program.to_account_info(),
-                token_program.to_account_info(),
-                system_program.to_account_info(),
-                rent.to_account_info(),
-                &[],
-            )?;
-        }
-
-        let seller_rec_acct = assert_is_ata(
-            &seller_payment_receipt_account.to_account_info(),
-            &seller.key(),
-            &treasury_mint.key(),
-        )?;
-
-        // make sure you cant get rugged
-        if seller_rec_acct.delegate.is_some() {
-            return Err(MarketplaceError::SellerATACannotHaveDelegate.into());
-        }
-
-        invoke_signed(
-            &spl_token::instruction::transfer(
-                token_program.key,
-                &escrow_payment_account.key(),
-                &seller_payment_receipt_account.key(),
-                &auction_house.key(),
-                &[],
-                buyer_leftover_after_royalties_and_house_fee,
-            )?,
-            &[
-                escrow_payment_account.to_account_info(),
-                seller_payment_receipt_account.to_account_info(),
-                token_program.to_account_info(),
-                auction_house.to_account_info(),
-            ],
-            &[&ah_seeds],
-        )?;
-    } else {
-        assert_keys_equal(seller_payment_receipt_account.key(), seller.key())?;
-        invoke_signed(
-            &system_instruction::transfer(
-                escrow_payment_account.key,
-                seller_payment_receipt_account.key,
-                buyer_leftover_after_royalties_and_house_fee,
-            ),
-            &[
-                escrow_payment_account.to_account_info(),
-                seller_payment_receipt_account.to_account_info(),
-                system_program.to_account_info(),
-            ],
-            &[&escrow_signer_seeds],
-        )?;
-    }
+    // Check fee or royalty changes since listing did not cut the seller payout below the guard
+    listing_account.assert_net_proceeds(seller_proceeds)?;
 
     // Check buyer NFT ATA is valid
     if buyer_receipt_token_account.data_is_empty() {
@@ -427,19 +345,59 @@ pub fn handle<'info>(ctx: Context<'_, '_, '_, 'info, ExecuteSale<'info>>) -> Res
     ];
     let treasury_signer = &[&treasury_signer_seeds[..]];
 
-    let cpi_account = Transfer {
-        from: token_account_clone,
-        to: buyer_receipt_clone,
-        authority: treasury_clone
-    };
+    if listing_account.custody == ListingCustody::Vault {
+        // Hand the vault content to the buyer and refund its rent to the seller
+        close_vault(
+            nft_account,
+            &buyer_receipt_clone,
+            seller,
+            &treasury_clone,
+            token_program,
+            treasury_signer,
+        )?;
+    } else {
+        thaw_listed_nft(
+            listing_account.custody,
+            &token_account_clone,
+            &ctx.accounts.edition,
+            nft_mint,
+            &treasury_clone,
+            &ctx.accounts.token_metadata_program,
+            &token_clone,
+            treasury_signer_seeds,
+        )?;
+
+        let cpi_account = Transfer {
+            from: token_account_clone,
+            to: buyer_receipt_clone,
+            authority: treasury_clone
+        };
+
+        let cpi_ctx = CpiContext::new_with_signer(
+            token_clone,
+            cpi_account,
+            treasury_signer
+        );
 
-    let cpi_ctx = CpiContext::new_with_signer(
-        token_clone,
-        cpi_account,
-        treasury_signer
-    );
+        token::transfer(cpi_ctx, 1)?;
+    }
+
+    // Settle the offer reservation against the paid price
+    ctx.accounts.escrow_state.spend(offer_account.escrow_amount, price)?;
+
+    // End any negotiation on the offer
+    reset_counter_offer(&ctx.accounts.counter_account.to_account_info(), ctx.program_id)?;
 
-    token::transfer(cpi_ctx, 1)?;
+    // Check the buyer was not charged more than allowed. The total cost is what the buyer pays in the treasury mint
+    // for the sale: the price, out of which royalties and auction house fees are taken, plus any escrow shortfall
+    // topped up from the buyer wallet. Rent of token accounts created for the sale is not part of it.
+    let total_cost = price
+        .checked_add(escrow_top_up)
+        .ok_or(MarketplaceError::NumericalOverflow)?;
+    if let Some(max_total_cost) = max_total_cost {
+        require!(total_cost <= max_total_cost, MarketplaceError::TotalCostExceedsMaximum);
+    }
+    msg!("{{\"price\": \"{}\", \"total_cost\": \"{}\"}}", price, total_cost);
 
     Ok(())
 }
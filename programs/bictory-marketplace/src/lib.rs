@@ -1,9 +1,17 @@
 use anchor_lang::prelude::*;
 
+mod auction;
 mod auction_house;
+mod bundle;
+mod collection_offer;
 mod deposit;
 mod execute_sale;
 mod listing;
+mod negotiation;
+mod quantity_listing;
+mod set_offer;
+mod swap;
+mod withdraw;
 
 /// constant
 mod constant;
@@ -14,7 +22,11 @@ mod states;
 /// utils
 mod utils;
 
-use crate::{auction_house::*, deposit::*, execute_sale::*, listing::*};
+use crate::{
+    auction::*, auction_house::*, bundle::*, collection_offer::*, deposit::*, execute_sale::*,
+    listing::*, negotiation::*, quantity_listing::*, set_offer::*,
+    states::{ListingTerms, PriceCurve, SwapRequest}, swap::*, withdraw::*,
+};
 
 declare_id!("GG2v349mCx2DUL2Pu3aFtKgjxdNjxYYZjUXVhLSbFt8Q");
 
@@ -22,57 +34,306 @@ declare_id!("GG2v349mCx2DUL2Pu3aFtKgjxdNjxYYZjUXVhLSbFt8Q");
 pub mod marketplace {
     use super::*;
     // admin
+    #[allow(clippy::too_many_arguments)]
     pub fn create_auction_house(
         ctx: Context<CreateAuctionHouse>,
         seller_fee_basis_points: u16,
         discount_collection: Pubkey,
         discount_basis_points: u16,
+        extension_window: u64,
+        extension_period: u64,
+        max_extension: u64,
+        cancel_fee_basis_points: u16,
+        cancel_cooldown: u64,
     ) -> Result<()> {
         auction_house::create_auction_house(
             ctx,
             seller_fee_basis_points,
             discount_collection,
             discount_basis_points,
+            extension_window,
+            extension_period,
+            max_extension,
+            cancel_fee_basis_points,
+            cancel_cooldown,
         )
     }
+    #[allow(clippy::too_many_arguments)]
     pub fn update_auction_house(
         ctx: Context<UpdateAuctionHouse>,
         seller_fee_basis_points: Option<u16>,
         discount_collection: Option<Pubkey>,
         discount_basis_points: Option<u16>,
+        extension_window: Option<u64>,
+        extension_period: Option<u64>,
+        max_extension: Option<u64>,
+        cancel_fee_basis_points: Option<u16>,
+        cancel_cooldown: Option<u64>,
     ) -> Result<()> {
         auction_house::update_auction_house(
             ctx,
             seller_fee_basis_points,
             discount_collection,
             discount_basis_points,
+            extension_window,
+            extension_period,
+            max_extension,
+            cancel_fee_basis_points,
+            cancel_cooldown,
         )
     }
     pub fn withdraw_from_treasury(ctx: Context<WithdrawFromTreasury>, amount: u64) -> Result<()> {
         auction_house::withdraw_from_treasury(ctx, amount)
     }
+    pub fn migrate_auction_house(ctx: Context<MigrateAuctionHouse>) -> Result<()> {
+        auction_house::migrate_auction_house(ctx)
+    }
 
     // user
     pub fn deposit(ctx: Context<Deposit>, amount: u64) -> Result<()> {
         deposit::handle(ctx, amount)
     }
+    pub fn withdraw(ctx: Context<Withdraw>, amount: u64) -> Result<()> {
+        withdraw::handle(ctx, amount)
+    }
 
     // listing
-    pub fn list(ctx: Context<Listing>, price: u64, seller_expiry: Option<u64>) -> Result<()> {
-        listing::list(ctx, price, seller_expiry)
+    pub fn list(ctx: Context<Listing>, price: u64, terms: ListingTerms) -> Result<()> {
+        listing::list(ctx, price, terms)
+    }
+    pub fn list_dutch(
+        ctx: Context<Listing>,
+        start_price: u64,
+        end_price: u64,
+        start_time: u64,
+        decay_duration: u64,
+        curve: PriceCurve,
+        terms: ListingTerms,
+    ) -> Result<()> {
+        listing::list_dutch(
+            ctx,
+            start_price,
+            end_price,
+            start_time,
+            decay_duration,
+            curve,
+            terms,
+        )
+    }
+    pub fn list_delegated(
+        ctx: Context<ListDelegated>,
+        price: u64,
+        terms: ListingTerms,
+        freeze: bool,
+    ) -> Result<()> {
+        listing::list_delegated(ctx, price, terms, freeze)
+    }
+    pub fn list_vaulted(ctx: Context<ListVaulted>, price: u64, terms: ListingTerms) -> Result<()> {
+        listing::list_vaulted(ctx, price, terms)
     }
     pub fn unlisting(ctx: Context<Unlisting>) -> Result<()> {
         listing::unlisting(ctx)
     }
-    pub fn buy(ctx: Context<Buy>, price: u64, buyer_expiry: Option<u64>) -> Result<()> {
-        listing::buy(ctx, price, buyer_expiry)
+    pub fn unlist_vaulted(ctx: Context<UnlistVaulted>) -> Result<()> {
+        listing::unlist_vaulted(ctx)
+    }
+    pub fn buy(
+        ctx: Context<Buy>,
+        price: u64,
+        buyer_expiry: Option<u64>,
+        deposit_shortfall: bool,
+        buyer_start_time: Option<u64>,
+    ) -> Result<()> {
+        listing::buy(ctx, price, buyer_expiry, deposit_shortfall, buyer_start_time)
     }
     pub fn cancel_buy(ctx: Context<CancelBuy>) -> Result<()> {
         listing::cancel_buy(ctx)
     }
+    pub fn force_unlisting(ctx: Context<ForceUnlisting>) -> Result<()> {
+        listing::force_unlisting(ctx)
+    }
+    pub fn migrate_listing(ctx: Context<MigrateListing>) -> Result<()> {
+        listing::migrate_listing(ctx)
+    }
+    pub fn close_legacy_offer(ctx: Context<CloseLegacyOffer>) -> Result<()> {
+        listing::close_legacy_offer(ctx)
+    }
+    pub fn force_cancel_buy(ctx: Context<ForceCancelBuy>) -> Result<()> {
+        listing::force_cancel_buy(ctx)
+    }
+
+    // quantity listing
+    pub fn list_quantity(
+        ctx: Context<ListQuantity>,
+        price: u64,
+        quantity: u64,
+        seller_expiry: Option<u64>,
+    ) -> Result<()> {
+        quantity_listing::list_quantity(ctx, price, quantity, seller_expiry)
+    }
+    pub fn buy_quantity<'info>(
+        ctx: Context<'_, '_, '_, 'info, BuyQuantity<'info>>,
+        quantity: u64,
+        deposit_shortfall: bool,
+    ) -> Result<()> {
+        quantity_listing::buy_quantity(ctx, quantity, deposit_shortfall)
+    }
+    pub fn unlist_quantity(ctx: Context<UnlistQuantity>) -> Result<()> {
+        quantity_listing::unlist_quantity(ctx)
+    }
+
+    // bundle
+    pub fn list_bundle<'info>(
+        ctx: Context<'_, '_, '_, 'info, ListBundle<'info>>,
+        bundle_id: u64,
+        price: u64,
+        allocations: Vec<u16>,
+        seller_expiry: Option<u64>,
+    ) -> Result<()> {
+        bundle::list_bundle(ctx, bundle_id, price, allocations, seller_expiry)
+    }
+    pub fn buy_bundle<'info>(
+        ctx: Context<'_, '_, '_, 'info, BuyBundle<'info>>,
+        deposit_shortfall: bool,
+    ) -> Result<()> {
+        bundle::buy_bundle(ctx, deposit_shortfall)
+    }
+    pub fn unlist_bundle<'info>(ctx: Context<'_, '_, '_, 'info, UnlistBundle<'info>>) -> Result<()> {
+        bundle::unlist_bundle(ctx)
+    }
+
+    // swap
+    pub fn make_swap_offer<'info>(
+        ctx: Context<'_, '_, '_, 'info, MakeSwapOffer<'info>>,
+        swap_id: u64,
+        request: SwapRequest,
+        sweetener: u64,
+        maker_expiry: Option<u64>,
+        deposit_shortfall: bool,
+    ) -> Result<()> {
+        swap::make_swap_offer(ctx, swap_id, request, sweetener, maker_expiry, deposit_shortfall)
+    }
+    pub fn cancel_swap_offer<'info>(ctx: Context<'_, '_, '_, 'info, CancelSwapOffer<'info>>) -> Result<()> {
+        swap::cancel_swap_offer(ctx)
+    }
+    pub fn fill_swap_offer<'info>(ctx: Context<'_, '_, '_, 'info, FillSwapOffer<'info>>) -> Result<()> {
+        swap::fill_swap_offer(ctx)
+    }
+
+    // negotiation
+    pub fn counter_offer(
+        ctx: Context<CounterOffer>,
+        price: u64,
+        expiry: Option<u64>,
+        deposit_shortfall: bool,
+    ) -> Result<()> {
+        negotiation::counter_offer(ctx, price, expiry, deposit_shortfall)
+    }
+    pub fn accept_counter<'info>(
+        ctx: Context<'_, '_, '_, 'info, AcceptCounter<'info>>,
+        deposit_shortfall: bool,
+    ) -> Result<()> {
+        negotiation::accept_counter(ctx, deposit_shortfall)
+    }
+
+    // collection offer
+    pub fn make_collection_offer(
+        ctx: Context<MakeCollectionOffer>,
+        price: u64,
+        quantity: u64,
+        buyer_expiry: Option<u64>,
+        deposit_shortfall: bool,
+    ) -> Result<()> {
+        collection_offer::make_collection_offer(ctx, price, quantity, buyer_expiry, deposit_shortfall)
+    }
+    pub fn cancel_collection_offer(ctx: Context<CancelCollectionOffer>) -> Result<()> {
+        collection_offer::cancel_collection_offer(ctx)
+    }
+    pub fn sell_into_collection_offer<'info>(
+        ctx: Context<'_, '_, '_, 'info, SellIntoCollectionOffer<'info>>,
+    ) -> Result<()> {
+        collection_offer::sell_into_collection_offer(ctx)
+    }
+
+    // set offer
+    pub fn make_set_offer(
+        ctx: Context<MakeSetOffer>,
+        merkle_root: [u8; 32],
+        price: u64,
+        quantity: u64,
+        buyer_expiry: Option<u64>,
+        deposit_shortfall: bool,
+    ) -> Result<()> {
+        set_offer::make_set_offer(ctx, merkle_root, price, quantity, buyer_expiry, deposit_shortfall)
+    }
+    pub fn cancel_set_offer(ctx: Context<CancelSetOffer>, merkle_root: [u8; 32]) -> Result<()> {
+        set_offer::cancel_set_offer(ctx, merkle_root)
+    }
+    pub fn sell_into_set_offer<'info>(
+        ctx: Context<'_, '_, '_, 'info, SellIntoSetOffer<'info>>,
+        proof: Vec<[u8; 32]>,
+    ) -> Result<()> {
+        set_offer::sell_into_set_offer(ctx, proof)
+    }
+
+    // auction
+    pub fn create_auction(
+        ctx: Context<CreateAuction>,
+        start_time: u64,
+        end_time: u64,
+        reserve_price: u64,
+        min_bid_increment: u64,
+    ) -> Result<()> {
+        auction::create_auction(ctx, start_time, end_time, reserve_price, min_bid_increment)
+    }
+    pub fn create_sealed_auction(
+        ctx: Context<CreateAuction>,
+        start_time: u64,
+        end_time: u64,
+        reveal_end_time: u64,
+        reserve_price: u64,
+        second_price: bool,
+    ) -> Result<()> {
+        auction::create_sealed_auction(
+            ctx,
+            start_time,
+            end_time,
+            reveal_end_time,
+            reserve_price,
+            second_price,
+        )
+    }
+    pub fn bid(ctx: Context<Bid>, amount: u64, deposit_shortfall: bool) -> Result<()> {
+        auction::bid(ctx, amount, deposit_shortfall)
+    }
+    pub fn commit_bid(
+        ctx: Context<CommitBid>,
+        commitment: [u8; 32],
+        deposit: u64,
+        deposit_shortfall: bool,
+    ) -> Result<()> {
+        auction::commit_bid(ctx, commitment, deposit, deposit_shortfall)
+    }
+    pub fn reveal_bid(ctx: Context<RevealBid>, amount: u64, salt: [u8; 32]) -> Result<()> {
+        auction::reveal_bid(ctx, amount, salt)
+    }
+    pub fn refund_sealed_bid(ctx: Context<RefundSealedBid>) -> Result<()> {
+        auction::refund_sealed_bid(ctx)
+    }
+    pub fn settle_auction<'info>(
+        ctx: Context<'_, '_, '_, 'info, SettleAuction<'info>>,
+    ) -> Result<()> {
+        auction::settle_auction(ctx)
+    }
 
     // sale
-    pub fn execute_sale<'info>(ctx: Context<'_, '_, '_, 'info, ExecuteSale<'info>>) -> Result<()> {
-        execute_sale::handle(ctx)
+    pub fn execute_sale<'info>(
+        ctx: Context<'_, '_, '_, 'info, ExecuteSale<'info>>,
+        max_total_cost: Option<u64>,
+    ) -> Result<()> {
+        execute_sale::handle(ctx, max_total_cost)
+    }
+    pub fn accept_offer<'info>(ctx: Context<'_, '_, '_, 'info, AcceptOffer<'info>>) -> Result<()> {
+        execute_sale::accept_offer(ctx)
     }
 }
@@ -4,8 +4,14 @@ use anchor_spl::token::{Mint, Token};
 
 use crate::{constant::*, states::*, utils::*, error::*};
 
-/// Offer buy NFT with price & expiry date.
-pub fn buy(ctx: Context<Buy>, price: u64, buyer_expiry: Option<u64>) -> Result<()> {
+/// Offer buy NFT with price & fill window, reserving the offered price from the buyer escrow.
+pub fn buy(
+    ctx: Context<Buy>,
+    price: u64,
+    buyer_expiry: Option<u64>,
+    deposit_shortfall: bool,
+    buyer_start_time: Option<u64>,
+) -> Result<()> {
 
     // Check expiry date
     let mut _expiry = 0;
@@ -16,20 +22,85 @@ pub fn buy(ctx: Context<Buy>, price: u64, buyer_expiry: Option<u64>) -> Result<(
         _expiry = expiry;
     }
 
+    // Check start time
+    let _start_time = buyer_start_time.unwrap_or(0);
+    require!(
+        _expiry == 0 || _start_time < _expiry,
+        MarketplaceError::InvalidExpiry,
+    );
+
+    let is_native = ctx.accounts.treasury_mint.key() == spl_token::native_mint::id();
+
+    let ah_key = ctx.accounts.auction_house.key();
+    let wallet_key = ctx.accounts.buyer.key();
+
+    // If first time to use the escrow ledger
+    if is_zero_account(&ctx.accounts.escrow_state.to_account_info()) {
+        ctx.accounts.escrow_state.wallet = wallet_key;
+        ctx.accounts.escrow_state.auction_house = ah_key;
+    }
+    let balance = escrow_balance(&ctx.accounts.escrow_payment_account.to_account_info(), is_native)?;
+    ctx.accounts.escrow_state.sync(balance)?;
+
     // If first time to offer
     if is_zero_account(&ctx.accounts.offer_account.to_account_info()) {
         
         // Fill offer account
         ctx.accounts.offer_account.buyer = ctx.accounts.buyer.key();
         ctx.accounts.offer_account.nft_mint = ctx.accounts.nft_mint.key();
+        ctx.accounts.offer_account.auction_house = ctx.accounts.auction_house.key();
+    } else {
+        // Release the previous reservation before committing the new price
+        ctx.accounts.escrow_state.release(ctx.accounts.offer_account.escrow_amount)?;
+    }
+
+    // Check free escrow covers the offer, topping it up first if requested
+    let free = ctx.accounts.escrow_state.free;
+    if free < price {
+        require!(deposit_shortfall, MarketplaceError::InsufficientEscrow);
+
+        let escrow_signer_seeds = [
+            PREFIX,
+            ah_key.as_ref(),
+            wallet_key.as_ref(),
+            &[bump(&[PREFIX, ah_key.as_ref(), wallet_key.as_ref()], ctx.program_id)],
+        ];
+
+        transfer_to_escrow(
+            &ctx.accounts.buyer.to_account_info(),
+            &ctx.accounts.payment_account,
+            &ctx.accounts.escrow_payment_account,
+            &ctx.accounts.treasury_mint,
+            &ctx.accounts.auction_house.to_account_info(),
+            &ctx.accounts.token_program,
+            &ctx.accounts.system_program,
+            &ctx.accounts.rent,
+            &escrow_signer_seeds,
+            price - free,
+            is_native,
+        )?;
+        ctx.accounts.escrow_state.deposit(price - free)?;
     }
+    ctx.accounts.escrow_state.commit(price)?;
+
+    // Restart any negotiation on the previous offer terms
+    reset_counter_offer(&ctx.accounts.counter_account.to_account_info(), ctx.program_id)?;
 
     // Update price and expiry date
     ctx.accounts.offer_account.price = price;
     ctx.accounts.offer_account.expiry = _expiry;
+    ctx.accounts.offer_account.escrow_amount = price;
+    ctx.accounts.offer_account.start_time = _start_time;
+    ctx.accounts.offer_account.created_at = Clock::get()?.unix_timestamp as u64;
 
     // Log offer detail
-    msg!("{{\"price\": \"{}\", \"buyer_expiry\": {}}}", price, _expiry);
+    msg!(
+        "{{\"price\": \"{}\", \"buyer_start_time\": {}, \"buyer_expiry\": {}, \"escrow_amount\": \"{}\"}}",
+        price,
+        _start_time,
+        _expiry,
+        price
+    );
 
     Ok(())
 }
@@ -55,6 +126,39 @@ pub struct Buy<'info> {
         has_one=treasury_mint,
     )]
     pub auction_house: Account<'info, AuctionHouse>,
+
+    /// CHECK: Validated in transfer_to_escrow.
+    /// Buyer SOL or SPL account to top up the escrow from when `deposit_shortfall` is set.
+    #[account(mut)]
+    pub payment_account: UncheckedAccount<'info>,
+
+    /// CHECK: Not dangerous. Account seeds checked in constraint.
+    /// Buyer escrow payment account PDA.
+    #[account(
+        mut,
+        seeds = [
+            PREFIX,
+            auction_house.key().as_ref(),
+            buyer.key().as_ref()
+        ],
+        bump
+    )]
+    pub escrow_payment_account: UncheckedAccount<'info>,
+
+    /// Buyer escrow ledger PDA account.
+    #[account(
+        init_if_needed,
+        seeds = [
+            PREFIX,
+            auction_house.key().as_ref(),
+            buyer.key().as_ref(),
+            ESCROW_STATE
+        ],
+        bump,
+        space = 8 + std::mem::size_of::<BuyerEscrowState>(),
+        payer = buyer
+    )]
+    pub escrow_state: Box<Account<'info, BuyerEscrowState>>,
     
     /// NFT mint account
     /// CHECK: Validated as a nft account.
@@ -65,6 +169,7 @@ pub struct Buy<'info> {
         init_if_needed,
         seeds=[
             PREFIX,
+            auction_house.key().as_ref(),
             nft_mint.key().as_ref(),
             buyer.key().as_ref(),
             OFFER
@@ -75,6 +180,19 @@ pub struct Buy<'info> {
     )]
     pub offer_account: Account<'info, OfferAccount>,
 
+    /// CHECK: Not dangerous. Account seeds checked in constraint.
+    /// Counter-offer PDA account, reset when the offer terms change.
+    #[account(
+        mut,
+        seeds=[
+            PREFIX,
+            offer_account.key().as_ref(),
+            COUNTER_OFFER
+        ],
+        bump
+    )]
+    pub counter_account: UncheckedAccount<'info>,
+
     pub token_program: Program<'info, Token>,
     pub system_program: Program<'info, System>,
     pub rent: Sysvar<'info, Rent>,
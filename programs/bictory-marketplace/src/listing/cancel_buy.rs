@@ -2,18 +2,73 @@ use anchor_lang::prelude::*;
 use anchor_lang::solana_program::clock::Clock;
 use anchor_spl::token::{Mint, Token};
 
-use crate::{constant::*, states::*, error::*};
+use crate::{constant::*, states::*, utils::*};
 
 /// Cancel offer.
 pub fn cancel_buy(ctx: Context<CancelBuy>) -> Result<()> {
-    // Check expiration date
+    let is_native = ctx.accounts.treasury_mint.key() == spl_token::native_mint::id();
+
+    // Cancelling a live offer waits out the cooldown and pays the cancellation fee
     let now = Clock::get()?.unix_timestamp as u64;
-    require!(
-        ctx.accounts.offer_account.expiry == 0 ||
-        ctx.accounts.offer_account.expiry < now,
-        MarketplaceError::InvalidExpiry,
+    let offer_account = &ctx.accounts.offer_account;
+    let cancel_fee = ctx.accounts.auction_house.cancel_fee(
+        offer_account.price,
+        offer_account.created_at,
+        offer_account.expiry,
+        now,
+    )?;
+
+    let balance = escrow_balance(&ctx.accounts.escrow_payment_account.to_account_info(), is_native)?;
+    ctx.accounts.escrow_state.sync(balance)?;
+
+    // Closing the offer releases its escrow reservation, less the fee paid out of escrow
+    let escrow_amount = ctx.accounts.offer_account.escrow_amount;
+    ctx.accounts.escrow_state.spend(escrow_amount, cancel_fee)?;
+    if cancel_fee > 0 {
+        let auction_house = &ctx.accounts.auction_house;
+        let ah_key = auction_house.key();
+        let wallet_key = ctx.accounts.buyer.key();
+        let escrow_signer_seeds = [
+            PREFIX,
+            ah_key.as_ref(),
+            wallet_key.as_ref(),
+            &[bump(&[PREFIX, ah_key.as_ref(), wallet_key.as_ref()], ctx.program_id)]
+        ];
+        let ah_seeds = [
+            PREFIX,
+            auction_house.creator.as_ref(),
+            auction_house.treasury_mint.as_ref(),
+            &[bump(&[PREFIX, auction_house.creator.as_ref(), auction_house.treasury_mint.as_ref()], ctx.program_id)]
+        ];
+
+        // with the native account, the escrow is its own owner,
+        // whereas with token, it is the auction house that is owner.
+        let escrow_signer = if is_native {
+            escrow_signer_seeds
+        } else {
+            ah_seeds
+        };
+
+        transfer_from_escrow(
+            &ctx.accounts.escrow_payment_account.to_account_info(),
+            &ctx.accounts.auction_house_treasury.to_account_info(),
+            &auction_house.to_account_info(),
+            &ctx.accounts.token_program.to_account_info(),
+            &ctx.accounts.system_program.to_account_info(),
+            &escrow_signer,
+            cancel_fee,
+            is_native,
+        )?;
+    }
+    msg!(
+        "{{\"released_escrow_amount\": \"{}\", \"cancel_fee\": \"{}\"}}",
+        escrow_amount - cancel_fee,
+        cancel_fee
     );
 
+    // End any negotiation on the offer
+    reset_counter_offer(&ctx.accounts.counter_account.to_account_info(), ctx.program_id)?;
+
     Ok(())
 }
 
@@ -35,9 +90,28 @@ pub struct CancelBuy<'info> {
         seeds=[PREFIX, auction_house.creator.as_ref(), treasury_mint.key().as_ref()], 
         bump, 
         has_one=authority, 
-        has_one=treasury_mint
+        has_one=treasury_mint,
+        has_one=auction_house_treasury
     )]
     pub auction_house: Account<'info, AuctionHouse>,
+
+    /// Auction House treasury PDA account.
+    /// CHECK: Not dangerous. Account seeds checked in constraint.
+    #[account(mut, seeds=[PREFIX, auction_house.key().as_ref(), TREASURY], bump)]
+    pub auction_house_treasury: UncheckedAccount<'info>,
+
+    /// CHECK: Not dangerous. Account seeds checked in constraint.
+    /// Buyer escrow payment account PDA.
+    #[account(
+        mut,
+        seeds = [
+            PREFIX,
+            auction_house.key().as_ref(),
+            buyer.key().as_ref()
+        ],
+        bump
+    )]
+    pub escrow_payment_account: UncheckedAccount<'info>,
     
     /// NFT mint account
     /// CHECK: Validated as a nft account.
@@ -48,6 +122,7 @@ pub struct CancelBuy<'info> {
         mut,
         seeds=[
             PREFIX,
+            auction_house.key().as_ref(),
             nft_mint.key().as_ref(),
             buyer.key().as_ref(),
             OFFER
@@ -57,6 +132,32 @@ pub struct CancelBuy<'info> {
     )]
     pub offer_account: Account<'info, OfferAccount>,
 
+    /// CHECK: Not dangerous. Account seeds checked in constraint.
+    /// Counter-offer PDA account, reset when the offer closes.
+    #[account(
+        mut,
+        seeds=[
+            PREFIX,
+            offer_account.key().as_ref(),
+            COUNTER_OFFER
+        ],
+        bump
+    )]
+    pub counter_account: UncheckedAccount<'info>,
+
+    /// Buyer escrow ledger PDA account.
+    #[account(
+        mut,
+        seeds = [
+            PREFIX,
+            auction_house.key().as_ref(),
+            buyer.key().as_ref(),
+            ESCROW_STATE
+        ],
+        bump
+    )]
+    pub escrow_state: Box<Account<'info, BuyerEscrowState>>,
+
     pub token_program: Program<'info, Token>,
     pub system_program: Program<'info, System>,
     pub rent: Sysvar<'info, Rent>,
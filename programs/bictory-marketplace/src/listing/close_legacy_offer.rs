@@ -0,0 +1,42 @@
+use anchor_lang::prelude::*;
+
+use crate::{constant::*, states::*, utils::*, error::*};
+
+/// Close an offer made before offers were scoped to an auction house and backed by the escrow ledger.
+/// Such offers reserved no escrow, so they are not carried over and the buyer offers again with `buy`.
+pub fn close_legacy_offer(ctx: Context<CloseLegacyOffer>) -> Result<()> {
+    let legacy_info = ctx.accounts.legacy_offer_account.to_account_info();
+    let legacy: LegacyOfferAccount = load_legacy_account::<OfferAccount, _>(&legacy_info, ctx.program_id)?;
+    require!(legacy.buyer == ctx.accounts.buyer.key(), MarketplaceError::InvalidOwner);
+
+    close_legacy_account(&legacy_info, &ctx.accounts.buyer.to_account_info())?;
+
+    msg!("{{\"price\": \"{}\", \"buyer_expiry\": {}}}", legacy.price, legacy.expiry);
+
+    Ok(())
+}
+
+#[derive(Accounts)]
+pub struct CloseLegacyOffer<'info> {
+    /// Buyer account.
+    #[account(mut)]
+    pub buyer: Signer<'info>,
+
+    /// NFT mint account
+    /// CHECK: Only used as a seed of the legacy offer.
+    pub nft_mint: UncheckedAccount<'info>,
+
+    /// CHECK: Account seeds checked in constraint, deserialized in close_legacy_offer.
+    /// Offer PDA account in the pre-upgrade layout.
+    #[account(
+        mut,
+        seeds=[
+            PREFIX,
+            nft_mint.key().as_ref(),
+            buyer.key().as_ref(),
+            OFFER
+        ],
+        bump
+    )]
+    pub legacy_offer_account: UncheckedAccount<'info>,
+}
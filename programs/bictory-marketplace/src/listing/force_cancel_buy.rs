@@ -0,0 +1,87 @@
+use anchor_lang::prelude::*;
+use anchor_spl::token::Mint;
+
+use crate::{constant::*, states::*, utils::*};
+
+/// Cancel offer by the auction house authority for moderation, releasing its escrow reservation without fee.
+pub fn force_cancel_buy(ctx: Context<ForceCancelBuy>) -> Result<()> {
+    ctx.accounts.escrow_state.release(ctx.accounts.offer_account.escrow_amount)?;
+    msg!(
+        "{{\"buyer\": \"{}\", \"released_escrow_amount\": \"{}\"}}",
+        ctx.accounts.buyer.key(),
+        ctx.accounts.offer_account.escrow_amount
+    );
+
+    // End any negotiation on the offer
+    reset_counter_offer(&ctx.accounts.counter_account.to_account_info(), ctx.program_id)?;
+
+    Ok(())
+}
+
+#[derive(Accounts)]
+pub struct ForceCancelBuy<'info> {
+    /// Authority key for the Auction House.
+    pub authority: Signer<'info>,
+
+    /// CHECK: Used in offer and escrow ledger seeds.
+    /// Buyer account receiving the offer rent.
+    #[account(mut)]
+    pub buyer: UncheckedAccount<'info>,
+
+    /// Treasury mint account, either native SOL mint or a SPL token mint.
+    pub treasury_mint: Account<'info, Mint>,
+
+    /// Auction House instance PDA account.
+    #[account(
+        seeds=[PREFIX, auction_house.creator.as_ref(), treasury_mint.key().as_ref()],
+        bump,
+        has_one=authority,
+        has_one=treasury_mint
+    )]
+    pub auction_house: Account<'info, AuctionHouse>,
+
+    /// NFT mint account
+    /// CHECK: Not dangerous. Used in offer account seeds.
+    pub nft_mint: UncheckedAccount<'info>,
+
+    /// Offer PDA account
+    #[account(
+        mut,
+        seeds=[
+            PREFIX,
+            auction_house.key().as_ref(),
+            nft_mint.key().as_ref(),
+            buyer.key().as_ref(),
+            OFFER
+        ],
+        bump,
+        close = buyer
+    )]
+    pub offer_account: Account<'info, OfferAccount>,
+
+    /// CHECK: Not dangerous. Account seeds checked in constraint.
+    /// Counter-offer PDA account, reset when the offer closes.
+    #[account(
+        mut,
+        seeds=[
+            PREFIX,
+            offer_account.key().as_ref(),
+            COUNTER_OFFER
+        ],
+        bump
+    )]
+    pub counter_account: UncheckedAccount<'info>,
+
+    /// Buyer escrow ledger PDA account.
+    #[account(
+        mut,
+        seeds = [
+            PREFIX,
+            auction_house.key().as_ref(),
+            buyer.key().as_ref(),
+            ESCROW_STATE
+        ],
+        bump
+    )]
+    pub escrow_state: Box<Account<'info, BuyerEscrowState>>,
+}
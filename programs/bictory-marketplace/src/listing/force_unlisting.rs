@@ -0,0 +1,152 @@
+use anchor_lang::prelude::*;
+use anchor_spl::token::{self, Mint, Token, TokenAccount, SetAuthority};
+use spl_token::instruction::AuthorityType;
+
+use crate::{constant::*, states::*, utils::*, error::*};
+
+/// Unlisting NFT by the auction house authority for moderation, returning it to the seller without fee.
+pub fn force_unlisting(ctx: Context<ForceUnlisting>) -> Result<()> {
+    let ah_key = ctx.accounts.auction_house.key();
+    let signer_seeds = &[
+        PREFIX,
+        ah_key.as_ref(),
+        TREASURY,
+        &[bump(&[PREFIX, ah_key.as_ref(), TREASURY], ctx.program_id)],
+    ];
+    let signer = &[&signer_seeds[..]];
+
+    let custody = ctx.accounts.listing_account.custody;
+    assert_listed_nft(
+        ctx.program_id,
+        &ctx.accounts.nft_account.to_account_info(),
+        &ctx.accounts.listing_account.key(),
+        &ctx.accounts.seller.key(),
+        &ctx.accounts.nft_mint.key(),
+        &ctx.accounts.auction_house_treasury.key(),
+        custody,
+    )?;
+
+    match custody {
+        ListingCustody::Owner => {
+            // Set nft's authority back to the seller
+            let cpi_account = SetAuthority {
+                current_authority: ctx.accounts.auction_house_treasury.to_account_info(),
+                account_or_mint: ctx.accounts.nft_account.to_account_info(),
+            };
+            token::set_authority(
+                CpiContext::new_with_signer(ctx.accounts.token_program.to_account_info(), cpi_account, signer),
+                AuthorityType::AccountOwner,
+                Some(ctx.accounts.seller.key()),
+            )?;
+        }
+        ListingCustody::Delegate | ListingCustody::Frozen => {
+            // SPL Token only lets the owner revoke a delegate, so the treasury stays approved until the seller
+            // revokes it or lists again. It is inert meanwhile: the treasury only moves a delegated NFT against
+            // a listing with delegate custody, and only the seller can create one.
+            thaw_listed_nft(
+                custody,
+                &ctx.accounts.nft_account.to_account_info(),
+                &ctx.accounts.edition,
+                &ctx.accounts.nft_mint,
+                &ctx.accounts.auction_house_treasury,
+                &ctx.accounts.token_metadata_program,
+                &ctx.accounts.token_program.to_account_info(),
+                signer_seeds,
+            )?;
+        }
+        ListingCustody::Vault => {
+            assert_is_ata(
+                &ctx.accounts.seller_nft_account,
+                &ctx.accounts.seller.key(),
+                &ctx.accounts.nft_mint.key(),
+            )?;
+            close_vault(
+                &ctx.accounts.nft_account,
+                &ctx.accounts.seller_nft_account,
+                &ctx.accounts.seller,
+                &ctx.accounts.auction_house_treasury,
+                &ctx.accounts.token_program,
+                signer,
+            )?;
+        }
+    }
+
+    msg!(
+        "{{\"seller\": \"{}\", \"delegate_revoke_pending\": {}}}",
+        ctx.accounts.seller.key(),
+        custody == ListingCustody::Delegate || custody == ListingCustody::Frozen
+    );
+
+    Ok(())
+}
+
+#[derive(Accounts)]
+pub struct ForceUnlisting<'info> {
+    /// Authority key for the Auction House.
+    pub authority: Signer<'info>,
+
+    /// CHECK: Checked as the listing owner.
+    /// Seller account receiving the NFT back and the listing rent.
+    #[account(mut)]
+    pub seller: UncheckedAccount<'info>,
+
+    /// Treasury mint account, either native SOL mint or a SPL token mint.
+    pub treasury_mint: Account<'info, Mint>,
+
+    /// Auction House treasury PDA account.
+    /// CHECK: Not dangerous. Account seeds checked in constraint.
+    #[account(seeds=[PREFIX, auction_house.key().as_ref(), TREASURY], bump)]
+    pub auction_house_treasury: UncheckedAccount<'info>,
+
+    /// Auction House instance PDA account.
+    #[account(
+        seeds=[PREFIX, auction_house.creator.as_ref(), treasury_mint.key().as_ref()],
+        bump,
+        has_one=authority,
+        has_one=treasury_mint,
+        has_one=auction_house_treasury
+    )]
+    pub auction_house: Box<Account<'info, AuctionHouse>>,
+
+    /// NFT mint account
+    /// CHECK: Not dangerous. Used in listing account seeds.
+    pub nft_mint: UncheckedAccount<'info>,
+
+    /// NFT token account holding the listed NFT, the listing vault for vault listings
+    #[account(mut,
+        constraint = nft_account.mint == nft_mint.key()
+    )]
+    pub nft_account: Box<Account<'info, TokenAccount>>,
+
+    /// CHECK: Checked as the seller ATA for vault listings, unused otherwise.
+    /// Seller NFT token account receiving a vaulted NFT.
+    #[account(mut)]
+    pub seller_nft_account: UncheckedAccount<'info>,
+
+    /// CHECK: Validated by Token Metadata when thawing.
+    /// Metaplex master edition account of the NFT mint.
+    pub edition: UncheckedAccount<'info>,
+
+    /// Listing PDA account
+    #[account(
+        mut,
+        seeds=[
+            PREFIX,
+            auction_house.key().as_ref(),
+            nft_mint.key().as_ref(),
+            LISTING
+        ],
+        bump,
+        close=seller,
+        constraint = listing_account.owner == seller.key() @ MarketplaceError::InvalidOwner,
+        constraint = listing_account.auction_house == auction_house.key() @ MarketplaceError::InvalidAuctionHouse,
+    )]
+    pub listing_account: Box<Account<'info, ListingAccount>>,
+
+    /// CHECK: Account address checked in constraint.
+    /// Metaplex Token Metadata program.
+    #[account(address = mpl_token_metadata::id())]
+    pub token_metadata_program: UncheckedAccount<'info>,
+
+    pub token_program: Program<'info, Token>,
+}
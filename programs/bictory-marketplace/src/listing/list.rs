@@ -5,48 +5,31 @@ use spl_token::instruction::AuthorityType;
 
 use crate::{constant::*, states::*, utils::*, error::*};
 
-/// Listing NFT with price & expiry date.
-pub fn list(ctx: Context<Listing>, price: u64, seller_expiry: Option<u64>) -> Result<()> {
-
-    // Check expiry date
-    let mut _expiry = 0;
-    if let Some(expiry) = seller_expiry {
-        let now = Clock::get()?.unix_timestamp as u64;
-        require!(expiry >= now, MarketplaceError::InvalidExpiry);
-
-        _expiry = expiry;
-    }
-
-    // Check NFT balance
-    require!(
-        ctx.accounts.nft_account.amount > 0,
-        MarketplaceError::InvalidAmount,
-    );
-
-    // If first time to listing
-    if is_zero_account(&ctx.accounts.listing_account.to_account_info()) {
-        // Set nft's authority to treasury account
-        token::set_authority(
-            ctx.accounts.into_set_authority_context(),
-            AuthorityType::AccountOwner,
-            Some(ctx.accounts.auction_house_treasury.key()),
-        )?;
-        
-        // Fill listing account
-        ctx.accounts.listing_account.owner = ctx.accounts.seller.key();
-        ctx.accounts.listing_account.nft_mint = ctx.accounts.nft_mint.key();
-    }
-
-    // Update price and expiry date only
-    ctx.accounts.listing_account.price = price;
-    ctx.accounts.listing_account.expiry = _expiry;
+/// Listing NFT with price & sale window, optionally reserved for a single buyer and guarded by a minimum payout.
+pub fn list(ctx: Context<Listing>, price: u64, terms: ListingTerms) -> Result<()> {
+    ctx.accounts.fill_listing(price, &terms)?;
 
     // Log listing detail
-    msg!("{{\"price\": \"{}\", \"seller_expiry\": {}}}", price, _expiry);
+    msg!("{{\"price\": \"{}\", {}}}", price, terms_log(&ctx.accounts.listing_account));
 
     Ok(())
 }
 
+/// Sale terms of a listing as JSON fields, shared by the listing logs.
+pub fn terms_log(listing_account: &ListingAccount) -> String {
+    let reserved_buyer = match listing_account.reserved_buyer {
+        Some(buyer) => format!("\"{}\"", buyer),
+        None => "null".to_string(),
+    };
+    format!(
+        "\"seller_start_time\": {}, \"seller_expiry\": {}, \"reserved_buyer\": {}, \"min_net_proceeds\": \"{}\"",
+        listing_account.start_time,
+        listing_account.expiry,
+        reserved_buyer,
+        listing_account.min_net_proceeds
+    )
+}
+
 #[derive(Accounts)]
 pub struct Listing<'info> {
     /// Seller account.
@@ -76,8 +59,7 @@ pub struct Listing<'info> {
     pub auction_house: Account<'info, AuctionHouse>,
     
     /// NFT mint account
-    /// CHECK: Validated as a nft account.
-    pub nft_mint: UncheckedAccount<'info>,
+    pub nft_mint: Box<Account<'info, Mint>>,
     
     /// NFT token account
     #[account(mut,
@@ -85,11 +67,20 @@ pub struct Listing<'info> {
     )]
     pub nft_account: Box<Account<'info, TokenAccount>>,
 
+    /// CHECK: Validated in assert_nft_mint.
+    /// Metaplex metadata account decorating SPL mint account.
+    pub metadata: UncheckedAccount<'info>,
+
+    /// CHECK: Validated in assert_nft_mint.
+    /// Metaplex master edition account of the NFT mint.
+    pub edition: UncheckedAccount<'info>,
+
     /// Listing PDA account
     #[account(
         init_if_needed,
         seeds=[
             PREFIX,
+            auction_house.key().as_ref(),
             nft_mint.key().as_ref(),
             LISTING
         ],
@@ -104,6 +95,61 @@ pub struct Listing<'info> {
     pub rent: Sysvar<'info, Rent>,
 }
 impl<'info> Listing<'info> {
+    /// Hand the NFT over to the treasury on first listing and update price and sale terms.
+    pub fn fill_listing(&mut self, price: u64, terms: &ListingTerms) -> Result<()> {
+        // Check NFT balance
+        require!(
+            self.nft_account.amount > 0,
+            MarketplaceError::InvalidAmount,
+        );
+
+        // Check the mint is a genuine NFT
+        assert_nft_mint(&self.nft_mint, &self.metadata, &self.edition)?;
+
+        // If first time to listing
+        if is_zero_account(&self.listing_account.to_account_info()) {
+            // Check NFT is held in the seller ATA
+            assert_is_ata2(
+                &self.nft_account.to_account_info(),
+                &self.seller.key(),
+                &self.nft_mint.key(),
+                &self.seller.key(),
+            )?;
+
+            // Set nft's authority to treasury account
+            token::set_authority(
+                self.into_set_authority_context(),
+                AuthorityType::AccountOwner,
+                Some(self.auction_house_treasury.key()),
+            )?;
+
+            // Fill listing account
+            self.listing_account.owner = self.seller.key();
+            self.listing_account.nft_mint = self.nft_mint.key();
+            self.listing_account.auction_house = self.auction_house.key();
+        } else {
+            // Only the seller can relist, on the same auction house and with the NFT still in treasury custody
+            require!(
+                self.listing_account.owner == self.seller.key() &&
+                self.listing_account.custody == ListingCustody::Owner,
+                MarketplaceError::InvalidOwner,
+            );
+            require!(
+                self.listing_account.auction_house == self.auction_house.key(),
+                MarketplaceError::InvalidAuctionHouse,
+            );
+            assert_is_ata2(
+                &self.nft_account.to_account_info(),
+                &self.seller.key(),
+                &self.nft_mint.key(),
+                &self.auction_house_treasury.key(),
+            )?;
+        }
+
+        // Update price and sale terms, relisting without a reserved buyer makes the listing public again
+        self.listing_account.set_terms(price, terms, Clock::get()?.unix_timestamp as u64)
+    }
+
     fn into_set_authority_context(&self) -> CpiContext<'_, '_, '_, 'info, SetAuthority<'info>> {
         let cpi_account = SetAuthority {
             current_authority: self.seller.to_account_info().clone(),
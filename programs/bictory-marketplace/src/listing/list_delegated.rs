@@ -0,0 +1,190 @@
+use anchor_lang::prelude::*;
+use anchor_lang::solana_program::{clock::Clock, program::invoke_signed};
+use anchor_spl::token::{self, Approve, Mint, Token, TokenAccount};
+use mpl_token_metadata::instruction::freeze_delegated_account;
+
+use crate::{constant::*, states::*, utils::*, error::*, listing::terms_log};
+
+/// Listing NFT without giving up the token account, approving the treasury as delegate instead.
+/// With `freeze` the token account is also frozen through Token Metadata until sold or unlisted.
+pub fn list_delegated(
+    ctx: Context<ListDelegated>,
+    price: u64,
+    terms: ListingTerms,
+    freeze: bool,
+) -> Result<()> {
+    // Check NFT balance, the seller keeps holding it
+    require!(
+        ctx.accounts.nft_account.owner == ctx.accounts.seller.key(),
+        MarketplaceError::InvalidOwner,
+    );
+    require!(
+        ctx.accounts.nft_account.amount > 0,
+        MarketplaceError::InvalidAmount,
+    );
+
+    // Check the mint is a genuine NFT
+    assert_nft_mint(&ctx.accounts.nft_mint, &ctx.accounts.metadata, &ctx.accounts.edition)?;
+
+    // If first time to listing
+    if is_zero_account(&ctx.accounts.listing_account.to_account_info()) {
+        // Check NFT is held in the seller ATA
+        assert_is_ata(
+            &ctx.accounts.nft_account.to_account_info(),
+            &ctx.accounts.seller.key(),
+            &ctx.accounts.nft_mint.key(),
+        )?;
+
+        // Approve treasury account as delegate of the NFT
+        let cpi_account = Approve {
+            to: ctx.accounts.nft_account.to_account_info(),
+            delegate: ctx.accounts.auction_house_treasury.to_account_info(),
+            authority: ctx.accounts.seller.to_account_info(),
+        };
+        token::approve(
+            CpiContext::new(ctx.accounts.token_program.to_account_info(), cpi_account),
+            1,
+        )?;
+
+        let mut custody = ListingCustody::Delegate;
+        if freeze {
+            let ah_key = ctx.accounts.auction_house.key();
+            let treasury_signer_seeds = &[
+                PREFIX,
+                ah_key.as_ref(),
+                TREASURY,
+                &[bump(&[PREFIX, ah_key.as_ref(), TREASURY], ctx.program_id)],
+            ];
+
+            invoke_signed(
+                &freeze_delegated_account(
+                    mpl_token_metadata::id(),
+                    ctx.accounts.auction_house_treasury.key(),
+                    ctx.accounts.nft_account.key(),
+                    ctx.accounts.edition.key(),
+                    ctx.accounts.nft_mint.key(),
+                ),
+                &[
+                    ctx.accounts.auction_house_treasury.to_account_info(),
+                    ctx.accounts.nft_account.to_account_info(),
+                    ctx.accounts.edition.to_account_info(),
+                    ctx.accounts.nft_mint.to_account_info(),
+                    ctx.accounts.token_program.to_account_info(),
+                    ctx.accounts.token_metadata_program.to_account_info(),
+                ],
+                &[treasury_signer_seeds],
+            )?;
+            custody = ListingCustody::Frozen;
+        }
+
+        // Fill listing account
+        ctx.accounts.listing_account.owner = ctx.accounts.seller.key();
+        ctx.accounts.listing_account.nft_mint = ctx.accounts.nft_mint.key();
+        ctx.accounts.listing_account.auction_house = ctx.accounts.auction_house.key();
+        ctx.accounts.listing_account.custody = custody;
+    } else {
+        // Only the seller can relist, on the same auction house and with the NFT still delegated
+        let custody = ctx.accounts.listing_account.custody;
+        require!(
+            ctx.accounts.listing_account.owner == ctx.accounts.seller.key() &&
+            (custody == ListingCustody::Delegate || custody == ListingCustody::Frozen),
+            MarketplaceError::InvalidOwner,
+        );
+        require!(
+            ctx.accounts.listing_account.auction_house == ctx.accounts.auction_house.key(),
+            MarketplaceError::InvalidAuctionHouse,
+        );
+        assert_listed_nft(
+            ctx.program_id,
+            &ctx.accounts.nft_account.to_account_info(),
+            &ctx.accounts.listing_account.key(),
+            &ctx.accounts.seller.key(),
+            &ctx.accounts.nft_mint.key(),
+            &ctx.accounts.auction_house_treasury.key(),
+            custody,
+        )?;
+    }
+
+    // Update price and sale terms
+    ctx.accounts.listing_account.set_terms(price, &terms, Clock::get()?.unix_timestamp as u64)?;
+
+    // Log listing detail
+    msg!(
+        "{{\"price\": \"{}\", {}, \"frozen\": {}}}",
+        price,
+        terms_log(&ctx.accounts.listing_account),
+        ctx.accounts.listing_account.custody == ListingCustody::Frozen
+    );
+
+    Ok(())
+}
+
+#[derive(Accounts)]
+pub struct ListDelegated<'info> {
+    /// Seller account.
+    #[account(mut)]
+    pub seller: Signer<'info>,
+
+    /// Treasury mint account, either native SOL mint or a SPL token mint.
+    pub treasury_mint: Account<'info, Mint>,
+
+    /// Authority key for the Auction House.
+    /// CHECK: Validated as a auction house signer.
+    pub authority: UncheckedAccount<'info>,
+
+    /// Auction House treasury PDA account.
+    /// CHECK: Not dangerous. Account seeds checked in constraint.
+    #[account(seeds=[PREFIX, auction_house.key().as_ref(), TREASURY], bump)]
+    pub auction_house_treasury: UncheckedAccount<'info>,
+
+    /// Auction House instance PDA account.
+    #[account( 
+        seeds=[PREFIX, auction_house.creator.as_ref(), treasury_mint.key().as_ref()], 
+        bump, 
+        has_one=authority, 
+        has_one=treasury_mint, 
+        has_one=auction_house_treasury
+    )]
+    pub auction_house: Account<'info, AuctionHouse>,
+    
+    /// NFT mint account
+    pub nft_mint: Box<Account<'info, Mint>>,
+    
+    /// NFT token account
+    #[account(mut,
+        constraint = nft_account.mint == nft_mint.key()
+    )]
+    pub nft_account: Box<Account<'info, TokenAccount>>,
+
+    /// CHECK: Validated in assert_nft_mint.
+    /// Metaplex metadata account decorating SPL mint account.
+    pub metadata: UncheckedAccount<'info>,
+
+    /// CHECK: Validated in assert_nft_mint.
+    /// Metaplex master edition account of the NFT mint.
+    pub edition: UncheckedAccount<'info>,
+
+    /// Listing PDA account
+    #[account(
+        init_if_needed,
+        seeds=[
+            PREFIX,
+            auction_house.key().as_ref(),
+            nft_mint.key().as_ref(),
+            LISTING
+        ],
+        bump, 
+        space=8 + std::mem::size_of::<ListingAccount>(),
+        payer=seller
+    )]
+    pub listing_account: Account<'info, ListingAccount>,
+
+    /// CHECK: Account address checked in constraint.
+    /// Metaplex Token Metadata program.
+    #[account(address = mpl_token_metadata::id())]
+    pub token_metadata_program: UncheckedAccount<'info>,
+
+    pub token_program: Program<'info, Token>,
+    pub system_program: Program<'info, System>,
+    pub rent: Sysvar<'info, Rent>,
+}
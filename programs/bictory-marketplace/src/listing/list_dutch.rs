@@ -0,0 +1,60 @@
+use anchor_lang::prelude::*;
+use anchor_lang::solana_program::clock::Clock;
+
+use crate::{states::*, error::*, listing::{Listing, terms_log}};
+
+/// Listing NFT with a price declining from start price to end price over the decay duration.
+/// `start_time` starts the decay, the listing opens at the start time of `terms`.
+pub fn list_dutch(
+    ctx: Context<Listing>,
+    start_price: u64,
+    end_price: u64,
+    start_time: u64,
+    decay_duration: u64,
+    curve: PriceCurve,
+    terms: ListingTerms,
+) -> Result<()> {
+
+    // Check price schedule
+    require!(start_price >= end_price, MarketplaceError::InvalidAmount);
+    require!(decay_duration > 0, MarketplaceError::InvalidAmount);
+    if let PriceCurve::Step { step_duration } = curve {
+        require!(
+            step_duration > 0 && step_duration <= decay_duration,
+            MarketplaceError::InvalidAmount,
+        );
+    }
+
+    ctx.accounts.fill_listing(end_price, &terms)?;
+    let _expiry = ctx.accounts.listing_account.expiry;
+
+    // Check the decay is still running and starts before the listing expires
+    let now = Clock::get()?.unix_timestamp as u64;
+    require!(
+        start_time.saturating_add(decay_duration) > now,
+        MarketplaceError::InvalidExpiry,
+    );
+    require!(
+        _expiry == 0 || start_time < _expiry,
+        MarketplaceError::InvalidExpiry,
+    );
+    ctx.accounts.listing_account.dutch = Some(DutchPricing {
+        start_price,
+        end_price,
+        start_time,
+        decay_duration,
+        curve,
+    });
+
+    // Log listing detail
+    msg!(
+        "{{\"start_price\": \"{}\", \"end_price\": \"{}\", \"start_time\": {}, \"decay_duration\": {}, {}}}",
+        start_price,
+        end_price,
+        start_time,
+        decay_duration,
+        terms_log(&ctx.accounts.listing_account)
+    );
+
+    Ok(())
+}
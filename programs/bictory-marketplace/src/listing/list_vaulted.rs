@@ -0,0 +1,138 @@
+use anchor_lang::prelude::*;
+use anchor_lang::solana_program::clock::Clock;
+use anchor_spl::token::{self, Mint, Token, TokenAccount, Transfer};
+
+use crate::{constant::*, states::*, utils::*, error::*, listing::terms_log};
+
+/// Listing NFT by moving it into a treasury-owned vault derived from the listing, leaving the seller ATA untouched.
+pub fn list_vaulted(ctx: Context<ListVaulted>, price: u64, terms: ListingTerms) -> Result<()> {
+    // Check the mint is a genuine NFT
+    assert_nft_mint(&ctx.accounts.nft_mint, &ctx.accounts.metadata, &ctx.accounts.edition)?;
+
+    // If first time to listing
+    if is_zero_account(&ctx.accounts.listing_account.to_account_info()) {
+        // Check NFT balance
+        require!(
+            ctx.accounts.nft_account.amount > 0,
+            MarketplaceError::InvalidAmount,
+        );
+
+        // Move NFT into the vault
+        let cpi_account = Transfer {
+            from: ctx.accounts.nft_account.to_account_info(),
+            to: ctx.accounts.vault.to_account_info(),
+            authority: ctx.accounts.seller.to_account_info(),
+        };
+        token::transfer(
+            CpiContext::new(ctx.accounts.token_program.to_account_info(), cpi_account),
+            1,
+        )?;
+
+        // Fill listing account
+        ctx.accounts.listing_account.owner = ctx.accounts.seller.key();
+        ctx.accounts.listing_account.nft_mint = ctx.accounts.nft_mint.key();
+        ctx.accounts.listing_account.auction_house = ctx.accounts.auction_house.key();
+        ctx.accounts.listing_account.custody = ListingCustody::Vault;
+    } else {
+        require!(
+            ctx.accounts.listing_account.owner == ctx.accounts.seller.key(),
+            MarketplaceError::InvalidOwner,
+        );
+        require!(
+            ctx.accounts.listing_account.custody == ListingCustody::Vault,
+            MarketplaceError::InvalidOwner,
+        );
+        require!(
+            ctx.accounts.listing_account.auction_house == ctx.accounts.auction_house.key(),
+            MarketplaceError::InvalidAuctionHouse,
+        );
+    }
+
+    // Update price and sale terms
+    ctx.accounts.listing_account.set_terms(price, &terms, Clock::get()?.unix_timestamp as u64)?;
+
+    // Log listing detail
+    msg!("{{\"price\": \"{}\", {}}}", price, terms_log(&ctx.accounts.listing_account));
+
+    Ok(())
+}
+
+#[derive(Accounts)]
+pub struct ListVaulted<'info> {
+    /// Seller account.
+    #[account(mut)]
+    pub seller: Signer<'info>,
+
+    /// Treasury mint account, either native SOL mint or a SPL token mint.
+    pub treasury_mint: Account<'info, Mint>,
+
+    /// Authority key for the Auction House.
+    /// CHECK: Validated as a auction house signer.
+    pub authority: UncheckedAccount<'info>,
+
+    /// Auction House treasury PDA account.
+    /// CHECK: Not dangerous. Account seeds checked in constraint.
+    #[account(seeds=[PREFIX, auction_house.key().as_ref(), TREASURY], bump)]
+    pub auction_house_treasury: UncheckedAccount<'info>,
+
+    /// Auction House instance PDA account.
+    #[account( 
+        seeds=[PREFIX, auction_house.creator.as_ref(), treasury_mint.key().as_ref()], 
+        bump, 
+        has_one=authority, 
+        has_one=treasury_mint, 
+        has_one=auction_house_treasury
+    )]
+    pub auction_house: Box<Account<'info, AuctionHouse>>,
+    
+    /// NFT mint account
+    pub nft_mint: Box<Account<'info, Mint>>,
+    
+    /// NFT token account
+    #[account(mut,
+        constraint = nft_account.mint == nft_mint.key()
+    )]
+    pub nft_account: Box<Account<'info, TokenAccount>>,
+
+    /// CHECK: Validated in assert_nft_mint.
+    /// Metaplex metadata account decorating SPL mint account.
+    pub metadata: UncheckedAccount<'info>,
+
+    /// CHECK: Validated in assert_nft_mint.
+    /// Metaplex master edition account of the NFT mint.
+    pub edition: UncheckedAccount<'info>,
+
+    /// Listing PDA account
+    #[account(
+        init_if_needed,
+        seeds=[
+            PREFIX,
+            auction_house.key().as_ref(),
+            nft_mint.key().as_ref(),
+            LISTING
+        ],
+        bump, 
+        space=8 + std::mem::size_of::<ListingAccount>(),
+        payer=seller
+    )]
+    pub listing_account: Box<Account<'info, ListingAccount>>,
+
+    /// Listing vault PDA token account, owned by the treasury.
+    #[account(
+        init_if_needed,
+        seeds=[
+            PREFIX,
+            listing_account.key().as_ref(),
+            VAULT
+        ],
+        bump,
+        token::mint = nft_mint,
+        token::authority = auction_house_treasury,
+        payer=seller
+    )]
+    pub vault: Box<Account<'info, TokenAccount>>,
+
+    pub token_program: Program<'info, Token>,
+    pub system_program: Program<'info, System>,
+    pub rent: Sysvar<'info, Rent>,
+}
@@ -0,0 +1,105 @@
+use anchor_lang::prelude::*;
+use anchor_lang::solana_program::clock::Clock;
+use anchor_spl::token::{Mint, TokenAccount};
+
+use crate::{constant::*, states::*, utils::*, error::*};
+
+/// Move a listing made before listings were scoped to an auction house to its current PDA and layout.
+pub fn migrate_listing(ctx: Context<MigrateListing>) -> Result<()> {
+    let legacy_info = ctx.accounts.legacy_listing_account.to_account_info();
+    let legacy: LegacyListingAccount = load_legacy_account::<ListingAccount, _>(&legacy_info, ctx.program_id)?;
+    require!(legacy.owner == ctx.accounts.seller.key(), MarketplaceError::InvalidOwner);
+    require!(legacy.nft_mint == ctx.accounts.nft_mint.key(), MarketplaceError::InvalidNftMint);
+
+    // Check the NFT is still held by the treasury of this auction house
+    assert_is_ata2(
+        &ctx.accounts.nft_account.to_account_info(),
+        &ctx.accounts.seller.key(),
+        &ctx.accounts.nft_mint.key(),
+        &ctx.accounts.auction_house_treasury.key(),
+    )?;
+
+    // Carry the listing terms over, new options keep their defaults
+    let listing_account = &mut ctx.accounts.listing_account;
+    listing_account.owner = legacy.owner;
+    listing_account.nft_mint = legacy.nft_mint;
+    listing_account.auction_house = ctx.accounts.auction_house.key();
+    listing_account.price = legacy.price;
+    listing_account.expiry = legacy.expiry;
+    listing_account.custody = ListingCustody::Owner;
+    listing_account.created_at = Clock::get()?.unix_timestamp as u64;
+
+    close_legacy_account(&legacy_info, &ctx.accounts.seller.to_account_info())?;
+
+    msg!("{{\"price\": \"{}\", \"seller_expiry\": {}}}", legacy.price, legacy.expiry);
+
+    Ok(())
+}
+
+#[derive(Accounts)]
+pub struct MigrateListing<'info> {
+    /// Seller account.
+    #[account(mut)]
+    pub seller: Signer<'info>,
+
+    /// Treasury mint account, either native SOL mint or a SPL token mint.
+    pub treasury_mint: Account<'info, Mint>,
+
+    /// Authority key for the Auction House.
+    /// CHECK: Validated as a auction house signer.
+    pub authority: UncheckedAccount<'info>,
+
+    /// Auction House treasury PDA account.
+    /// CHECK: Not dangerous. Account seeds checked in constraint.
+    #[account(seeds=[PREFIX, auction_house.key().as_ref(), TREASURY], bump)]
+    pub auction_house_treasury: UncheckedAccount<'info>,
+
+    /// Auction House instance PDA account.
+    #[account(
+        seeds=[PREFIX, auction_house.creator.as_ref(), treasury_mint.key().as_ref()],
+        bump,
+        has_one=authority,
+        has_one=treasury_mint,
+        has_one=auction_house_treasury
+    )]
+    pub auction_house: Account<'info, AuctionHouse>,
+
+    /// NFT mint account
+    /// CHECK: Checked against the legacy listing.
+    pub nft_mint: UncheckedAccount<'info>,
+
+    /// NFT token account
+    #[account(constraint = nft_account.mint == nft_mint.key())]
+    pub nft_account: Box<Account<'info, TokenAccount>>,
+
+    /// CHECK: Account seeds checked in constraint, deserialized in migrate_listing.
+    /// Listing PDA account in the pre-upgrade layout.
+    #[account(
+        mut,
+        seeds=[
+            PREFIX,
+            nft_mint.key().as_ref(),
+            LISTING
+        ],
+        bump
+    )]
+    pub legacy_listing_account: UncheckedAccount<'info>,
+
+    /// Listing PDA account
+    #[account(
+        init,
+        seeds=[
+            PREFIX,
+            auction_house.key().as_ref(),
+            nft_mint.key().as_ref(),
+            LISTING
+        ],
+        bump,
+        space=8 + std::mem::size_of::<ListingAccount>(),
+        payer=seller
+    )]
+    pub listing_account: Account<'info, ListingAccount>,
+
+    pub system_program: Program<'info, System>,
+    pub rent: Sysvar<'info, Rent>,
+}
@@ -1,6 +1,17 @@
 pub mod buy;
 pub mod cancel_buy;
+pub mod close_legacy_offer;
+pub mod force_cancel_buy;
+pub mod force_unlisting;
 pub mod list;
+pub mod list_delegated;
+pub mod list_dutch;
+pub mod list_vaulted;
+pub mod migrate_listing;
+pub mod unlist_vaulted;
 pub mod unlisting;
 
-pub use {buy::*, cancel_buy::*, list::*, unlisting::*};
+pub use {
+    buy::*, cancel_buy::*, close_legacy_offer::*, force_cancel_buy::*, force_unlisting::*, list::*, list_delegated::*,
+    list_dutch::*, list_vaulted::*, migrate_listing::*, unlist_vaulted::*, unlisting::*,
+};
@@ -0,0 +1,128 @@
+use anchor_lang::prelude::*;
+use anchor_lang::solana_program::clock::Clock;
+use anchor_spl::token::{Mint, Token, TokenAccount};
+
+use crate::{constant::*, states::*, utils::*, error::*};
+
+/// Unlisting a vault listing, returning the NFT to the seller and closing the vault.
+pub fn unlist_vaulted(ctx: Context<UnlistVaulted>) -> Result<()> {
+    // Cancelling a live listing waits out the cooldown and pays the cancellation fee
+    let now = Clock::get()?.unix_timestamp as u64;
+    let listing_account = &ctx.accounts.listing_account;
+    let cancel_fee = ctx.accounts.auction_house.cancel_fee(
+        listing_account.price,
+        listing_account.created_at,
+        listing_account.expiry,
+        now,
+    )?;
+    if cancel_fee > 0 {
+        pay_cancel_fee(
+            &ctx.accounts.seller.to_account_info(),
+            &ctx.accounts.payment_account,
+            &ctx.accounts.auction_house_treasury.to_account_info(),
+            &ctx.accounts.treasury_mint.key(),
+            &ctx.accounts.token_program.to_account_info(),
+            &ctx.accounts.system_program.to_account_info(),
+            cancel_fee,
+            ctx.accounts.treasury_mint.key() == spl_token::native_mint::id(),
+        )?;
+    }
+    msg!("{{\"cancel_fee\": \"{}\"}}", cancel_fee);
+
+    let ah_key = ctx.accounts.auction_house.key();
+    let treasury_signer_seeds = &[
+        PREFIX,
+        ah_key.as_ref(),
+        TREASURY,
+        &[bump(&[PREFIX, ah_key.as_ref(), TREASURY], ctx.program_id)],
+    ];
+    let treasury_signer = &[&treasury_signer_seeds[..]];
+
+    close_vault(
+        &ctx.accounts.vault,
+        &ctx.accounts.nft_account.to_account_info(),
+        &ctx.accounts.seller.to_account_info(),
+        &ctx.accounts.auction_house_treasury.to_account_info(),
+        &ctx.accounts.token_program,
+        treasury_signer,
+    )?;
+
+    Ok(())
+}
+
+#[derive(Accounts)]
+pub struct UnlistVaulted<'info> {
+    /// Seller account.
+    #[account(mut)]
+    pub seller: Signer<'info>,
+
+    /// Treasury mint account, either native SOL mint or a SPL token mint.
+    pub treasury_mint: Account<'info, Mint>,
+
+    /// Authority key for the Auction House.
+    /// CHECK: Validated as a auction house signer.
+    pub authority: UncheckedAccount<'info>,
+
+    /// Auction House treasury PDA account.
+    /// CHECK: Not dangerous. Account seeds checked in constraint.
+    #[account(mut, seeds=[PREFIX, auction_house.key().as_ref(), TREASURY], bump)]
+    pub auction_house_treasury: UncheckedAccount<'info>,
+
+    /// CHECK: Validated in pay_cancel_fee.
+    /// Seller SOL or SPL account paying the cancellation fee.
+    #[account(mut)]
+    pub payment_account: UncheckedAccount<'info>,
+
+    /// Auction House instance PDA account.
+    #[account( 
+        seeds=[PREFIX, auction_house.creator.as_ref(), treasury_mint.key().as_ref()], 
+        bump, 
+        has_one=authority, 
+        has_one=treasury_mint, 
+        has_one=auction_house_treasury
+    )]
+    pub auction_house: Box<Account<'info, AuctionHouse>>,
+    
+    /// NFT mint account
+    /// CHECK: Not dangerous. Used in listing account seeds.
+    pub nft_mint: UncheckedAccount<'info>,
+    
+    /// Seller NFT token account to return the NFT to
+    #[account(mut,
+        constraint = nft_account.mint == nft_mint.key(),
+        constraint = nft_account.owner == seller.key()
+    )]
+    pub nft_account: Box<Account<'info, TokenAccount>>,
+
+    /// Listing PDA account
+    #[account(
+        mut,
+        seeds=[
+            PREFIX,
+            auction_house.key().as_ref(),
+            nft_mint.key().as_ref(),
+            LISTING
+        ],
+        bump, 
+        close=seller,
+        constraint = listing_account.owner == seller.key() @ MarketplaceError::InvalidOwner,
+        constraint = listing_account.custody == ListingCustody::Vault @ MarketplaceError::InvalidOwner,
+        constraint = listing_account.auction_house == auction_house.key() @ MarketplaceError::InvalidAuctionHouse,
+    )]
+    pub listing_account: Box<Account<'info, ListingAccount>>,
+
+    /// Listing vault PDA token account.
+    #[account(
+        mut,
+        seeds=[
+            PREFIX,
+            listing_account.key().as_ref(),
+            VAULT
+        ],
+        bump,
+    )]
+    pub vault: Box<Account<'info, TokenAccount>>,
+
+    pub token_program: Program<'info, Token>,
+    pub system_program: Program<'info, System>,
+}
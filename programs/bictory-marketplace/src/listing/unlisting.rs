@@ -1,25 +1,34 @@
 use anchor_lang::prelude::*;
 use anchor_lang::solana_program::clock::Clock;
-use anchor_spl::token::{self, Mint, Token, TokenAccount, SetAuthority};
+use anchor_spl::token::{self, Mint, Token, TokenAccount, Revoke, SetAuthority};
 use spl_token::instruction::AuthorityType;
 
 use crate::{constant::*, states::*, utils::*, error::*};
 
 /// Unlisting NFT.
 pub fn unlisting(ctx: Context<Unlisting>) -> Result<()> {
-    // Check expiration date
+    // Cancelling a live listing waits out the cooldown and pays the cancellation fee
     let now = Clock::get()?.unix_timestamp as u64;
-    require!(
-        ctx.accounts.listing_account.expiry == 0 ||
-        ctx.accounts.listing_account.expiry < now,
-        MarketplaceError::InvalidExpiry,
-    );
-
-    // Set nft's authority to owner
-    let cpi_account = SetAuthority {
-        current_authority: ctx.accounts.auction_house_treasury.to_account_info().clone(),
-        account_or_mint: ctx.accounts.nft_account.to_account_info().clone(),
-    };
+    let listing_account = &ctx.accounts.listing_account;
+    let cancel_fee = ctx.accounts.auction_house.cancel_fee(
+        listing_account.price,
+        listing_account.created_at,
+        listing_account.expiry,
+        now,
+    )?;
+    if cancel_fee > 0 {
+        pay_cancel_fee(
+            &ctx.accounts.seller.to_account_info(),
+            &ctx.accounts.payment_account,
+            &ctx.accounts.auction_house_treasury.to_account_info(),
+            &ctx.accounts.treasury_mint.key(),
+            &ctx.accounts.token_program.to_account_info(),
+            &ctx.accounts.system_program.to_account_info(),
+            cancel_fee,
+            ctx.accounts.treasury_mint.key() == spl_token::native_mint::id(),
+        )?;
+    }
+    msg!("{{\"cancel_fee\": \"{}\"}}", cancel_fee);
 
     let ah_key = ctx.accounts.auction_house.key();
     let signer_seeds = &[
@@ -34,18 +43,58 @@ pub fn unlisting(ctx: Context<Unlisting>) -> Result<()> {
     ];
     let signer = &[&signer_seeds[..]];
 
-    let cpi_ctx = CpiContext::new_with_signer(
-        ctx.accounts.token_program.to_account_info().clone(),
-        cpi_account,
-        signer
-    );
+    let custody = ctx.accounts.listing_account.custody;
+    require!(custody != ListingCustody::Vault, MarketplaceError::VaultListing);
 
-    token::set_authority(
-        cpi_ctx,
-        AuthorityType::AccountOwner,
-        Some(ctx.accounts.seller.key()),
+    // Check the NFT account is the listed one, held for the treasury
+    assert_listed_nft(
+        ctx.program_id,
+        &ctx.accounts.nft_account.to_account_info(),
+        &ctx.accounts.listing_account.key(),
+        &ctx.accounts.seller.key(),
+        &ctx.accounts.nft_mint.key(),
+        &ctx.accounts.auction_house_treasury.key(),
+        custody,
     )?;
 
+    if custody == ListingCustody::Owner {
+        // Set nft's authority to owner
+        let cpi_account = SetAuthority {
+            current_authority: ctx.accounts.auction_house_treasury.to_account_info().clone(),
+            account_or_mint: ctx.accounts.nft_account.to_account_info().clone(),
+        };
+
+        let cpi_ctx = CpiContext::new_with_signer(
+            ctx.accounts.token_program.to_account_info().clone(),
+            cpi_account,
+            signer
+        );
+
+        token::set_authority(
+            cpi_ctx,
+            AuthorityType::AccountOwner,
+            Some(ctx.accounts.seller.key()),
+        )?;
+    } else {
+        // Thaw and revoke the treasury delegation
+        thaw_listed_nft(
+            custody,
+            &ctx.accounts.nft_account.to_account_info(),
+            &ctx.accounts.edition,
+            &ctx.accounts.nft_mint,
+            &ctx.accounts.auction_house_treasury,
+            &ctx.accounts.token_metadata_program,
+            &ctx.accounts.token_program.to_account_info(),
+            signer_seeds,
+        )?;
+
+        let cpi_account = Revoke {
+            source: ctx.accounts.nft_account.to_account_info(),
+            authority: ctx.accounts.seller.to_account_info(),
+        };
+        token::revoke(CpiContext::new(ctx.accounts.token_program.to_account_info(), cpi_account))?;
+    }
+
     Ok(())
 }
 
@@ -64,9 +113,14 @@ pub struct Unlisting<'info> {
 
     /// Auction House treasury PDA account.
     /// CHECK: Not dangerous. Account seeds checked in constraint.
-    #[account(seeds=[PREFIX, auction_house.key().as_ref(), TREASURY], bump)]
+    #[account(mut, seeds=[PREFIX, auction_house.key().as_ref(), TREASURY], bump)]
     pub auction_house_treasury: UncheckedAccount<'info>,
 
+    /// CHECK: Validated in pay_cancel_fee.
+    /// Seller SOL or SPL account paying the cancellation fee.
+    #[account(mut)]
+    pub payment_account: UncheckedAccount<'info>,
+
     /// Auction House instance PDA account.
     #[account( 
         seeds=[PREFIX, auction_house.creator.as_ref(), treasury_mint.key().as_ref()], 
@@ -87,19 +141,31 @@ pub struct Unlisting<'info> {
     )]
     pub nft_account: Box<Account<'info, TokenAccount>>,
 
+    /// CHECK: Validated by Token Metadata when thawing.
+    /// Metaplex master edition account of the NFT mint.
+    pub edition: UncheckedAccount<'info>,
+
     /// Listing PDA account
     #[account(
         mut,
         seeds=[
             PREFIX,
+            auction_house.key().as_ref(),
             nft_mint.key().as_ref(),
             LISTING
         ],
         bump, 
-        close=seller
+        close=seller,
+        constraint = listing_account.owner == seller.key() @ MarketplaceError::InvalidOwner,
+        constraint = listing_account.auction_house == auction_house.key() @ MarketplaceError::InvalidAuctionHouse,
     )]
     pub listing_account: Account<'info, ListingAccount>,
 
+    /// CHECK: Account address checked in constraint.
+    /// Metaplex Token Metadata program.
+    #[account(address = mpl_token_metadata::id())]
+    pub token_metadata_program: UncheckedAccount<'info>,
+
     pub token_program: Program<'info, Token>,
     pub system_program: Program<'info, System>,
 }
\ No newline at end of file
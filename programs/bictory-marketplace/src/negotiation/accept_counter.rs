@@ -0,0 +1,371 @@
+use anchor_lang::prelude::*;
+use anchor_lang::solana_program::clock::Clock;
+use anchor_spl::associated_token::AssociatedToken;
+use anchor_spl::token::{self, Mint, Token, TokenAccount, Transfer};
+
+use crate::{constant::*, error::*, states::*, utils::*};
+use super::reserve_offer_price;
+
+#[derive(Accounts)]
+pub struct AcceptCounter<'info> {
+    /// Listing owner or offer buyer account, answering the last counter.
+    #[account(mut)]
+    pub wallet: Signer<'info>,
+
+    /// CHECK: Validated as the listing owner.
+    /// Seller user wallet account.
+    #[account(mut, constraint = seller.key() == listing_account.owner)]
+    pub seller: UncheckedAccount<'info>,
+
+    /// CHECK: Validated as the offer buyer.
+    /// Buyer user wallet account.
+    #[account(mut, constraint = buyer.key() == offer_account.buyer)]
+    pub buyer: UncheckedAccount<'info>,
+
+    /// Treasury mint account, either native SOL mint or a SPL token mint.
+    pub treasury_mint: Box<Account<'info, Mint>>,
+
+    /// CHECK: Validated in transfer_to_escrow.
+    /// Buyer SOL or SPL account to top up the escrow from when `deposit_shortfall` is set on a buyer acceptance.
+    #[account(mut)]
+    pub payment_account: UncheckedAccount<'info>,
+
+    /// CHECK: Not dangerous. Account seeds checked in constraint.
+    /// Buyer escrow payment account PDA.
+    #[account(
+        mut,
+        seeds = [
+            PREFIX,
+            auction_house.key().as_ref(),
+            buyer.key().as_ref()
+        ],
+        bump
+    )]
+    pub escrow_payment_account: UncheckedAccount<'info>,
+
+    /// Buyer escrow ledger PDA account.
+    #[account(
+        mut,
+        seeds = [
+            PREFIX,
+            auction_house.key().as_ref(),
+            buyer.key().as_ref(),
+            ESCROW_STATE
+        ],
+        bump
+    )]
+    pub escrow_state: Box<Account<'info, BuyerEscrowState>>,
+
+    /// CHECK: Validated in accept_counter.
+    /// Seller SOL or SPL account to receive payment at.
+    #[account(mut)]
+    pub seller_payment_receipt_account: UncheckedAccount<'info>,
+
+    /// CHECK: Validated in accept_counter.
+    /// Buyer SPL token account to receive purchased item at.
+    #[account(mut)]
+    pub buyer_receipt_token_account: UncheckedAccount<'info>,
+
+    /// Auction House instance authority account.
+    /// CHECK: Validated as a auction house signer.
+    pub authority: UncheckedAccount<'info>,
+
+    /// Auction House treasury PDA account.
+    /// CHECK: Not dangerous. Account seeds checked in constraint.
+    #[account(mut, seeds=[PREFIX, auction_house.key().as_ref(), TREASURY], bump)]
+    pub auction_house_treasury: UncheckedAccount<'info>,
+
+    /// Auction House instance PDA account.
+    #[account(
+        seeds = [
+            PREFIX,
+            auction_house.creator.as_ref(),
+            auction_house.treasury_mint.as_ref()
+        ],
+        bump,
+        has_one=authority,
+        has_one=treasury_mint,
+        has_one=auction_house_treasury
+    )]
+    pub auction_house: Box<Account<'info, AuctionHouse>>,
+
+    /// CHECK: Validated in accept_counter.
+    /// NFT mint account
+    pub nft_mint: UncheckedAccount<'info>,
+
+    /// NFT token account
+    #[account(mut,
+        constraint = nft_account.mint == nft_mint.key()
+    )]
+    pub nft_account: Box<Account<'info, TokenAccount>>,
+
+    /// CHECK: Validated in accept_counter.
+    /// Metaplex metadata account decorating SPL mint account.
+    pub metadata: UncheckedAccount<'info>,
+
+    /// CHECK: Validated by Token Metadata when thawing.
+    /// Metaplex master edition account of the NFT mint, used by frozen delegated listings.
+    pub edition: UncheckedAccount<'info>,
+
+    /// Listing PDA account
+    #[account(
+        mut,
+        seeds=[
+            PREFIX,
+            auction_house.key().as_ref(),
+            nft_mint.key().as_ref(),
+            LISTING
+        ],
+        bump,
+        close = seller,
+        constraint = listing_account.nft_mint == nft_mint.key(),
+    )]
+    pub listing_account: Box<Account<'info, ListingAccount>>,
+
+    /// Offer PDA account
+    #[account(
+        mut,
+        seeds=[
+            PREFIX,
+            auction_house.key().as_ref(),
+            nft_mint.key().as_ref(),
+            buyer.key().as_ref(),
+            OFFER
+        ],
+        bump,
+        close = buyer,
+        constraint = offer_account.nft_mint == nft_mint.key(),
+    )]
+    pub offer_account: Box<Account<'info, OfferAccount>>,
+
+    /// Counter-offer PDA account
+    #[account(
+        mut,
+        seeds=[
+            PREFIX,
+            offer_account.key().as_ref(),
+            COUNTER_OFFER
+        ],
+        bump,
+        close = seller,
+        constraint = counter_account.offer == offer_account.key(),
+        constraint = counter_account.seller == seller.key() && counter_account.buyer == buyer.key(),
+    )]
+    pub counter_account: Box<Account<'info, CounterOfferAccount>>,
+
+    /// CHECK: Account address checked in constraint.
+    /// Metaplex Token Metadata program.
+    #[account(address = mpl_token_metadata::id())]
+    pub token_metadata_program: UncheckedAccount<'info>,
+
+    pub token_program: Program<'info, Token>,
+    pub system_program: Program<'info, System>,
+    pub ata_program: Program<'info, AssociatedToken>,
+    pub rent: Sysvar<'info, Rent>,
+}
+
+/// Accept the last counter of the other party, settling the sale at the counter price.
+pub fn accept_counter<'info>(
+    ctx: Context<'_, '_, '_, 'info, AcceptCounter<'info>>,
+    deposit_shortfall: bool,
+) -> Result<()> {
+    let wallet_key = ctx.accounts.wallet.key();
+    let by_seller = wallet_key == ctx.accounts.seller.key();
+    require!(
+        by_seller || wallet_key == ctx.accounts.buyer.key(),
+        MarketplaceError::InvalidOwner,
+    );
+
+    // Check the last counter came from the other party and is still open
+    let counter_account = &ctx.accounts.counter_account;
+    require!(counter_account.by_seller != by_seller, MarketplaceError::NotYourTurn);
+    let now = Clock::get()?.unix_timestamp as u64;
+    require!(
+        counter_account.expiry == 0 ||
+        now <= counter_account.expiry,
+        MarketplaceError::Expired,
+    );
+    let price = counter_account.price;
+
+    // Check offer and listing sale windows are open and a private listing is settled with its reserved buyer
+    ctx.accounts.listing_account.assert_fillable_by(&ctx.accounts.buyer.key(), now)?;
+    ctx.accounts.offer_account.assert_fillable(now)?;
+
+    // A seller counter is not reserved yet, the buyer reserves it on acceptance
+    if !by_seller {
+        reserve_offer_price(
+            &mut ctx.accounts.offer_account,
+            &mut ctx.accounts.escrow_state,
+            &ctx.accounts.wallet.to_account_info(),
+            &ctx.accounts.payment_account,
+            &ctx.accounts.escrow_payment_account,
+            &ctx.accounts.treasury_mint,
+            &ctx.accounts.auction_house.to_account_info(),
+            &ctx.accounts.token_program,
+            &ctx.accounts.system_program,
+            &ctx.accounts.rent,
+            ctx.program_id,
+            price,
+            deposit_shortfall,
+        )?;
+    }
+    require!(ctx.accounts.offer_account.price == price, MarketplaceError::InvalidAmount);
+
+    let wallet = &ctx.accounts.wallet;
+    let buyer = &ctx.accounts.buyer;
+    let seller = &ctx.accounts.seller;
+    let auction_house = &ctx.accounts.auction_house;
+    let auction_house_treasury = &ctx.accounts.auction_house_treasury;
+    let metadata = &ctx.accounts.metadata;
+    let nft_mint = &ctx.accounts.nft_mint;
+    let nft_account = &ctx.accounts.nft_account;
+    let buyer_receipt_token_account = &ctx.accounts.buyer_receipt_token_account;
+    let system_program = &ctx.accounts.system_program;
+    let ata_program = &ctx.accounts.ata_program;
+    let token_program = &ctx.accounts.token_program;
+    let rent = &ctx.accounts.rent;
+
+    // Check NFT token ownership (needs to be in treasury account or delegated to it)
+    assert_listed_nft(
+        ctx.program_id,
+        &nft_account.to_account_info(),
+        &ctx.accounts.listing_account.key(),
+        &seller.key(),
+        &nft_mint.key(),
+        &auction_house_treasury.key(),
+        ctx.accounts.listing_account.custody,
+    )?;
+    require!(nft_account.amount > 0, MarketplaceError::InvalidAmount);
+
+    // Check metadata is correct
+    assert_metadata(&metadata.to_account_info(), &nft_mint.key())?;
+
+    let is_native = ctx.accounts.treasury_mint.key() == spl_token::native_mint::id();
+    let ah_key = auction_house.key();
+    let buyer_key = buyer.key();
+    let escrow_signer_seeds = [
+        PREFIX,
+        ah_key.as_ref(),
+        buyer_key.as_ref(),
+        &[bump(&[PREFIX, ah_key.as_ref(), buyer_key.as_ref()], ctx.program_id)],
+    ];
+    let ah_seeds = [
+        PREFIX,
+        auction_house.creator.as_ref(),
+        auction_house.treasury_mint.as_ref(),
+        &[bump(&[PREFIX, auction_house.creator.as_ref(), auction_house.treasury_mint.as_ref()], ctx.program_id)],
+    ];
+
+    // with the native account, the escrow is its own owner,
+    // whereas with token, it is the auction house that is owner.
+    let signer_seeds_for_royalties = if is_native {
+        escrow_signer_seeds
+    } else {
+        ah_seeds
+    };
+
+    let remaining_accounts = &mut ctx.remaining_accounts.iter();
+
+    let seller_proceeds = pay_sale_proceeds(
+        remaining_accounts,
+        auction_house,
+        &auction_house_treasury.to_account_info(),
+        &metadata.to_account_info(),
+        &ctx.accounts.escrow_payment_account.to_account_info(),
+        buyer,
+        seller,
+        &ctx.accounts.seller_payment_receipt_account.to_account_info(),
+        wallet,
+        &ctx.accounts.treasury_mint.to_account_info(),
+        &ata_program.to_account_info(),
+        &token_program.to_account_info(),
+        &system_program.to_account_info(),
+        &rent.to_account_info(),
+        &signer_seeds_for_royalties,
+        price,
+        is_native,
+    )?;
+
+    // Check fee or royalty changes since listing did not cut the seller payout below the guard
+    ctx.accounts.listing_account.assert_net_proceeds(seller_proceeds)?;
+
+    // Check buyer NFT ATA is valid
+    if buyer_receipt_token_account.data_is_empty() {
+        make_ata(
+            buyer_receipt_token_account.to_account_info(),
+            buyer.to_account_info(),
+            nft_mint.to_account_info(),
+            wallet.to_account_info(),
+            ata_program.to_account_info(),
+            token_program.to_account_info(),
+            system_program.to_account_info(),
+            rent.to_account_info(),
+            &[],
+        )?;
+    }
+
+    let buyer_rec_acct = assert_is_ata(buyer_receipt_token_account, &buyer_key, &nft_mint.key())?;
+
+    // make sure you cant get rugged
+    if buyer_rec_acct.delegate.is_some() {
+        return Err(MarketplaceError::BuyerATACannotHaveDelegate.into());
+    }
+
+    // Transfer NFT to buyer
+    let treasury_signer_seeds = &[
+        PREFIX,
+        ah_key.as_ref(),
+        TREASURY,
+        &[bump(&[PREFIX, ah_key.as_ref(), TREASURY], ctx.program_id)],
+    ];
+    let treasury_signer = &[&treasury_signer_seeds[..]];
+
+    let custody = ctx.accounts.listing_account.custody;
+    if custody == ListingCustody::Vault {
+        // Hand the vault content to the buyer and refund its rent to the seller
+        close_vault(
+            nft_account,
+            &buyer_receipt_token_account.to_account_info(),
+            &seller.to_account_info(),
+            &auction_house_treasury.to_account_info(),
+            token_program,
+            treasury_signer,
+        )?;
+    } else {
+        thaw_listed_nft(
+            custody,
+            &nft_account.to_account_info(),
+            &ctx.accounts.edition,
+            nft_mint,
+            &auction_house_treasury.to_account_info(),
+            &ctx.accounts.token_metadata_program,
+            &token_program.to_account_info(),
+            treasury_signer_seeds,
+        )?;
+
+        let cpi_account = Transfer {
+            from: nft_account.to_account_info(),
+            to: buyer_receipt_token_account.to_account_info(),
+            authority: auction_house_treasury.to_account_info(),
+        };
+        let cpi_ctx = CpiContext::new_with_signer(
+            token_program.to_account_info(),
+            cpi_account,
+            treasury_signer,
+        );
+        token::transfer(cpi_ctx, 1)?;
+    }
+
+    // Settle the offer reservation against the agreed price
+    let escrow_amount = ctx.accounts.offer_account.escrow_amount;
+    ctx.accounts.escrow_state.spend(escrow_amount, price)?;
+
+    msg!(
+        "{{\"accepted_by\": \"{}\", \"price\": \"{}\", \"round\": {}}}",
+        if by_seller { "seller" } else { "buyer" },
+        price,
+        ctx.accounts.counter_account.round
+    );
+
+    Ok(())
+}
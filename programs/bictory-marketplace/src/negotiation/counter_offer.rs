@@ -0,0 +1,202 @@
+use anchor_lang::prelude::*;
+use anchor_lang::solana_program::clock::Clock;
+use anchor_spl::token::{Mint, Token};
+
+use crate::{constant::*, states::*, error::*};
+use super::reserve_offer_price;
+
+/// Counter an offer with a new price, alternating between the listing owner and the offer buyer.
+/// A buyer counter moves the offer itself to the new price, reserving it from the buyer escrow.
+pub fn counter_offer(
+    ctx: Context<CounterOffer>,
+    price: u64,
+    expiry: Option<u64>,
+    deposit_shortfall: bool,
+) -> Result<()> {
+    require!(price > 0, MarketplaceError::InvalidAmount);
+
+    // Check expiry date
+    let mut _expiry = 0;
+    if let Some(expiry) = expiry {
+        let now = Clock::get()?.unix_timestamp as u64;
+        require!(expiry >= now, MarketplaceError::InvalidExpiry);
+
+        _expiry = expiry;
+    }
+
+    let wallet_key = ctx.accounts.wallet.key();
+    let by_seller = wallet_key == ctx.accounts.seller.key();
+    require!(
+        by_seller || wallet_key == ctx.accounts.buyer.key(),
+        MarketplaceError::InvalidOwner,
+    );
+
+    // If first counter on the current offer terms, or the counter was left by a previous owner of the NFT,
+    // the seller opens the negotiation
+    let counter_account = &ctx.accounts.counter_account;
+    let is_current = counter_account.seller == ctx.accounts.seller.key() &&
+        counter_account.buyer == ctx.accounts.buyer.key();
+    if counter_account.round == 0 || !is_current {
+        require!(by_seller, MarketplaceError::NotYourTurn);
+
+        let counter_account = &mut ctx.accounts.counter_account;
+        counter_account.round = 0;
+        counter_account.offer = ctx.accounts.offer_account.key();
+        counter_account.seller = ctx.accounts.seller.key();
+        counter_account.buyer = ctx.accounts.buyer.key();
+        counter_account.nft_mint = ctx.accounts.nft_mint.key();
+    } else {
+        require!(counter_account.by_seller != by_seller, MarketplaceError::NotYourTurn);
+        require!(counter_account.round < MAX_COUNTER_ROUNDS, MarketplaceError::MaxCounterRounds);
+    }
+
+    if !by_seller {
+        reserve_offer_price(
+            &mut ctx.accounts.offer_account,
+            &mut ctx.accounts.escrow_state,
+            &ctx.accounts.wallet.to_account_info(),
+            &ctx.accounts.payment_account,
+            &ctx.accounts.escrow_payment_account,
+            &ctx.accounts.treasury_mint,
+            &ctx.accounts.auction_house.to_account_info(),
+            &ctx.accounts.token_program,
+            &ctx.accounts.system_program,
+            &ctx.accounts.rent,
+            ctx.program_id,
+            price,
+            deposit_shortfall,
+        )?;
+        ctx.accounts.offer_account.expiry = _expiry;
+    }
+
+    let counter_account = &mut ctx.accounts.counter_account;
+    counter_account.price = price;
+    counter_account.expiry = _expiry;
+    counter_account.round += 1;
+    counter_account.by_seller = by_seller;
+
+    // Log counter detail
+    msg!(
+        "{{\"counter_by\": \"{}\", \"price\": \"{}\", \"expiry\": {}, \"round\": {}}}",
+        if by_seller { "seller" } else { "buyer" },
+        price,
+        _expiry,
+        counter_account.round
+    );
+
+    Ok(())
+}
+
+#[derive(Accounts)]
+pub struct CounterOffer<'info> {
+    /// Listing owner or offer buyer account.
+    #[account(mut)]
+    pub wallet: Signer<'info>,
+
+    /// CHECK: Validated as the listing owner.
+    /// Seller user wallet account.
+    #[account(constraint = seller.key() == listing_account.owner)]
+    pub seller: UncheckedAccount<'info>,
+
+    /// CHECK: Validated as the offer buyer.
+    /// Buyer user wallet account.
+    #[account(constraint = buyer.key() == offer_account.buyer)]
+    pub buyer: UncheckedAccount<'info>,
+
+    /// Treasury mint account, either native SOL mint or a SPL token mint.
+    pub treasury_mint: Account<'info, Mint>,
+
+    /// Authority key for the Auction House.
+    /// CHECK: Validated as a auction house signer.
+    pub authority: UncheckedAccount<'info>,
+
+    /// Auction House instance PDA account.
+    #[account( 
+        seeds=[PREFIX, auction_house.creator.as_ref(), treasury_mint.key().as_ref()], 
+        bump, 
+        has_one=authority, 
+        has_one=treasury_mint,
+    )]
+    pub auction_house: Box<Account<'info, AuctionHouse>>,
+
+    /// CHECK: Validated in transfer_to_escrow.
+    /// Buyer SOL or SPL account to top up the escrow from when `deposit_shortfall` is set on a buyer counter.
+    #[account(mut)]
+    pub payment_account: UncheckedAccount<'info>,
+
+    /// CHECK: Not dangerous. Account seeds checked in constraint.
+    /// Buyer escrow payment account PDA.
+    #[account(
+        mut,
+        seeds = [
+            PREFIX,
+            auction_house.key().as_ref(),
+            buyer.key().as_ref()
+        ],
+        bump
+    )]
+    pub escrow_payment_account: UncheckedAccount<'info>,
+
+    /// Buyer escrow ledger PDA account.
+    #[account(
+        mut,
+        seeds = [
+            PREFIX,
+            auction_house.key().as_ref(),
+            buyer.key().as_ref(),
+            ESCROW_STATE
+        ],
+        bump
+    )]
+    pub escrow_state: Box<Account<'info, BuyerEscrowState>>,
+
+    /// NFT mint account
+    /// CHECK: Validated as a nft account.
+    pub nft_mint: UncheckedAccount<'info>,
+
+    /// Listing PDA account
+    #[account(
+        seeds=[
+            PREFIX,
+            auction_house.key().as_ref(),
+            nft_mint.key().as_ref(),
+            LISTING
+        ],
+        bump,
+        constraint = listing_account.nft_mint == nft_mint.key(),
+    )]
+    pub listing_account: Box<Account<'info, ListingAccount>>,
+
+    /// Offer PDA account
+    #[account(
+        mut,
+        seeds=[
+            PREFIX,
+            auction_house.key().as_ref(),
+            nft_mint.key().as_ref(),
+            buyer.key().as_ref(),
+            OFFER
+        ],
+        bump,
+        constraint = offer_account.nft_mint == nft_mint.key(),
+    )]
+    pub offer_account: Box<Account<'info, OfferAccount>>,
+
+    /// Counter-offer PDA account
+    #[account(
+        init_if_needed,
+        seeds=[
+            PREFIX,
+            offer_account.key().as_ref(),
+            COUNTER_OFFER
+        ],
+        bump,
+        space=8 + std::mem::size_of::<CounterOfferAccount>(),
+        payer=wallet,
+    )]
+    pub counter_account: Box<Account<'info, CounterOfferAccount>>,
+
+    pub token_program: Program<'info, Token>,
+    pub system_program: Program<'info, System>,
+    pub rent: Sysvar<'info, Rent>,
+}
@@ -0,0 +1,68 @@
+use anchor_lang::prelude::*;
+use anchor_spl::token::{Mint, Token};
+
+use crate::{constant::*, error::*, states::*, utils::*};
+
+pub mod accept_counter;
+pub mod counter_offer;
+
+pub use {accept_counter::*, counter_offer::*};
+
+/// Move the buyer escrow reservation of `offer_account` to `price`, topping up the escrow first if requested.
+#[allow(clippy::too_many_arguments)]
+fn reserve_offer_price<'info>(
+    offer_account: &mut OfferAccount,
+    escrow_state: &mut BuyerEscrowState,
+    buyer: &AccountInfo<'info>,
+    payment_account: &UncheckedAccount<'info>,
+    escrow_payment_account: &UncheckedAccount<'info>,
+    treasury_mint: &Account<'info, Mint>,
+    auction_house: &AccountInfo<'info>,
+    token_program: &Program<'info, Token>,
+    system_program: &Program<'info, System>,
+    rent: &Sysvar<'info, Rent>,
+    program_id: &Pubkey,
+    price: u64,
+    deposit_shortfall: bool,
+) -> Result<()> {
+    let is_native = treasury_mint.key() == spl_token::native_mint::id();
+    let ah_key = auction_house.key();
+    let wallet_key = buyer.key();
+
+    escrow_state.sync(escrow_balance(&escrow_payment_account.to_account_info(), is_native)?)?;
+    escrow_state.release(offer_account.escrow_amount)?;
+
+    // Check free escrow covers the price, topping it up first if requested
+    let free = escrow_state.free;
+    if free < price {
+        require!(deposit_shortfall, MarketplaceError::InsufficientEscrow);
+
+        let escrow_signer_seeds = [
+            PREFIX,
+            ah_key.as_ref(),
+            wallet_key.as_ref(),
+            &[bump(&[PREFIX, ah_key.as_ref(), wallet_key.as_ref()], program_id)],
+        ];
+
+        transfer_to_escrow(
+            buyer,
+            payment_account,
+            escrow_payment_account,
+            treasury_mint,
+            auction_house,
+            token_program,
+            system_program,
+            rent,
+            &escrow_signer_seeds,
+            price - free,
+            is_native,
+        )?;
+        escrow_state.deposit(price - free)?;
+    }
+    escrow_state.commit(price)?;
+
+    offer_account.price = price;
+    offer_account.escrow_amount = price;
+
+    Ok(())
+}
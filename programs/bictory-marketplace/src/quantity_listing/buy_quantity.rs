@@ -0,0 +1,313 @@
+use anchor_lang::{prelude::*, AccountsClose};
+use anchor_lang::solana_program::clock::Clock;
+use anchor_spl::associated_token::AssociatedToken;
+use anchor_spl::token::{self, Mint, Token, TokenAccount, Transfer};
+
+use crate::{constant::*, error::*, states::*, utils::*};
+
+#[derive(Accounts)]
+pub struct BuyQuantity<'info> {
+    /// Buyer user wallet account.
+    #[account(mut)]
+    pub buyer: Signer<'info>,
+
+    /// CHECK: Validated as the listing owner.
+    /// Seller user wallet account.
+    #[account(mut, constraint = seller.key() == listing_account.owner)]
+    pub seller: UncheckedAccount<'info>,
+
+    /// Treasury mint account, either native SOL mint or a SPL token mint.
+    pub treasury_mint: Box<Account<'info, Mint>>,
+
+    /// CHECK: Validated in transfer_to_escrow.
+    /// Buyer SOL or SPL account to top up the escrow from when `deposit_shortfall` is set.
+    #[account(mut)]
+    pub payment_account: UncheckedAccount<'info>,
+
+    /// CHECK: Not dangerous. Account seeds checked in constraint.
+    /// Buyer escrow payment account PDA.
+    #[account(
+        mut,
+        seeds = [
+            PREFIX,
+            auction_house.key().as_ref(),
+            buyer.key().as_ref()
+        ],
+        bump
+    )]
+    pub escrow_payment_account: UncheckedAccount<'info>,
+
+    /// Buyer escrow ledger PDA account.
+    #[account(
+        init_if_needed,
+        seeds = [
+            PREFIX,
+            auction_house.key().as_ref(),
+            buyer.key().as_ref(),
+            ESCROW_STATE
+        ],
+        bump,
+        space = 8 + std::mem::size_of::<BuyerEscrowState>(),
+        payer = buyer
+    )]
+    pub escrow_state: Box<Account<'info, BuyerEscrowState>>,
+
+    /// CHECK: Validated in buy_quantity.
+    /// Seller SOL or SPL account to receive payment at.
+    #[account(mut)]
+    pub seller_payment_receipt_account: UncheckedAccount<'info>,
+
+    /// CHECK: Validated in buy_quantity.
+    /// Buyer SPL token account to receive purchased units at.
+    #[account(mut)]
+    pub buyer_receipt_token_account: UncheckedAccount<'info>,
+
+    /// Auction House instance authority account.
+    /// CHECK: Validated as a auction house signer.
+    pub authority: UncheckedAccount<'info>,
+
+    /// Auction House treasury PDA account.
+    /// CHECK: Not dangerous. Account seeds checked in constraint.
+    #[account(mut, seeds=[PREFIX, auction_house.key().as_ref(), TREASURY], bump)]
+    pub auction_house_treasury: UncheckedAccount<'info>,
+
+    /// Auction House instance PDA account.
+    #[account(
+        seeds = [
+            PREFIX,
+            auction_house.creator.as_ref(),
+            auction_house.treasury_mint.as_ref()
+        ],
+        bump,
+        has_one=authority,
+        has_one=treasury_mint,
+        has_one=auction_house_treasury
+    )]
+    pub auction_house: Box<Account<'info, AuctionHouse>>,
+
+    /// CHECK: Validated in buy_quantity.
+    /// Semi-fungible mint account
+    pub nft_mint: UncheckedAccount<'info>,
+
+    /// CHECK: Validated in buy_quantity.
+    /// Metaplex metadata account decorating SPL mint account.
+    pub metadata: UncheckedAccount<'info>,
+
+    /// Quantity listing PDA account
+    #[account(
+        mut,
+        seeds=[
+            PREFIX,
+            auction_house.key().as_ref(),
+            nft_mint.key().as_ref(),
+            seller.key().as_ref(),
+            QUANTITY_LISTING
+        ],
+        bump,
+    )]
+    pub listing_account: Box<Account<'info, QuantityListingAccount>>,
+
+    /// Listing vault PDA token account.
+    #[account(
+        mut,
+        seeds=[
+            PREFIX,
+            listing_account.key().as_ref(),
+            VAULT
+        ],
+        bump,
+    )]
+    pub vault: Box<Account<'info, TokenAccount>>,
+
+    pub token_program: Program<'info, Token>,
+    pub system_program: Program<'info, System>,
+    pub ata_program: Program<'info, AssociatedToken>,
+    pub rent: Sysvar<'info, Rent>,
+}
+
+/// Buy `quantity` units out of a quantity listing, paying the per-unit price from the buyer escrow.
+pub fn buy_quantity<'info>(
+    ctx: Context<'_, '_, '_, 'info, BuyQuantity<'info>>,
+    quantity: u64,
+    deposit_shortfall: bool,
+) -> Result<()> {
+    let listing_account = &ctx.accounts.listing_account;
+
+    // Check listing is not expired and holds enough units
+    let now = Clock::get()?.unix_timestamp as u64;
+    require!(
+        listing_account.expiry == 0 || now <= listing_account.expiry,
+        MarketplaceError::Expired,
+    );
+    require!(
+        quantity > 0 && quantity <= listing_account.quantity,
+        MarketplaceError::InvalidAmount,
+    );
+
+    let total = listing_account
+        .price
+        .checked_mul(quantity)
+        .ok_or(MarketplaceError::NumericalOverflow)?;
+
+    let is_native = ctx.accounts.treasury_mint.key() == spl_token::native_mint::id();
+    let ah_key = ctx.accounts.auction_house.key();
+    let wallet_key = ctx.accounts.buyer.key();
+    let escrow_signer_seeds = [
+        PREFIX,
+        ah_key.as_ref(),
+        wallet_key.as_ref(),
+        &[bump(&[PREFIX, ah_key.as_ref(), wallet_key.as_ref()], ctx.program_id)],
+    ];
+
+    // If first time to use the escrow ledger
+    if is_zero_account(&ctx.accounts.escrow_state.to_account_info()) {
+        ctx.accounts.escrow_state.wallet = wallet_key;
+        ctx.accounts.escrow_state.auction_house = ah_key;
+    }
+    let balance = escrow_balance(&ctx.accounts.escrow_payment_account.to_account_info(), is_native)?;
+    ctx.accounts.escrow_state.sync(balance)?;
+
+    // Check free escrow covers the purchase, topping it up first if requested
+    let free = ctx.accounts.escrow_state.free;
+    if free < total {
+        require!(deposit_shortfall, MarketplaceError::InsufficientEscrow);
+
+        transfer_to_escrow(
+            &ctx.accounts.buyer.to_account_info(),
+            &ctx.accounts.payment_account,
+            &ctx.accounts.escrow_payment_account,
+            &ctx.accounts.treasury_mint,
+            &ctx.accounts.auction_house.to_account_info(),
+            &ctx.accounts.token_program,
+            &ctx.accounts.system_program,
+            &ctx.accounts.rent,
+            &escrow_signer_seeds,
+            total - free,
+            is_native,
+        )?;
+        ctx.accounts.escrow_state.deposit(total - free)?;
+    }
+
+    let buyer = &ctx.accounts.buyer;
+    let seller = &ctx.accounts.seller;
+    let auction_house = &ctx.accounts.auction_house;
+    let auction_house_treasury = &ctx.accounts.auction_house_treasury;
+    let metadata = &ctx.accounts.metadata;
+    let nft_mint = &ctx.accounts.nft_mint;
+    let buyer_receipt_token_account = &ctx.accounts.buyer_receipt_token_account;
+    let system_program = &ctx.accounts.system_program;
+    let ata_program = &ctx.accounts.ata_program;
+    let token_program = &ctx.accounts.token_program;
+    let rent = &ctx.accounts.rent;
+
+    // Check metadata is correct
+    assert_metadata(&metadata.to_account_info(), &nft_mint.key())?;
+
+    let ah_seeds = [
+        PREFIX,
+        auction_house.creator.as_ref(),
+        auction_house.treasury_mint.as_ref(),
+        &[bump(&[PREFIX, auction_house.creator.as_ref(), auction_house.treasury_mint.as_ref()], ctx.program_id)],
+    ];
+
+    // with the native account, the escrow is its own owner,
+    // whereas with token, it is the auction house that is owner.
+    let signer_seeds_for_royalties = if is_native {
+        escrow_signer_seeds
+    } else {
+        ah_seeds
+    };
+
+    let remaining_accounts = &mut ctx.remaining_accounts.iter();
+
+    pay_sale_proceeds(
+        remaining_accounts,
+        auction_house,
+        &auction_house_treasury.to_account_info(),
+        &metadata.to_account_info(),
+        &ctx.accounts.escrow_payment_account.to_account_info(),
+        buyer,
+        seller,
+        &ctx.accounts.seller_payment_receipt_account.to_account_info(),
+        buyer,
+        &ctx.accounts.treasury_mint.to_account_info(),
+        &ata_program.to_account_info(),
+        &token_program.to_account_info(),
+        &system_program.to_account_info(),
+        &rent.to_account_info(),
+        &signer_seeds_for_royalties,
+        total,
+        is_native,
+    )?;
+
+    // Check buyer ATA is valid
+    if buyer_receipt_token_account.data_is_empty() {
+        make_ata(
+            buyer_receipt_token_account.to_account_info(),
+            buyer.to_account_info(),
+            nft_mint.to_account_info(),
+            buyer.to_account_info(),
+            ata_program.to_account_info(),
+            token_program.to_account_info(),
+            system_program.to_account_info(),
+            rent.to_account_info(),
+            &[],
+        )?;
+    }
+
+    let buyer_rec_acct = assert_is_ata(buyer_receipt_token_account, &wallet_key, &nft_mint.key())?;
+
+    // make sure you cant get rugged
+    if buyer_rec_acct.delegate.is_some() {
+        return Err(MarketplaceError::BuyerATACannotHaveDelegate.into());
+    }
+
+    // Transfer units to buyer out of the vault
+    let treasury_signer_seeds = &[
+        PREFIX,
+        ah_key.as_ref(),
+        TREASURY,
+        &[bump(&[PREFIX, ah_key.as_ref(), TREASURY], ctx.program_id)],
+    ];
+    let treasury_signer = &[&treasury_signer_seeds[..]];
+
+    let cpi_account = Transfer {
+        from: ctx.accounts.vault.to_account_info(),
+        to: buyer_receipt_token_account.to_account_info(),
+        authority: auction_house_treasury.to_account_info(),
+    };
+    token::transfer(
+        CpiContext::new_with_signer(token_program.to_account_info(), cpi_account, treasury_signer),
+        quantity,
+    )?;
+
+    // Purchase is paid straight out of free escrow
+    ctx.accounts.escrow_state.withdraw(total)?;
+
+    let listing_account = &mut ctx.accounts.listing_account;
+    listing_account.quantity -= quantity;
+
+    msg!(
+        "{{\"buyer\": \"{}\", \"quantity\": {}, \"price\": \"{}\", \"remaining_quantity\": {}}}",
+        wallet_key,
+        quantity,
+        total,
+        listing_account.quantity
+    );
+
+    // Close the listing once sold out
+    if listing_account.quantity == 0 {
+        ctx.accounts.vault.reload()?;
+        close_vault(
+            &ctx.accounts.vault,
+            &buyer_receipt_token_account.to_account_info(),
+            &seller.to_account_info(),
+            &auction_house_treasury.to_account_info(),
+            token_program,
+            treasury_signer,
+        )?;
+        ctx.accounts.listing_account.close(seller.to_account_info())?;
+    }
+
+    Ok(())
+}
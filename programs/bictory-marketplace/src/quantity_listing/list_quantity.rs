@@ -0,0 +1,133 @@
+use anchor_lang::prelude::*;
+use anchor_lang::solana_program::clock::Clock;
+use anchor_spl::token::{self, Mint, Token, TokenAccount, Transfer};
+
+use crate::{constant::*, states::*, error::*};
+
+/// List `quantity` units of a semi-fungible mint at a per-unit price, moving them into the listing vault.
+pub fn list_quantity(
+    ctx: Context<ListQuantity>,
+    price: u64,
+    quantity: u64,
+    seller_expiry: Option<u64>,
+) -> Result<()> {
+    require!(quantity > 0, MarketplaceError::InvalidAmount);
+
+    // Check expiry date
+    let mut _expiry = 0;
+    if let Some(expiry) = seller_expiry {
+        let now = Clock::get()?.unix_timestamp as u64;
+        require!(expiry >= now, MarketplaceError::InvalidExpiry);
+
+        _expiry = expiry;
+    }
+
+    // Check token balance
+    require!(
+        ctx.accounts.nft_account.amount >= quantity,
+        MarketplaceError::InvalidAmount,
+    );
+
+    // Move listed units into the vault
+    let cpi_account = Transfer {
+        from: ctx.accounts.nft_account.to_account_info(),
+        to: ctx.accounts.vault.to_account_info(),
+        authority: ctx.accounts.seller.to_account_info(),
+    };
+    token::transfer(
+        CpiContext::new(ctx.accounts.token_program.to_account_info(), cpi_account),
+        quantity,
+    )?;
+
+    // Fill listing account
+    let listing_account = &mut ctx.accounts.listing_account;
+    listing_account.owner = ctx.accounts.seller.key();
+    listing_account.nft_mint = ctx.accounts.nft_mint.key();
+    listing_account.auction_house = ctx.accounts.auction_house.key();
+    listing_account.price = price;
+    listing_account.quantity = quantity;
+    listing_account.expiry = _expiry;
+
+    // Log listing detail
+    msg!(
+        "{{\"price\": \"{}\", \"quantity\": {}, \"seller_expiry\": {}}}",
+        price,
+        quantity,
+        _expiry
+    );
+
+    Ok(())
+}
+
+#[derive(Accounts)]
+pub struct ListQuantity<'info> {
+    /// Seller account.
+    #[account(mut)]
+    pub seller: Signer<'info>,
+
+    /// Treasury mint account, either native SOL mint or a SPL token mint.
+    pub treasury_mint: Account<'info, Mint>,
+
+    /// Authority key for the Auction House.
+    /// CHECK: Validated as a auction house signer.
+    pub authority: UncheckedAccount<'info>,
+
+    /// Auction House treasury PDA account.
+    /// CHECK: Not dangerous. Account seeds checked in constraint.
+    #[account(seeds=[PREFIX, auction_house.key().as_ref(), TREASURY], bump)]
+    pub auction_house_treasury: UncheckedAccount<'info>,
+
+    /// Auction House instance PDA account.
+    #[account( 
+        seeds=[PREFIX, auction_house.creator.as_ref(), treasury_mint.key().as_ref()], 
+        bump, 
+        has_one=authority, 
+        has_one=treasury_mint, 
+        has_one=auction_house_treasury
+    )]
+    pub auction_house: Box<Account<'info, AuctionHouse>>,
+
+    /// Semi-fungible mint account
+    pub nft_mint: Box<Account<'info, Mint>>,
+
+    /// Seller token account
+    #[account(mut,
+        constraint = nft_account.mint == nft_mint.key()
+    )]
+    pub nft_account: Box<Account<'info, TokenAccount>>,
+
+    /// Quantity listing PDA account
+    #[account(
+        init,
+        seeds=[
+            PREFIX,
+            auction_house.key().as_ref(),
+            nft_mint.key().as_ref(),
+            seller.key().as_ref(),
+            QUANTITY_LISTING
+        ],
+        bump, 
+        space=8 + std::mem::size_of::<QuantityListingAccount>(),
+        payer=seller
+    )]
+    pub listing_account: Box<Account<'info, QuantityListingAccount>>,
+
+    /// Listing vault PDA token account, owned by the treasury.
+    #[account(
+        init,
+        seeds=[
+            PREFIX,
+            listing_account.key().as_ref(),
+            VAULT
+        ],
+        bump,
+        token::mint = nft_mint,
+        token::authority = auction_house_treasury,
+        payer=seller
+    )]
+    pub vault: Box<Account<'info, TokenAccount>>,
+
+    pub token_program: Program<'info, Token>,
+    pub system_program: Program<'info, System>,
+    pub rent: Sysvar<'info, Rent>,
+}
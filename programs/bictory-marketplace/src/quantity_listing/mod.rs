@@ -0,0 +1,5 @@
+pub mod buy_quantity;
+pub mod list_quantity;
+pub mod unlist_quantity;
+
+pub use {buy_quantity::*, list_quantity::*, unlist_quantity::*};
@@ -0,0 +1,100 @@
+use anchor_lang::prelude::*;
+use anchor_spl::token::{Mint, Token, TokenAccount};
+
+use crate::{constant::*, states::*, utils::*};
+
+/// Return the unsold units of a quantity listing to the seller and close it.
+pub fn unlist_quantity(ctx: Context<UnlistQuantity>) -> Result<()> {
+    let ah_key = ctx.accounts.auction_house.key();
+    let treasury_signer_seeds = &[
+        PREFIX,
+        ah_key.as_ref(),
+        TREASURY,
+        &[bump(&[PREFIX, ah_key.as_ref(), TREASURY], ctx.program_id)],
+    ];
+    let treasury_signer = &[&treasury_signer_seeds[..]];
+
+    let remaining = ctx.accounts.vault.amount;
+    close_vault(
+        &ctx.accounts.vault,
+        &ctx.accounts.nft_account.to_account_info(),
+        &ctx.accounts.seller.to_account_info(),
+        &ctx.accounts.auction_house_treasury.to_account_info(),
+        &ctx.accounts.token_program,
+        treasury_signer,
+    )?;
+
+    msg!("{{\"returned_quantity\": {}}}", remaining);
+
+    Ok(())
+}
+
+#[derive(Accounts)]
+pub struct UnlistQuantity<'info> {
+    /// Seller account.
+    #[account(mut)]
+    pub seller: Signer<'info>,
+
+    /// Treasury mint account, either native SOL mint or a SPL token mint.
+    pub treasury_mint: Account<'info, Mint>,
+
+    /// Authority key for the Auction House.
+    /// CHECK: Validated as a auction house signer.
+    pub authority: UncheckedAccount<'info>,
+
+    /// Auction House treasury PDA account.
+    /// CHECK: Not dangerous. Account seeds checked in constraint.
+    #[account(seeds=[PREFIX, auction_house.key().as_ref(), TREASURY], bump)]
+    pub auction_house_treasury: UncheckedAccount<'info>,
+
+    /// Auction House instance PDA account.
+    #[account( 
+        seeds=[PREFIX, auction_house.creator.as_ref(), treasury_mint.key().as_ref()], 
+        bump, 
+        has_one=authority, 
+        has_one=treasury_mint, 
+        has_one=auction_house_treasury
+    )]
+    pub auction_house: Box<Account<'info, AuctionHouse>>,
+
+    /// CHECK: Not dangerous. Used in listing account seeds.
+    /// Semi-fungible mint account
+    pub nft_mint: UncheckedAccount<'info>,
+
+    /// Seller token account to return the unsold units to.
+    #[account(mut,
+        constraint = nft_account.mint == nft_mint.key(),
+        constraint = nft_account.owner == seller.key(),
+    )]
+    pub nft_account: Box<Account<'info, TokenAccount>>,
+
+    /// Quantity listing PDA account
+    #[account(
+        mut,
+        seeds=[
+            PREFIX,
+            auction_house.key().as_ref(),
+            nft_mint.key().as_ref(),
+            seller.key().as_ref(),
+            QUANTITY_LISTING
+        ],
+        bump, 
+        close = seller,
+        constraint = listing_account.owner == seller.key(),
+    )]
+    pub listing_account: Box<Account<'info, QuantityListingAccount>>,
+
+    /// Listing vault PDA token account.
+    #[account(
+        mut,
+        seeds=[
+            PREFIX,
+            listing_account.key().as_ref(),
+            VAULT
+        ],
+        bump,
+    )]
+    pub vault: Box<Account<'info, TokenAccount>>,
+
+    pub token_program: Program<'info, Token>,
+}
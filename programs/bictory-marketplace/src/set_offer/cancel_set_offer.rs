@@ -0,0 +1,65 @@
+use anchor_lang::prelude::*;
+use anchor_spl::token::Mint;
+
+use crate::{constant::*, states::*};
+
+/// Cancel set offer, releasing the reservation of its remaining quantity.
+pub fn cancel_set_offer(ctx: Context<CancelSetOffer>, _merkle_root: [u8; 32]) -> Result<()> {
+    let escrow_amount = ctx.accounts.offer_account.escrow_amount()?;
+    ctx.accounts.escrow_state.release(escrow_amount)?;
+    msg!("{{\"released_escrow_amount\": \"{}\"}}", escrow_amount);
+
+    Ok(())
+}
+
+#[derive(Accounts)]
+#[instruction(merkle_root: [u8; 32])]
+pub struct CancelSetOffer<'info> {
+    /// Buyer account.
+    #[account(mut)]
+    pub buyer: Signer<'info>,
+
+    /// Treasury mint account, either native SOL mint or a SPL token mint.
+    pub treasury_mint: Account<'info, Mint>,
+
+    /// Authority key for the Auction House.
+    /// CHECK: Validated as a auction house signer.
+    pub authority: UncheckedAccount<'info>,
+
+    /// Auction House instance PDA account.
+    #[account( 
+        seeds=[PREFIX, auction_house.creator.as_ref(), treasury_mint.key().as_ref()], 
+        bump, 
+        has_one=authority, 
+        has_one=treasury_mint
+    )]
+    pub auction_house: Account<'info, AuctionHouse>,
+
+    /// Set offer PDA account
+    #[account(
+        mut,
+        seeds=[
+            PREFIX,
+            auction_house.key().as_ref(),
+            merkle_root.as_ref(),
+            buyer.key().as_ref(),
+            SET_OFFER
+        ],
+        bump, 
+        close = buyer
+    )]
+    pub offer_account: Account<'info, SetOfferAccount>,
+
+    /// Buyer escrow ledger PDA account.
+    #[account(
+        mut,
+        seeds = [
+            PREFIX,
+            auction_house.key().as_ref(),
+            buyer.key().as_ref(),
+            ESCROW_STATE
+        ],
+        bump
+    )]
+    pub escrow_state: Box<Account<'info, BuyerEscrowState>>,
+}
@@ -0,0 +1,5 @@
+pub mod cancel_set_offer;
+pub mod make_set_offer;
+pub mod sell_into_set_offer;
+
+pub use {cancel_set_offer::*, make_set_offer::*, sell_into_set_offer::*};
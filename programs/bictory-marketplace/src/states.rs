@@ -1,5 +1,7 @@
 use anchor_lang::prelude::*;
 
+use crate::{constant::*, error::*};
+
 #[account]
 #[derive(Default)]
 pub struct AuctionHouse {
@@ -11,6 +13,37 @@ pub struct AuctionHouse {
     pub seller_fee_basis_points: u16,
     pub discount_collection: Pubkey,
     pub discount_basis_points: u16,
+    /// Bids within this many seconds of an auction end extend the auction.
+    pub extension_window: u64,
+    /// Seconds added to the auction end time by a bid inside the extension window.
+    pub extension_period: u64,
+    /// Maximum total seconds an auction end time can be extended by.
+    pub max_extension: u64,
+    /// Fee charged on the price when a live listing or offer is cancelled.
+    pub cancel_fee_basis_points: u16,
+    /// Seconds after listing or offering before a live listing or offer can be cancelled.
+    pub cancel_cooldown: u64,
+}
+
+impl AuctionHouse {
+    /// Fee owed to cancel a listing or offer at `price` made at `created_at` and expiring at `expiry`.
+    /// Expired listings and offers are cancelled for free, live ones only once the cooldown is over.
+    pub fn cancel_fee(&self, price: u64, created_at: u64, expiry: u64, now: u64) -> Result<u64> {
+        if expiry != 0 && expiry < now {
+            return Ok(0);
+        }
+
+        require!(
+            created_at.saturating_add(self.cancel_cooldown) <= now,
+            MarketplaceError::CancelCooldown,
+        );
+
+        Ok((self.cancel_fee_basis_points as u128)
+            .checked_mul(price as u128)
+            .ok_or(MarketplaceError::NumericalOverflow)?
+            .checked_div(10000)
+            .ok_or(MarketplaceError::NumericalOverflow)? as u64)
+    }
 }
 
 #[account]
@@ -18,15 +51,459 @@ pub struct AuctionHouse {
 pub struct ListingAccount {
     pub owner: Pubkey,
     pub nft_mint: Pubkey,
+    pub auction_house: Pubkey,
+    pub price: u64,
+    pub expiry: u64,
+    /// Declining price schedule, `price` is the floor when set.
+    pub dutch: Option<DutchPricing>,
+    /// How the NFT is held for the treasury while listed.
+    pub custody: ListingCustody,
+    /// Only buyer allowed to fill the listing, private listings are hidden from the public book.
+    pub reserved_buyer: Option<Pubkey>,
+    /// Time the listing becomes purchasable, 0 when immediately.
+    pub start_time: u64,
+    /// Time the listing terms were last set.
+    pub created_at: u64,
+    /// Least the seller accepts after royalties and fees, 0 when unguarded.
+    pub min_net_proceeds: u64,
+}
+
+impl ListingAccount {
+    /// Price of the listing at `now`.
+    pub fn current_price(&self, now: u64) -> Result<u64> {
+        match self.dutch {
+            Some(dutch) => dutch.price_at(now),
+            None => Ok(self.price),
+        }
+    }
+
+    /// Check `buyer` may fill the listing at `now`: its sale window is open and it is not reserved to someone else.
+    pub fn assert_fillable_by(&self, buyer: &Pubkey, now: u64) -> Result<()> {
+        require!(self.start_time <= now, MarketplaceError::NotYetActive);
+        require!(
+            self.expiry == 0 ||
+            now <= self.expiry,
+            MarketplaceError::Expired,
+        );
+        if let Some(reserved_buyer) = self.reserved_buyer {
+            require!(reserved_buyer == *buyer, MarketplaceError::ReservedListing);
+        }
+        Ok(())
+    }
+
+    /// Check the seller share of a sale meets the listing minimum.
+    pub fn assert_net_proceeds(&self, seller_proceeds: u64) -> Result<()> {
+        require!(
+            seller_proceeds >= self.min_net_proceeds,
+            MarketplaceError::SellerProceedsBelowMinimum,
+        );
+        Ok(())
+    }
+
+    /// Set the fixed price and sale terms, replacing any previous terms and declining price.
+    pub fn set_terms(&mut self, price: u64, terms: &ListingTerms, now: u64) -> Result<()> {
+        // Check expiry date and start time
+        let expiry = terms.expiry.unwrap_or(0);
+        require!(expiry == 0 || expiry >= now, MarketplaceError::InvalidExpiry);
+        let start_time = terms.start_time.unwrap_or(0);
+        require!(expiry == 0 || start_time < expiry, MarketplaceError::InvalidExpiry);
+
+        // Check the payout guard can be met at the listing price
+        let min_net_proceeds = terms.min_net_proceeds.unwrap_or(0);
+        require!(min_net_proceeds <= price, MarketplaceError::InvalidAmount);
+
+        self.price = price;
+        self.expiry = expiry;
+        self.dutch = None;
+        self.reserved_buyer = terms.reserved_buyer;
+        self.start_time = start_time;
+        self.min_net_proceeds = min_net_proceeds;
+        self.created_at = now;
+        Ok(())
+    }
+}
+
+/// Sale terms taken by every single NFT listing instruction.
+#[derive(AnchorSerialize, AnchorDeserialize, Clone, Copy, Default)]
+pub struct ListingTerms {
+    /// Time the listing stops being purchasable, never when unset.
+    pub expiry: Option<u64>,
+    /// Only buyer allowed to fill the listing, public when unset.
+    pub reserved_buyer: Option<Pubkey>,
+    /// Time the listing becomes purchasable, immediately when unset.
+    pub start_time: Option<u64>,
+    /// Least the seller accepts after royalties and fees, unguarded when unset.
+    pub min_net_proceeds: Option<u64>,
+}
+
+#[derive(AnchorSerialize, AnchorDeserialize, Clone, Copy, Default, PartialEq, Eq)]
+pub enum ListingCustody {
+    /// Token account ownership handed over to the treasury.
+    #[default]
+    Owner,
+    /// Treasury approved as delegate, the NFT stays in the seller wallet.
+    Delegate,
+    /// Treasury approved as delegate and the token account frozen through Token Metadata.
+    Frozen,
+    /// NFT moved into a treasury-owned vault token account derived from the listing.
+    Vault,
+}
+
+#[derive(AnchorSerialize, AnchorDeserialize, Clone, Copy, Default, PartialEq, Eq)]
+pub enum PriceCurve {
+    /// Price declines continuously.
+    #[default]
+    Linear,
+    /// Price declines once every `step_duration` seconds.
+    Step { step_duration: u64 },
+}
+
+#[derive(AnchorSerialize, AnchorDeserialize, Clone, Copy, Default)]
+pub struct DutchPricing {
+    pub start_price: u64,
+    pub end_price: u64,
+    pub start_time: u64,
+    pub decay_duration: u64,
+    pub curve: PriceCurve,
+}
+
+impl DutchPricing {
+    pub fn price_at(&self, now: u64) -> Result<u64> {
+        // The floor is reached once the decay is over, whether or not steps divide the duration
+        let elapsed = now.saturating_sub(self.start_time);
+        if elapsed >= self.decay_duration {
+            return Ok(self.end_price);
+        }
+
+        let elapsed = match self.curve {
+            PriceCurve::Linear => elapsed,
+            PriceCurve::Step { step_duration } => elapsed - elapsed % step_duration,
+        };
+
+        let decay = ((self.start_price - self.end_price) as u128)
+            .checked_mul(elapsed as u128)
+            .ok_or(MarketplaceError::NumericalOverflow)?
+            .checked_div(self.decay_duration as u128)
+            .ok_or(MarketplaceError::NumericalOverflow)? as u64;
+
+        Ok(self.start_price - decay)
+    }
+}
+
+/// Listing of several units of a semi-fungible mint, held in a treasury-owned vault until sold or unlisted.
+#[account]
+#[derive(Default)]
+pub struct QuantityListingAccount {
+    pub owner: Pubkey,
+    pub nft_mint: Pubkey,
+    pub auction_house: Pubkey,
+    /// Price per unit.
     pub price: u64,
+    /// Units left for sale.
+    pub quantity: u64,
     pub expiry: u64,
 }
 
+/// Listing of several NFTs sold together for one total price, each held under the treasury authority.
+#[account]
+#[derive(Default)]
+pub struct BundleListingAccount {
+    pub owner: Pubkey,
+    pub auction_house: Pubkey,
+    pub bundle_id: u64,
+    /// Total price of the bundle.
+    pub price: u64,
+    pub expiry: u64,
+    /// Number of items used in `mints` and `allocations`.
+    pub count: u8,
+    pub mints: [Pubkey; MAX_BUNDLE_ITEMS],
+    /// Treasury-owned token account holding each item.
+    pub token_accounts: [Pubkey; MAX_BUNDLE_ITEMS],
+    /// Share of the price attributed to each item in basis points, used to pro-rate royalties.
+    pub allocations: [u16; MAX_BUNDLE_ITEMS],
+}
+
+impl BundleListingAccount {
+    /// Price attributed to item `index`, the last item takes the rounding dust.
+    pub fn item_price(&self, index: usize) -> Result<u64> {
+        if index + 1 == self.count as usize {
+            let mut allocated = 0u64;
+            for i in 0..index {
+                allocated = allocated
+                    .checked_add(self.item_price(i)?)
+                    .ok_or(MarketplaceError::NumericalOverflow)?;
+            }
+            return Ok(self.price - allocated);
+        }
+
+        Ok((self.price as u128)
+            .checked_mul(self.allocations[index] as u128)
+            .ok_or(MarketplaceError::NumericalOverflow)?
+            .checked_div(10000)
+            .ok_or(MarketplaceError::NumericalOverflow)? as u64)
+    }
+}
+
+/// What the maker of a swap offer asks for in return.
+#[derive(AnchorSerialize, AnchorDeserialize, Clone, PartialEq, Eq)]
+pub enum SwapRequest {
+    /// Exactly these mints.
+    Mints { mints: Vec<Pubkey> },
+    /// Any `quantity` verified members of the collection.
+    Collection { collection: Pubkey, quantity: u8 },
+}
+
+/// NFT-for-NFT swap offer, the offered NFTs are held under the treasury authority until filled or cancelled.
+#[account]
+#[derive(Default)]
+pub struct SwapOfferAccount {
+    pub maker: Pubkey,
+    pub auction_house: Pubkey,
+    pub swap_id: u64,
+    /// Treasury mint amount paid by the maker on top of the NFTs, reserved in the maker escrow.
+    pub sweetener: u64,
+    pub expiry: u64,
+    pub offered_count: u8,
+    pub offered: [Pubkey; MAX_SWAP_ITEMS],
+    /// Treasury-owned token account holding each offered NFT.
+    pub offered_accounts: [Pubkey; MAX_SWAP_ITEMS],
+    pub requested_count: u8,
+    /// Requested mints, unused when `requested_collection` is set.
+    pub requested: [Pubkey; MAX_SWAP_ITEMS],
+    pub requested_collection: Option<Pubkey>,
+}
+
 #[account]
 #[derive(Default)]
 pub struct OfferAccount {
     pub buyer: Pubkey,
     pub nft_mint: Pubkey,
+    pub auction_house: Pubkey,
+    pub price: u64,
+    pub expiry: u64,
+    /// Amount of the buyer escrow reserved to back this offer.
+    pub escrow_amount: u64,
+    /// Time the offer becomes fillable, 0 when immediately.
+    pub start_time: u64,
+    /// Time the offer terms were last set.
+    pub created_at: u64,
+}
+
+impl OfferAccount {
+    /// Check the offer fill window is open at `now`.
+    pub fn assert_fillable(&self, now: u64) -> Result<()> {
+        require!(self.start_time <= now, MarketplaceError::NotYetActive);
+        require!(
+            self.expiry == 0 ||
+            now <= self.expiry,
+            MarketplaceError::Expired,
+        );
+        Ok(())
+    }
+}
+
+/// Listing layout before listings were scoped to an auction house, stored at `[PREFIX, nft_mint, LISTING]`.
+#[derive(AnchorSerialize, AnchorDeserialize, Default)]
+pub struct LegacyListingAccount {
+    pub owner: Pubkey,
+    pub nft_mint: Pubkey,
+    pub price: u64,
+    pub expiry: u64,
+}
+
+/// Offer layout before offers were scoped to an auction house, stored at `[PREFIX, nft_mint, buyer, OFFER]`.
+#[derive(AnchorSerialize, AnchorDeserialize, Default)]
+pub struct LegacyOfferAccount {
+    pub buyer: Pubkey,
+    pub nft_mint: Pubkey,
+    pub price: u64,
+    pub expiry: u64,
+}
+
+/// Latest counter price in a negotiation between a listing owner and an offer buyer.
+#[account]
+#[derive(Default)]
+pub struct CounterOfferAccount {
+    pub offer: Pubkey,
+    pub seller: Pubkey,
+    pub buyer: Pubkey,
+    pub nft_mint: Pubkey,
+    pub price: u64,
+    pub expiry: u64,
+    /// Number of counters made so far, bounded by `MAX_COUNTER_ROUNDS`.
+    pub round: u8,
+    /// Whether the last counter came from the seller, the other party is the one to answer.
+    pub by_seller: bool,
+}
+
+#[account]
+#[derive(Default)]
+pub struct CollectionOfferAccount {
+    pub buyer: Pubkey,
+    pub auction_house: Pubkey,
+    /// Verified Metaplex collection key the NFTs must belong to.
+    pub collection: Pubkey,
+    /// Price paid per NFT.
+    pub price: u64,
+    /// NFTs still to be bought.
+    pub quantity: u64,
+    pub expiry: u64,
+}
+
+impl CollectionOfferAccount {
+    /// Escrow reserved for the remaining quantity.
+    pub fn escrow_amount(&self) -> Result<u64> {
+        Ok(self
+            .price
+            .checked_mul(self.quantity)
+            .ok_or(MarketplaceError::NumericalOverflow)?)
+    }
+}
+
+#[account]
+#[derive(Default)]
+pub struct SetOfferAccount {
+    pub buyer: Pubkey,
+    pub auction_house: Pubkey,
+    /// Merkle root of the eligible NFT mints, see `verify_merkle_proof`.
+    pub merkle_root: [u8; 32],
+    /// Price paid per NFT.
     pub price: u64,
+    /// NFTs still to be bought.
+    pub quantity: u64,
     pub expiry: u64,
 }
+
+impl SetOfferAccount {
+    /// Escrow reserved for the remaining quantity.
+    pub fn escrow_amount(&self) -> Result<u64> {
+        Ok(self
+            .price
+            .checked_mul(self.quantity)
+            .ok_or(MarketplaceError::NumericalOverflow)?)
+    }
+}
+
+#[account]
+#[derive(Default)]
+pub struct AuctionAccount {
+    pub owner: Pubkey,
+    pub nft_mint: Pubkey,
+    pub auction_house: Pubkey,
+    pub start_time: u64,
+    pub end_time: u64,
+    pub initial_end_time: u64,
+    pub reserve_price: u64,
+    pub min_bid_increment: u64,
+    pub highest_bidder: Pubkey,
+    pub highest_bid: u64,
+    pub kind: AuctionKind,
+    /// End of the reveal phase of sealed-bid auctions.
+    pub reveal_end_time: u64,
+    /// Second highest revealed bid of sealed-bid auctions.
+    pub second_bid: u64,
+    /// Slot the auction was created in, scoping its sealed bids so a relaunch on the same PDA starts afresh.
+    pub nonce: u64,
+}
+
+impl AuctionAccount {
+    /// Price the winner pays at settlement.
+    pub fn clearing_price(&self) -> u64 {
+        match self.kind {
+            AuctionKind::SealedSecondPrice => std::cmp::max(self.second_bid, self.reserve_price),
+            _ => self.highest_bid,
+        }
+    }
+}
+
+#[derive(AnchorSerialize, AnchorDeserialize, Clone, Copy, Default, PartialEq, Eq)]
+pub enum AuctionKind {
+    /// Open ascending bids.
+    #[default]
+    English,
+    /// Sealed bids, the winner pays their own bid.
+    SealedFirstPrice,
+    /// Sealed bids, the winner pays the second highest bid.
+    SealedSecondPrice,
+}
+
+#[account]
+#[derive(Default)]
+pub struct SealedBidAccount {
+    pub bidder: Pubkey,
+    pub auction: Pubkey,
+    /// Nonce of the auction the bid was committed to.
+    pub auction_nonce: u64,
+    /// Hash of the bid amount (little endian) followed by the salt.
+    pub commitment: [u8; 32],
+    /// Escrow amount reserved for the bid, caps the revealed amount.
+    pub deposit: u64,
+    pub revealed: bool,
+    pub amount: u64,
+}
+
+/// Ledger of a buyer escrow payment account, tracking how much of it backs open offers.
+#[account]
+#[derive(Default)]
+pub struct BuyerEscrowState {
+    pub wallet: Pubkey,
+    pub auction_house: Pubkey,
+    /// Funds held in escrow that have not been withdrawn or spent.
+    pub total_deposited: u64,
+    /// Funds reserved by open offers.
+    pub committed: u64,
+    /// Funds available to withdraw or to back new offers.
+    pub free: u64,
+}
+
+impl BuyerEscrowState {
+    /// Credit funds found in escrow but not yet recorded, e.g. deposits made before the ledger existed.
+    pub fn sync(&mut self, balance: u64) -> Result<()> {
+        if balance > self.total_deposited {
+            self.deposit(balance - self.total_deposited)?;
+        }
+        Ok(())
+    }
+
+    pub fn deposit(&mut self, amount: u64) -> Result<()> {
+        self.total_deposited = self
+            .total_deposited
+            .checked_add(amount)
+            .ok_or(MarketplaceError::NumericalOverflow)?;
+        self.update_free()
+    }
+
+    pub fn withdraw(&mut self, amount: u64) -> Result<()> {
+        require!(amount <= self.free, MarketplaceError::InsufficientEscrow);
+        self.total_deposited -= amount;
+        self.update_free()
+    }
+
+    pub fn commit(&mut self, amount: u64) -> Result<()> {
+        require!(amount <= self.free, MarketplaceError::InsufficientEscrow);
+        self.committed += amount;
+        self.update_free()
+    }
+
+    pub fn release(&mut self, amount: u64) -> Result<()> {
+        self.committed = self
+            .committed
+            .checked_sub(amount)
+            .ok_or(MarketplaceError::NumericalOverflow)?;
+        self.update_free()
+    }
+
+    /// Release a commitment and pay `amount` out of escrow.
+    pub fn spend(&mut self, committed: u64, amount: u64) -> Result<()> {
+        self.release(committed)?;
+        self.withdraw(amount)
+    }
+
+    fn update_free(&mut self) -> Result<()> {
+        self.free = self
+            .total_deposited
+            .checked_sub(self.committed)
+            .ok_or(MarketplaceError::NumericalOverflow)?;
+        Ok(())
+    }
+}
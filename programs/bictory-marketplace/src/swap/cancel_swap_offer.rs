@@ -0,0 +1,107 @@
+use anchor_lang::prelude::*;
+use anchor_spl::token::{self, Mint, Token, SetAuthority};
+use spl_token::instruction::AuthorityType;
+
+use crate::{constant::*, states::*, utils::*, error::*};
+
+/// Cancel swap offer, handing the offered NFTs back to the maker and releasing the sweetener reservation.
+/// Remaining accounts hold the nft_account of each offered NFT, in offer order.
+pub fn cancel_swap_offer<'info>(ctx: Context<'_, '_, '_, 'info, CancelSwapOffer<'info>>) -> Result<()> {
+    let offer_account = &ctx.accounts.offer_account;
+    require!(
+        ctx.remaining_accounts.len() == offer_account.offered_count as usize,
+        MarketplaceError::InvalidSwap,
+    );
+
+    let ah_key = ctx.accounts.auction_house.key();
+    let treasury_signer_seeds = &[
+        PREFIX,
+        ah_key.as_ref(),
+        TREASURY,
+        &[bump(&[PREFIX, ah_key.as_ref(), TREASURY], ctx.program_id)],
+    ];
+    let treasury_signer = &[&treasury_signer_seeds[..]];
+
+    for (index, nft_account_info) in ctx.remaining_accounts.iter().enumerate() {
+        // Check NFT account is the one the item was offered from
+        assert_keys_equal(nft_account_info.key(), offer_account.offered_accounts[index])?;
+
+        // Set nft's authority to owner
+        let cpi_account = SetAuthority {
+            current_authority: ctx.accounts.auction_house_treasury.to_account_info(),
+            account_or_mint: nft_account_info.clone(),
+        };
+        token::set_authority(
+            CpiContext::new_with_signer(ctx.accounts.token_program.to_account_info(), cpi_account, treasury_signer),
+            AuthorityType::AccountOwner,
+            Some(ctx.accounts.maker.key()),
+        )?;
+    }
+
+    let sweetener = offer_account.sweetener;
+    if sweetener > 0 {
+        ctx.accounts.escrow_state.release(sweetener)?;
+    }
+    msg!("{{\"released_escrow_amount\": \"{}\"}}", sweetener);
+
+    Ok(())
+}
+
+#[derive(Accounts)]
+pub struct CancelSwapOffer<'info> {
+    /// Maker account.
+    #[account(mut)]
+    pub maker: Signer<'info>,
+
+    /// Treasury mint account, either native SOL mint or a SPL token mint.
+    pub treasury_mint: Account<'info, Mint>,
+
+    /// Authority key for the Auction House.
+    /// CHECK: Validated as a auction house signer.
+    pub authority: UncheckedAccount<'info>,
+
+    /// Auction House treasury PDA account.
+    /// CHECK: Not dangerous. Account seeds checked in constraint.
+    #[account(seeds=[PREFIX, auction_house.key().as_ref(), TREASURY], bump)]
+    pub auction_house_treasury: UncheckedAccount<'info>,
+
+    /// Auction House instance PDA account.
+    #[account( 
+        seeds=[PREFIX, auction_house.creator.as_ref(), treasury_mint.key().as_ref()], 
+        bump, 
+        has_one=authority, 
+        has_one=treasury_mint,
+        has_one=auction_house_treasury
+    )]
+    pub auction_house: Box<Account<'info, AuctionHouse>>,
+
+    /// Swap offer PDA account
+    #[account(
+        mut,
+        seeds=[
+            PREFIX,
+            auction_house.key().as_ref(),
+            maker.key().as_ref(),
+            &offer_account.swap_id.to_le_bytes(),
+            SWAP
+        ],
+        bump, 
+        close = maker
+    )]
+    pub offer_account: Box<Account<'info, SwapOfferAccount>>,
+
+    /// Maker escrow ledger PDA account.
+    #[account(
+        mut,
+        seeds = [
+            PREFIX,
+            auction_house.key().as_ref(),
+            maker.key().as_ref(),
+            ESCROW_STATE
+        ],
+        bump
+    )]
+    pub escrow_state: Box<Account<'info, BuyerEscrowState>>,
+
+    pub token_program: Program<'info, Token>,
+}
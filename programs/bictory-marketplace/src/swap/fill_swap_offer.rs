@@ -0,0 +1,299 @@
+use anchor_lang::prelude::*;
+use anchor_lang::solana_program::account_info::next_account_info;
+use anchor_lang::solana_program::clock::Clock;
+use anchor_spl::associated_token::AssociatedToken;
+use anchor_spl::token::{self, Mint, Token, TokenAccount, Transfer};
+use mpl_token_metadata::state::Metadata;
+
+use crate::{constant::*, error::*, states::*, utils::*};
+
+#[derive(Accounts)]
+pub struct FillSwapOffer<'info> {
+    /// Taker user wallet account.
+    #[account(mut)]
+    pub taker: Signer<'info>,
+
+    /// CHECK: Validated as the swap offer maker.
+    /// Maker user wallet account.
+    #[account(mut, constraint = maker.key() == offer_account.maker)]
+    pub maker: UncheckedAccount<'info>,
+
+    /// Treasury mint account, either native SOL mint or a SPL token mint.
+    pub treasury_mint: Box<Account<'info, Mint>>,
+
+    /// CHECK: Not dangerous. Account seeds checked in constraint.
+    /// Maker escrow payment account PDA.
+    #[account(
+        mut,
+        seeds = [
+            PREFIX,
+            auction_house.key().as_ref(),
+            maker.key().as_ref()
+        ],
+        bump
+    )]
+    pub escrow_payment_account: UncheckedAccount<'info>,
+
+    /// Maker escrow ledger PDA account.
+    #[account(
+        mut,
+        seeds = [
+            PREFIX,
+            auction_house.key().as_ref(),
+            maker.key().as_ref(),
+            ESCROW_STATE
+        ],
+        bump
+    )]
+    pub escrow_state: Box<Account<'info, BuyerEscrowState>>,
+
+    /// CHECK: Validated in fill_swap_offer.
+    /// Taker SOL or SPL account to receive the sweetener at.
+    #[account(mut)]
+    pub taker_payment_receipt_account: UncheckedAccount<'info>,
+
+    /// Auction House instance authority account.
+    /// CHECK: Validated as a auction house signer.
+    pub authority: UncheckedAccount<'info>,
+
+    /// Auction House treasury PDA account.
+    /// CHECK: Not dangerous. Account seeds checked in constraint.
+    #[account(mut, seeds=[PREFIX, auction_house.key().as_ref(), TREASURY], bump)]
+    pub auction_house_treasury: UncheckedAccount<'info>,
+
+    /// Auction House instance PDA account.
+    #[account(
+        seeds = [
+            PREFIX,
+            auction_house.creator.as_ref(),
+            auction_house.treasury_mint.as_ref()
+        ],
+        bump,
+        has_one=authority,
+        has_one=treasury_mint,
+        has_one=auction_house_treasury
+    )]
+    pub auction_house: Box<Account<'info, AuctionHouse>>,
+
+    /// Swap offer PDA account
+    #[account(
+        mut,
+        seeds=[
+            PREFIX,
+            auction_house.key().as_ref(),
+            maker.key().as_ref(),
+            &offer_account.swap_id.to_le_bytes(),
+            SWAP
+        ],
+        bump,
+        close=maker
+    )]
+    pub offer_account: Box<Account<'info, SwapOfferAccount>>,
+
+    pub token_program: Program<'info, Token>,
+    pub system_program: Program<'info, System>,
+    pub ata_program: Program<'info, AssociatedToken>,
+    pub rent: Sysvar<'info, Rent>,
+}
+
+/// Fill a swap offer: the taker receives the offered NFTs and the sweetener net of auction house fees,
+/// the maker receives the requested NFTs.
+/// Remaining accounts hold (nft_mint, nft_account, taker_receipt_token_account) per offered NFT,
+/// followed by (nft_mint, metadata, taker_nft_account, maker_receipt_token_account) per requested NFT.
+pub fn fill_swap_offer<'info>(
+    ctx: Context<'_, '_, '_, 'info, FillSwapOffer<'info>>,
+) -> Result<()> {
+    let taker = &ctx.accounts.taker;
+    let maker = &ctx.accounts.maker;
+    let offer_account = &ctx.accounts.offer_account;
+    let auction_house = &ctx.accounts.auction_house;
+    let auction_house_treasury = &ctx.accounts.auction_house_treasury;
+    let system_program = &ctx.accounts.system_program;
+    let ata_program = &ctx.accounts.ata_program;
+    let token_program = &ctx.accounts.token_program;
+    let rent = &ctx.accounts.rent;
+
+    // Check offer is not expired
+    let now = Clock::get()?.unix_timestamp as u64;
+    require!(
+        offer_account.expiry == 0 || now <= offer_account.expiry,
+        MarketplaceError::Expired,
+    );
+
+    let ah_key = auction_house.key();
+    let treasury_signer_seeds = &[
+        PREFIX,
+        ah_key.as_ref(),
+        TREASURY,
+        &[bump(&[PREFIX, ah_key.as_ref(), TREASURY], ctx.program_id)],
+    ];
+    let treasury_signer = &[&treasury_signer_seeds[..]];
+
+    let remaining_accounts = &mut ctx.remaining_accounts.iter();
+
+    // Offered NFTs go from the treasury to the taker
+    for index in 0..offer_account.offered_count as usize {
+        let nft_mint = next_account_info(remaining_accounts)?;
+        let nft_account = next_account_info(remaining_accounts)?;
+        let taker_receipt_token_account = next_account_info(remaining_accounts)?;
+
+        assert_keys_equal(nft_mint.key(), offer_account.offered[index])?;
+        assert_keys_equal(nft_account.key(), offer_account.offered_accounts[index])?;
+
+        if taker_receipt_token_account.data_is_empty() {
+            make_ata(
+                taker_receipt_token_account.to_account_info(),
+                taker.to_account_info(),
+                nft_mint.to_account_info(),
+                taker.to_account_info(),
+                ata_program.to_account_info(),
+                token_program.to_account_info(),
+                system_program.to_account_info(),
+                rent.to_account_info(),
+                &[],
+            )?;
+        }
+        assert_is_ata(taker_receipt_token_account, &taker.key(), nft_mint.key)?;
+
+        let cpi_account = Transfer {
+            from: nft_account.clone(),
+            to: taker_receipt_token_account.clone(),
+            authority: auction_house_treasury.to_account_info(),
+        };
+        token::transfer(
+            CpiContext::new_with_signer(token_program.to_account_info(), cpi_account, treasury_signer),
+            1,
+        )?;
+    }
+
+    // Requested NFTs go from the taker to the maker
+    let mut requested_mints: Vec<Pubkey> = Vec::with_capacity(offer_account.requested_count as usize);
+    for index in 0..offer_account.requested_count as usize {
+        let nft_mint = next_account_info(remaining_accounts)?;
+        let metadata = next_account_info(remaining_accounts)?;
+        let taker_nft_account = next_account_info(remaining_accounts)?;
+        let maker_receipt_token_account = next_account_info(remaining_accounts)?;
+
+        match offer_account.requested_collection {
+            Some(collection) => {
+                // Check NFT is a verified member of the requested collection
+                require!(!requested_mints.contains(nft_mint.key), MarketplaceError::InvalidSwap);
+                assert_metadata(metadata, nft_mint.key)?;
+                let nft_metadata = Metadata::from_account_info(metadata)?;
+                match nft_metadata.collection {
+                    Some(nft_collection) => require!(
+                        nft_collection.verified && nft_collection.key == collection,
+                        MarketplaceError::InvalidCollection
+                    ),
+                    None => return Err(MarketplaceError::InvalidCollection.into()),
+                }
+            }
+            None => assert_keys_equal(nft_mint.key(), offer_account.requested[index])?,
+        }
+        requested_mints.push(nft_mint.key());
+
+        let nft_account: Account<TokenAccount> = Account::try_from(taker_nft_account)?;
+        require!(
+            nft_account.mint == nft_mint.key() && nft_account.amount > 0,
+            MarketplaceError::InvalidSwap,
+        );
+
+        if maker_receipt_token_account.data_is_empty() {
+            make_ata(
+                maker_receipt_token_account.to_account_info(),
+                maker.to_account_info(),
+                nft_mint.to_account_info(),
+                taker.to_account_info(),
+                ata_program.to_account_info(),
+                token_program.to_account_info(),
+                system_program.to_account_info(),
+                rent.to_account_info(),
+                &[],
+            )?;
+        }
+        let maker_rec_acct = assert_is_ata(maker_receipt_token_account, &maker.key(), nft_mint.key)?;
+
+        // make sure you cant get rugged
+        if maker_rec_acct.delegate.is_some() {
+            return Err(MarketplaceError::BuyerATACannotHaveDelegate.into());
+        }
+
+        let cpi_account = Transfer {
+            from: taker_nft_account.clone(),
+            to: maker_receipt_token_account.clone(),
+            authority: taker.to_account_info(),
+        };
+        token::transfer(CpiContext::new(token_program.to_account_info(), cpi_account), 1)?;
+    }
+
+    // Currency leg, auction house fees are charged on the sweetener only
+    let sweetener = offer_account.sweetener;
+    let mut auction_house_fee_paid = 0;
+    if sweetener > 0 {
+        let is_native = ctx.accounts.treasury_mint.key() == spl_token::native_mint::id();
+        let maker_key = maker.key();
+        let escrow_signer_seeds = [
+            PREFIX,
+            ah_key.as_ref(),
+            maker_key.as_ref(),
+            &[bump(&[PREFIX, ah_key.as_ref(), maker_key.as_ref()], ctx.program_id)],
+        ];
+        let ah_seeds = [
+            PREFIX,
+            auction_house.creator.as_ref(),
+            auction_house.treasury_mint.as_ref(),
+            &[bump(&[PREFIX, auction_house.creator.as_ref(), auction_house.treasury_mint.as_ref()], ctx.program_id)],
+        ];
+
+        // with the native account, the escrow is its own owner,
+        // whereas with token, it is the auction house that is owner.
+        let signer_seeds = if is_native {
+            escrow_signer_seeds
+        } else {
+            ah_seeds
+        };
+
+        let escrow_payment_account = &ctx.accounts.escrow_payment_account.to_account_info();
+        auction_house_fee_paid = pay_auction_house_fees(
+            auction_house,
+            &auction_house_treasury.to_account_info(),
+            escrow_payment_account,
+            &token_program.to_account_info(),
+            &system_program.to_account_info(),
+            &signer_seeds,
+            sweetener,
+            is_native,
+            false,
+        )?;
+
+        pay_seller(
+            &auction_house.to_account_info(),
+            escrow_payment_account,
+            &taker.to_account_info(),
+            &ctx.accounts.taker_payment_receipt_account,
+            &taker.to_account_info(),
+            &ctx.accounts.treasury_mint.to_account_info(),
+            &ata_program.to_account_info(),
+            &token_program.to_account_info(),
+            &system_program.to_account_info(),
+            &rent.to_account_info(),
+            &signer_seeds,
+            sweetener
+                .checked_sub(auction_house_fee_paid)
+                .ok_or(MarketplaceError::NumericalOverflow)?,
+            is_native,
+        )?;
+
+        ctx.accounts.escrow_state.spend(sweetener, sweetener)?;
+    }
+
+    msg!(
+        "{{\"taker\": \"{}\", \"swap_id\": {}, \"sweetener\": \"{}\", \"fee\": \"{}\"}}",
+        ctx.accounts.taker.key(),
+        ctx.accounts.offer_account.swap_id,
+        sweetener,
+        auction_house_fee_paid
+    );
+
+    Ok(())
+}
@@ -0,0 +1,239 @@
+use anchor_lang::prelude::*;
+use anchor_lang::solana_program::clock::Clock;
+use anchor_spl::token::{self, Mint, Token, TokenAccount, SetAuthority};
+use spl_token::instruction::AuthorityType;
+
+use crate::{constant::*, states::*, utils::*, error::*};
+
+/// Offer NFTs, plus an optional `sweetener` reserved from the maker escrow, in exchange for the requested NFTs.
+/// Remaining accounts hold (nft_mint, nft_account, metadata, edition) per offered NFT.
+pub fn make_swap_offer<'info>(
+    ctx: Context<'_, '_, '_, 'info, MakeSwapOffer<'info>>,
+    swap_id: u64,
+    request: SwapRequest,
+    sweetener: u64,
+    maker_expiry: Option<u64>,
+    deposit_shortfall: bool,
+) -> Result<()> {
+    // Check both sides of the swap
+    let offered_count = ctx.remaining_accounts.len() / 4;
+    require!(
+        offered_count > 0 && offered_count <= MAX_SWAP_ITEMS && ctx.remaining_accounts.len() == offered_count * 4,
+        MarketplaceError::InvalidSwap,
+    );
+
+    let offer_account = &mut ctx.accounts.offer_account;
+    match request {
+        SwapRequest::Mints { mints } => {
+            require!(
+                !mints.is_empty() && mints.len() <= MAX_SWAP_ITEMS,
+                MarketplaceError::InvalidSwap,
+            );
+            offer_account.requested[..mints.len()].copy_from_slice(&mints);
+            offer_account.requested_count = mints.len() as u8;
+            offer_account.requested_collection = None;
+        }
+        SwapRequest::Collection { collection, quantity } => {
+            require!(
+                quantity > 0 && quantity as usize <= MAX_SWAP_ITEMS,
+                MarketplaceError::InvalidSwap,
+            );
+            offer_account.requested_count = quantity;
+            offer_account.requested_collection = Some(collection);
+        }
+    }
+
+    // Check expiry date
+    let mut _expiry = 0;
+    if let Some(expiry) = maker_expiry {
+        let now = Clock::get()?.unix_timestamp as u64;
+        require!(expiry >= now, MarketplaceError::InvalidExpiry);
+
+        _expiry = expiry;
+    }
+
+    let maker = &ctx.accounts.maker;
+    for (index, item) in ctx.remaining_accounts.chunks(4).enumerate() {
+        let nft_mint_info = &item[0];
+        let nft_account_info = &item[1];
+
+        // Check offered item is a master edition NFT
+        let nft_mint: Account<Mint> = Account::try_from(nft_mint_info)?;
+        assert_nft_mint(&nft_mint, &item[2], &item[3])?;
+
+        // Check NFT is held by the maker and not offered twice
+        let nft_account: Account<TokenAccount> = Account::try_from(nft_account_info)?;
+        require!(
+            nft_account.mint == nft_mint.key() &&
+            nft_account.owner == maker.key() &&
+            nft_account.amount == 1,
+            MarketplaceError::InvalidSwap,
+        );
+        require!(
+            !offer_account.offered[..index].contains(&nft_mint.key()),
+            MarketplaceError::InvalidSwap,
+        );
+
+        // Set nft's authority to treasury account
+        let cpi_account = SetAuthority {
+            current_authority: maker.to_account_info(),
+            account_or_mint: nft_account_info.clone(),
+        };
+        token::set_authority(
+            CpiContext::new(ctx.accounts.token_program.to_account_info(), cpi_account),
+            AuthorityType::AccountOwner,
+            Some(ctx.accounts.auction_house_treasury.key()),
+        )?;
+
+        offer_account.offered[index] = nft_mint.key();
+        offer_account.offered_accounts[index] = nft_account_info.key();
+    }
+
+    let ah_key = ctx.accounts.auction_house.key();
+    let wallet_key = maker.key();
+
+    // Fill offer account
+    offer_account.maker = wallet_key;
+    offer_account.auction_house = ah_key;
+    offer_account.swap_id = swap_id;
+    offer_account.sweetener = sweetener;
+    offer_account.expiry = _expiry;
+    offer_account.offered_count = offered_count as u8;
+
+    // Reserve the sweetener from the maker escrow
+    if sweetener > 0 {
+        let is_native = ctx.accounts.treasury_mint.key() == spl_token::native_mint::id();
+
+        // If first time to use the escrow ledger
+        if is_zero_account(&ctx.accounts.escrow_state.to_account_info()) {
+            ctx.accounts.escrow_state.wallet = wallet_key;
+            ctx.accounts.escrow_state.auction_house = ah_key;
+        }
+        let balance = escrow_balance(&ctx.accounts.escrow_payment_account.to_account_info(), is_native)?;
+        ctx.accounts.escrow_state.sync(balance)?;
+
+        // Check free escrow covers the sweetener, topping it up first if requested
+        let free = ctx.accounts.escrow_state.free;
+        if free < sweetener {
+            require!(deposit_shortfall, MarketplaceError::InsufficientEscrow);
+
+            let escrow_signer_seeds = [
+                PREFIX,
+                ah_key.as_ref(),
+                wallet_key.as_ref(),
+                &[bump(&[PREFIX, ah_key.as_ref(), wallet_key.as_ref()], ctx.program_id)],
+            ];
+
+            transfer_to_escrow(
+                &maker.to_account_info(),
+                &ctx.accounts.payment_account,
+                &ctx.accounts.escrow_payment_account,
+                &ctx.accounts.treasury_mint,
+                &ctx.accounts.auction_house.to_account_info(),
+                &ctx.accounts.token_program,
+                &ctx.accounts.system_program,
+                &ctx.accounts.rent,
+                &escrow_signer_seeds,
+                sweetener - free,
+                is_native,
+            )?;
+            ctx.accounts.escrow_state.deposit(sweetener - free)?;
+        }
+        ctx.accounts.escrow_state.commit(sweetener)?;
+    }
+
+    // Log offer detail
+    msg!(
+        "{{\"swap_id\": {}, \"offered\": {}, \"requested\": {}, \"sweetener\": \"{}\", \"maker_expiry\": {}}}",
+        swap_id,
+        offered_count,
+        ctx.accounts.offer_account.requested_count,
+        sweetener,
+        _expiry
+    );
+
+    Ok(())
+}
+
+#[derive(Accounts)]
+#[instruction(swap_id: u64)]
+pub struct MakeSwapOffer<'info> {
+    /// Maker account.
+    #[account(mut)]
+    pub maker: Signer<'info>,
+
+    /// Treasury mint account, either native SOL mint or a SPL token mint.
+    pub treasury_mint: Account<'info, Mint>,
+
+    /// Authority key for the Auction House.
+    /// CHECK: Validated as a auction house signer.
+    pub authority: UncheckedAccount<'info>,
+
+    /// Auction House treasury PDA account.
+    /// CHECK: Not dangerous. Account seeds checked in constraint.
+    #[account(seeds=[PREFIX, auction_house.key().as_ref(), TREASURY], bump)]
+    pub auction_house_treasury: UncheckedAccount<'info>,
+
+    /// Auction House instance PDA account.
+    #[account( 
+        seeds=[PREFIX, auction_house.creator.as_ref(), treasury_mint.key().as_ref()], 
+        bump, 
+        has_one=authority, 
+        has_one=treasury_mint,
+        has_one=auction_house_treasury
+    )]
+    pub auction_house: Box<Account<'info, AuctionHouse>>,
+
+    /// CHECK: Validated in transfer_to_escrow.
+    /// Maker SOL or SPL account to top up the escrow from when `deposit_shortfall` is set.
+    #[account(mut)]
+    pub payment_account: UncheckedAccount<'info>,
+
+    /// CHECK: Not dangerous. Account seeds checked in constraint.
+    /// Maker escrow payment account PDA.
+    #[account(
+        mut,
+        seeds = [
+            PREFIX,
+            auction_house.key().as_ref(),
+            maker.key().as_ref()
+        ],
+        bump
+    )]
+    pub escrow_payment_account: UncheckedAccount<'info>,
+
+    /// Maker escrow ledger PDA account.
+    #[account(
+        init_if_needed,
+        seeds = [
+            PREFIX,
+            auction_house.key().as_ref(),
+            maker.key().as_ref(),
+            ESCROW_STATE
+        ],
+        bump,
+        space = 8 + std::mem::size_of::<BuyerEscrowState>(),
+        payer = maker
+    )]
+    pub escrow_state: Box<Account<'info, BuyerEscrowState>>,
+
+    /// Swap offer PDA account
+    #[account(
+        init,
+        seeds=[
+            PREFIX,
+            auction_house.key().as_ref(),
+            maker.key().as_ref(),
+            &swap_id.to_le_bytes(),
+            SWAP
+        ],
+        bump, 
+        space=8 + std::mem::size_of::<SwapOfferAccount>(),
+        payer=maker,
+    )]
+    pub offer_account: Box<Account<'info, SwapOfferAccount>>,
+
+    pub token_program: Program<'info, Token>,
+    pub system_program: Program<'info, System>,
+    pub rent: Sysvar<'info, Rent>,
+}
@@ -0,0 +1,5 @@
+pub mod cancel_swap_offer;
+pub mod fill_swap_offer;
+pub mod make_swap_offer;
+
+pub use {cancel_swap_offer::*, fill_swap_offer::*, make_swap_offer::*};
@@ -1,14 +1,20 @@
-use crate::{error::*, states::*};
+use crate::{constant::*, error::*, states::*};
 use anchor_lang::prelude::*;
+use anchor_lang::Discriminator;
 use anchor_lang::solana_program::{
-    program::invoke_signed,
+    keccak,
+    program::{invoke, invoke_signed},
     program_memory::sol_memcmp,
+    program_option::COption,
     program_pack::{IsInitialized, Pack},
     pubkey::PUBKEY_BYTES,
     system_instruction,
 };
-use anchor_spl::token::{Mint, Token};
-use mpl_token_metadata::state::Metadata;
+use anchor_spl::token::{self, CloseAccount, Mint, Token, TokenAccount, Transfer};
+use mpl_token_metadata::{
+    instruction::thaw_delegated_account,
+    state::{Key as MetadataKey, Metadata, EDITION},
+};
 use spl_associated_token_account::get_associated_token_address;
 use spl_token::{instruction::initialize_account2, state::Account as SplAccount};
 use std::{convert::TryInto, slice::Iter};
@@ -70,6 +76,64 @@ pub fn assert_is_ata2(
     Ok(ata_account)
 }
 
+/// Deserialize a pre-upgrade account of this program stored with the discriminator of its current type `T`.
+pub fn load_legacy_account<T: Discriminator, L: AnchorDeserialize>(
+    account: &AccountInfo,
+    program_id: &Pubkey,
+) -> Result<L> {
+    assert_owned_by(account, program_id)?;
+    let data = account.try_borrow_data()?;
+    require!(
+        data.len() >= 8 && data[..8] == T::discriminator(),
+        MarketplaceError::InvalidAccountInput,
+    );
+    Ok(L::deserialize(&mut &data[8..])?)
+}
+
+/// Close a pre-upgrade account of this program, refunding its rent to `destination`.
+pub fn close_legacy_account(account: &AccountInfo, destination: &AccountInfo) -> Result<()> {
+    let lamports = account.lamports();
+    **destination.lamports.borrow_mut() = destination
+        .lamports()
+        .checked_add(lamports)
+        .ok_or(MarketplaceError::NumericalOverflow)?;
+    **account.lamports.borrow_mut() = 0;
+    account.try_borrow_mut_data()?.fill(0);
+    Ok(())
+}
+
+/// Check the listed NFT is held for the treasury, as token account owner, as delegate or in the listing vault.
+pub fn assert_listed_nft(
+    program_id: &Pubkey,
+    nft_account: &AccountInfo,
+    listing: &Pubkey,
+    seller: &Pubkey,
+    mint: &Pubkey,
+    auction_house_treasury: &Pubkey,
+    custody: ListingCustody,
+) -> Result<SplAccount> {
+    match custody {
+        ListingCustody::Owner => assert_is_ata2(nft_account, seller, mint, auction_house_treasury),
+        ListingCustody::Delegate | ListingCustody::Frozen => {
+            let ata_account = assert_is_ata2(nft_account, seller, mint, seller)?;
+            require!(
+                ata_account.delegate == COption::Some(*auction_house_treasury) &&
+                ata_account.delegated_amount > 0,
+                MarketplaceError::InvalidDelegate
+            );
+            Ok(ata_account)
+        }
+        ListingCustody::Vault => {
+            assert_derivation(program_id, nft_account, &[PREFIX, listing.as_ref(), VAULT])?;
+            assert_owned_by(nft_account, &spl_token::id())?;
+            let vault_account: SplAccount = assert_initialized(nft_account)?;
+            assert_keys_equal(vault_account.owner, *auction_house_treasury)?;
+            assert_keys_equal(vault_account.mint, *mint)?;
+            Ok(vault_account)
+        }
+    }
+}
+
 pub fn assert_derivation(program_id: &Pubkey, account: &AccountInfo, path: &[&[u8]]) -> Result<()> {
     let (key, _) = Pubkey::find_program_address(path, program_id);
     if key != *account.key {
@@ -78,6 +142,64 @@ pub fn assert_derivation(program_id: &Pubkey, account: &AccountInfo, path: &[&[u
     Ok(())
 }
 
+pub fn assert_metadata(metadata: &AccountInfo, mint: &Pubkey) -> Result<()> {
+    assert_derivation(
+        &mpl_token_metadata::id(),
+        metadata,
+        &[
+            mpl_token_metadata::state::PREFIX.as_bytes(),
+            mpl_token_metadata::id().as_ref(),
+            mint.as_ref(),
+        ],
+    )?;
+
+    if metadata.data_is_empty() {
+        return Err(MarketplaceError::InvalidAccountInput.into());
+    }
+    Ok(())
+}
+
+/// Check `mint` is a genuine NFT: no decimals, a supply of one, a Token Metadata account and a master edition.
+pub fn assert_nft_mint(mint: &Account<Mint>, metadata: &AccountInfo, edition: &AccountInfo) -> Result<()> {
+    require!(mint.decimals == 0 && mint.supply == 1, MarketplaceError::InvalidNftMint);
+
+    assert_metadata(metadata, &mint.key())?;
+    assert_owned_by(metadata, &mpl_token_metadata::id())?;
+    Metadata::from_account_info(metadata)?;
+
+    assert_derivation(
+        &mpl_token_metadata::id(),
+        edition,
+        &[
+            mpl_token_metadata::state::PREFIX.as_bytes(),
+            mpl_token_metadata::id().as_ref(),
+            mint.key().as_ref(),
+            EDITION.as_bytes(),
+        ],
+    )?;
+    assert_owned_by(edition, &mpl_token_metadata::id())?;
+    let key = edition.try_borrow_data()?.first().copied();
+    require!(
+        key == Some(MetadataKey::MasterEditionV1 as u8) || key == Some(MetadataKey::MasterEditionV2 as u8),
+        MarketplaceError::InvalidNftMint,
+    );
+    Ok(())
+}
+
+/// Check `leaf` is in the merkle tree of `root`. Leaves are the keccak hash of the mint key
+/// and every node is the keccak hash of its two children in ascending order.
+pub fn verify_merkle_proof(proof: &[[u8; 32]], root: &[u8; 32], leaf: &Pubkey) -> bool {
+    let mut node = keccak::hash(leaf.as_ref()).to_bytes();
+    for sibling in proof {
+        node = if node <= *sibling {
+            keccak::hashv(&[&node, sibling]).to_bytes()
+        } else {
+            keccak::hashv(&[sibling, &node]).to_bytes()
+        };
+    }
+    node == *root
+}
+
 pub fn make_ata<'a>(
     ata: AccountInfo<'a>,
     wallet: AccountInfo<'a>,
@@ -119,6 +241,19 @@ pub fn make_ata<'a>(
     Ok(())
 }
 
+/// Spendable balance held in a buyer escrow payment account, excluding the rent exempt minimum for native escrows.
+pub fn escrow_balance(escrow_payment_account: &AccountInfo, is_native: bool) -> Result<u64> {
+    if is_native {
+        let rent_minimum: u64 = (Rent::get()?).minimum_balance(escrow_payment_account.data_len());
+        Ok(escrow_payment_account.lamports().saturating_sub(rent_minimum))
+    } else if escrow_payment_account.data_is_empty() {
+        Ok(0)
+    } else {
+        let escrow: SplAccount = assert_initialized(escrow_payment_account)?;
+        Ok(escrow.amount)
+    }
+}
+
 pub fn rent_checked_sub(escrow_account: AccountInfo, diff: u64) -> Result<u64> {
     let rent_minimum: u64 = (Rent::get()?).minimum_balance(escrow_account.data_len());
     let account_lamports: u64 = escrow_account
@@ -253,6 +388,119 @@ pub fn create_program_token_account_if_not_present<'a>(
     Ok(())
 }
 
+/// Move `amount` from the wallet's payment account into its escrow payment account, creating the escrow token account if needed.
+#[allow(clippy::too_many_arguments)]
+pub fn transfer_to_escrow<'a>(
+    wallet: &AccountInfo<'a>,
+    payment_account: &UncheckedAccount<'a>,
+    escrow_payment_account: &UncheckedAccount<'a>,
+    treasury_mint: &anchor_lang::prelude::Account<'a, Mint>,
+    auction_house: &AccountInfo<'a>,
+    token_program: &Program<'a, Token>,
+    system_program: &Program<'a, System>,
+    rent: &Sysvar<'a, Rent>,
+    escrow_signer_seeds: &[&[u8]],
+    amount: u64,
+    is_native: bool,
+) -> Result<()> {
+    create_program_token_account_if_not_present(
+        escrow_payment_account,
+        system_program,
+        wallet,
+        token_program,
+        treasury_mint,
+        auction_house,
+        rent,
+        escrow_signer_seeds,
+        &[],
+        is_native,
+    )?;
+
+    if !is_native {
+        assert_is_ata(payment_account, wallet.key, &treasury_mint.key())?;
+        invoke(
+            &spl_token::instruction::transfer(
+                token_program.key,
+                &payment_account.key(),
+                &escrow_payment_account.key(),
+                wallet.key,
+                &[],
+                amount,
+            )?,
+            &[
+                escrow_payment_account.to_account_info(),
+                payment_account.to_account_info(),
+                token_program.to_account_info(),
+                wallet.clone(),
+            ],
+        )?;
+    } else {
+        assert_keys_equal(payment_account.key(), *wallet.key)?;
+
+        // Reach rental exemption and then add deposit amount.
+        let checked_amount = rent_checked_add(escrow_payment_account.to_account_info(), 0)?
+            .checked_add(amount)
+            .ok_or(MarketplaceError::NumericalOverflow)?;
+        invoke(
+            &system_instruction::transfer(
+                &payment_account.key(),
+                &escrow_payment_account.key(),
+                checked_amount,
+            ),
+            &[
+                escrow_payment_account.to_account_info(),
+                payment_account.to_account_info(),
+                system_program.to_account_info(),
+            ],
+        )?;
+    }
+    Ok(())
+}
+
+/// Pay a cancellation fee of `amount` from the wallet's payment account into the auction house treasury.
+#[allow(clippy::too_many_arguments)]
+pub fn pay_cancel_fee<'a>(
+    wallet: &AccountInfo<'a>,
+    payment_account: &UncheckedAccount<'a>,
+    auction_house_treasury: &AccountInfo<'a>,
+    treasury_mint: &Pubkey,
+    token_program: &AccountInfo<'a>,
+    system_program: &AccountInfo<'a>,
+    amount: u64,
+    is_native: bool,
+) -> Result<()> {
+    if !is_native {
+        assert_is_ata(payment_account, wallet.key, treasury_mint)?;
+        invoke(
+            &spl_token::instruction::transfer(
+                token_program.key,
+                &payment_account.key(),
+                auction_house_treasury.key,
+                wallet.key,
+                &[],
+                amount,
+            )?,
+            &[
+                payment_account.to_account_info(),
+                auction_house_treasury.clone(),
+                token_program.clone(),
+                wallet.clone(),
+            ],
+        )?;
+    } else {
+        assert_keys_equal(payment_account.key(), *wallet.key)?;
+        invoke(
+            &system_instruction::transfer(wallet.key, auction_house_treasury.key, amount),
+            &[
+                wallet.clone(),
+                auction_house_treasury.clone(),
+                system_program.clone(),
+            ],
+        )?;
+    }
+    Ok(())
+}
+
 #[allow(clippy::too_many_arguments)]
 pub fn pay_auction_house_fees<'a>(
     auction_house: &anchor_lang::prelude::Account<'a, AuctionHouse>,
@@ -424,3 +672,306 @@ pub fn pay_creator_fees<'a>(
         .checked_add(remaining_fee)
         .ok_or(MarketplaceError::NumericalOverflow)?)
 }
+
+/// Check the optional discount NFT (mint, token account, metadata) passed after the creators in remaining accounts.
+pub fn check_discount(
+    remaining_accounts: &mut Iter<AccountInfo>,
+    buyer: &Pubkey,
+    auction_house: &AuctionHouse,
+) -> Result<bool> {
+    let remain_discount_mint = next_account_info(remaining_accounts).ok();
+
+    if let Some(discount_mint) = remain_discount_mint {
+        let discount_account = next_account_info(remaining_accounts)?;
+        let discount_metadata = next_account_info(remaining_accounts)?;
+
+        if discount_metadata.data_is_empty() {
+            return Err(MarketplaceError::InvalidDiscountAccount.into());
+        }
+
+        // Check discount NFT is valid
+        let discount_ata = assert_is_ata(discount_account, buyer, discount_mint.key)?;
+
+        // Check discount ATA balance
+        if discount_ata.amount < 1 {
+            return Err(MarketplaceError::InvalidDiscountAccount.into());
+        }
+
+        // Check discount metadata
+        assert_metadata(discount_metadata, discount_mint.key)?;
+
+        // Check discount account is valid collection
+        let _discount_metadata = Metadata::from_account_info(discount_metadata)?;
+        if let Some(collection) = _discount_metadata.collection {
+            require!(
+                collection.verified && collection.key == auction_house.discount_collection,
+                MarketplaceError::InvalidDiscountAccount
+            );
+            return Ok(true);
+        } else {
+            return Err(MarketplaceError::InvalidDiscountAccount.into());
+        }
+    }
+    Ok(false)
+}
+
+/// Transfer `amount` out of a buyer escrow payment account.
+/// With the native account, the escrow is its own owner, whereas with token, it is the auction house that is owner,
+/// so `signer_seeds` must be the escrow seeds or the auction house seeds respectively.
+#[allow(clippy::too_many_arguments)]
+pub fn transfer_from_escrow<'a>(
+    escrow_payment_account: &AccountInfo<'a>,
+    receipt_account: &AccountInfo<'a>,
+    auction_house: &AccountInfo<'a>,
+    token_program: &AccountInfo<'a>,
+    system_program: &AccountInfo<'a>,
+    signer_seeds: &[&[u8]],
+    amount: u64,
+    is_native: bool,
+) -> Result<()> {
+    if !is_native {
+        invoke_signed(
+            &spl_token::instruction::transfer(
+                token_program.key,
+                escrow_payment_account.key,
+                receipt_account.key,
+                auction_house.key,
+                &[],
+                amount,
+            )?,
+            &[
+                escrow_payment_account.clone(),
+                receipt_account.clone(),
+                token_program.clone(),
+                auction_house.clone(),
+            ],
+            &[signer_seeds],
+        )?;
+    } else {
+        invoke_signed(
+            &system_instruction::transfer(escrow_payment_account.key, receipt_account.key, amount),
+            &[
+                escrow_payment_account.clone(),
+                receipt_account.clone(),
+                system_program.clone(),
+            ],
+            &[signer_seeds],
+        )?;
+    }
+    Ok(())
+}
+
+/// Pay creator royalties, auction house fees and the seller out of a buyer escrow for a sale at `price`.
+/// Remaining accounts hold the NFT creators followed by the optional discount NFT accounts.
+/// Returns the amount received by the seller.
+#[allow(clippy::too_many_arguments)]
+pub fn pay_sale_proceeds<'a>(
+    remaining_accounts: &mut Iter<AccountInfo<'a>>,
+    auction_house: &anchor_lang::prelude::Account<'a, AuctionHouse>,
+    auction_house_treasury: &AccountInfo<'a>,
+    metadata: &AccountInfo<'a>,
+    escrow_payment_account: &AccountInfo<'a>,
+    buyer: &AccountInfo<'a>,
+    seller: &AccountInfo<'a>,
+    seller_payment_receipt_account: &AccountInfo<'a>,
+    fee_payer: &AccountInfo<'a>,
+    treasury_mint: &AccountInfo<'a>,
+    ata_program: &AccountInfo<'a>,
+    token_program: &AccountInfo<'a>,
+    system_program: &AccountInfo<'a>,
+    rent: &AccountInfo<'a>,
+    signer_seeds: &[&[u8]],
+    price: u64,
+    is_native: bool,
+) -> Result<u64> {
+    let auction_house_info = auction_house.to_account_info();
+
+    let buyer_leftover_after_royalties = pay_creator_fees(
+        remaining_accounts,
+        metadata,
+        escrow_payment_account,
+        &auction_house_info,
+        fee_payer,
+        treasury_mint,
+        ata_program,
+        token_program,
+        system_program,
+        rent,
+        signer_seeds,
+        &[],
+        price,
+        is_native,
+    )?;
+
+    // Check discount NFT metadata
+    let is_discount = check_discount(remaining_accounts, buyer.key, auction_house)?;
+
+    msg!("Discount: {}", is_discount);
+
+    let auction_house_fee_paid = pay_auction_house_fees(
+        auction_house,
+        auction_house_treasury,
+        escrow_payment_account,
+        token_program,
+        system_program,
+        signer_seeds,
+        price,
+        is_native,
+        is_discount,
+    )?;
+
+    let buyer_leftover_after_royalties_and_house_fee = buyer_leftover_after_royalties
+        .checked_sub(auction_house_fee_paid)
+        .ok_or(MarketplaceError::NumericalOverflow)?;
+
+    pay_seller(
+        &auction_house_info,
+        escrow_payment_account,
+        seller,
+        seller_payment_receipt_account,
+        fee_payer,
+        treasury_mint,
+        ata_program,
+        token_program,
+        system_program,
+        rent,
+        signer_seeds,
+        buyer_leftover_after_royalties_and_house_fee,
+        is_native,
+    )?;
+
+    Ok(buyer_leftover_after_royalties_and_house_fee)
+}
+
+/// Transfer the seller share of a sale out of a buyer escrow, creating the seller receipt ATA if needed.
+#[allow(clippy::too_many_arguments)]
+pub fn pay_seller<'a>(
+    auction_house_info: &AccountInfo<'a>,
+    escrow_payment_account: &AccountInfo<'a>,
+    seller: &AccountInfo<'a>,
+    seller_payment_receipt_account: &AccountInfo<'a>,
+    fee_payer: &AccountInfo<'a>,
+    treasury_mint: &AccountInfo<'a>,
+    ata_program: &AccountInfo<'a>,
+    token_program: &AccountInfo<'a>,
+    system_program: &AccountInfo<'a>,
+    rent: &AccountInfo<'a>,
+    signer_seeds: &[&[u8]],
+    amount: u64,
+    is_native: bool,
+) -> Result<()> {
+    if !is_native {
+        if seller_payment_receipt_account.data_is_empty() {
+            make_ata(
+                seller_payment_receipt_account.clone(),
+                seller.clone(),
+                treasury_mint.clone(),
+                fee_payer.clone(),
+                ata_program.clone(),
+                token_program.clone(),
+                system_program.clone(),
+                rent.clone(),
+                &[],
+            )?;
+        }
+
+        let seller_rec_acct =
+            assert_is_ata(seller_payment_receipt_account, seller.key, treasury_mint.key)?;
+
+        // make sure you cant get rugged
+        if seller_rec_acct.delegate.is_some() {
+            return Err(MarketplaceError::SellerATACannotHaveDelegate.into());
+        }
+    } else {
+        assert_keys_equal(*seller_payment_receipt_account.key, *seller.key)?;
+    }
+
+    transfer_from_escrow(
+        escrow_payment_account,
+        seller_payment_receipt_account,
+        auction_house_info,
+        token_program,
+        system_program,
+        signer_seeds,
+        amount,
+        is_native,
+    )
+}
+
+/// Thaw a frozen delegated listing so the treasury can move the NFT or the seller can take it back.
+#[allow(clippy::too_many_arguments)]
+pub fn thaw_listed_nft<'a>(
+    custody: ListingCustody,
+    nft_account: &AccountInfo<'a>,
+    edition: &AccountInfo<'a>,
+    nft_mint: &AccountInfo<'a>,
+    auction_house_treasury: &AccountInfo<'a>,
+    token_metadata_program: &AccountInfo<'a>,
+    token_program: &AccountInfo<'a>,
+    treasury_signer_seeds: &[&[u8]],
+) -> Result<()> {
+    if custody == ListingCustody::Frozen {
+        invoke_signed(
+            &thaw_delegated_account(
+                mpl_token_metadata::id(),
+                auction_house_treasury.key(),
+                nft_account.key(),
+                edition.key(),
+                nft_mint.key(),
+            ),
+            &[
+                auction_house_treasury.clone(),
+                nft_account.clone(),
+                edition.clone(),
+                nft_mint.clone(),
+                token_program.clone(),
+                token_metadata_program.clone(),
+            ],
+            &[treasury_signer_seeds],
+        )?;
+    }
+    Ok(())
+}
+
+/// Send what is left in a listing vault to `receipt_account` and close the vault, refunding its rent to `seller`.
+pub fn close_vault<'info>(
+    vault: &Account<'info, TokenAccount>,
+    receipt_account: &AccountInfo<'info>,
+    seller: &AccountInfo<'info>,
+    auction_house_treasury: &AccountInfo<'info>,
+    token_program: &Program<'info, Token>,
+    treasury_signer: &[&[&[u8]]],
+) -> Result<()> {
+    if vault.amount > 0 {
+        let cpi_account = Transfer {
+            from: vault.to_account_info(),
+            to: receipt_account.clone(),
+            authority: auction_house_treasury.clone(),
+        };
+        token::transfer(
+            CpiContext::new_with_signer(token_program.to_account_info(), cpi_account, treasury_signer),
+            vault.amount,
+        )?;
+    }
+
+    let cpi_account = CloseAccount {
+        account: vault.to_account_info(),
+        destination: seller.clone(),
+        authority: auction_house_treasury.clone(),
+    };
+    token::close_account(CpiContext::new_with_signer(
+        token_program.to_account_info(),
+        cpi_account,
+        treasury_signer,
+    ))
+}
+
+/// Reset the counter-offer PDA of an offer if a negotiation was opened on it, so a counter cannot outlive its offer.
+pub fn reset_counter_offer<'info>(counter_info: &AccountInfo<'info>, program_id: &Pubkey) -> Result<()> {
+    if counter_info.owner == program_id && !counter_info.data_is_empty() {
+        let mut counter_account: Account<CounterOfferAccount> = Account::try_from(counter_info)?;
+        *counter_account = CounterOfferAccount::default();
+        counter_account.exit(program_id)?;
+    }
+    Ok(())
+}
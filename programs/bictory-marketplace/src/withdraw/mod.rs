@@ -0,0 +1,183 @@
+use anchor_lang::{
+    prelude::*,
+    solana_program::{program::invoke_signed, system_instruction},
+};
+use anchor_spl::{
+    associated_token::AssociatedToken,
+    token::{Mint, Token},
+};
+
+use crate::{constant::*, states::*, utils::*};
+
+#[derive(Accounts)]
+pub struct Withdraw<'info> {
+    /// User wallet account.
+    #[account(mut)]
+    pub wallet: Signer<'info>,
+
+    /// CHECK: Validated in withdraw_logic.
+    /// SPL token account or native SOL account to transfer funds to. If the account is a native SOL account, this is the same as the wallet address.
+    #[account(mut)]
+    pub receipt_account: UncheckedAccount<'info>,
+
+    /// CHECK: Not dangerous. Account seeds checked in constraint.
+    /// Buyer escrow payment account PDA.
+    #[account(
+        mut,
+        seeds = [
+            PREFIX,
+            auction_house.key().as_ref(),
+            wallet.key().as_ref()
+        ],
+        bump
+    )]
+    pub escrow_payment_account: UncheckedAccount<'info>,
+
+    /// Buyer escrow ledger PDA account.
+    #[account(
+        init_if_needed,
+        seeds = [
+            PREFIX,
+            auction_house.key().as_ref(),
+            wallet.key().as_ref(),
+            ESCROW_STATE
+        ],
+        bump,
+        space = 8 + std::mem::size_of::<BuyerEscrowState>(),
+        payer = wallet
+    )]
+    pub escrow_state: Box<Account<'info, BuyerEscrowState>>,
+
+    /// Auction House instance treasury mint account.
+    pub treasury_mint: Box<Account<'info, Mint>>,
+
+    /// Auction House instance authority account.
+    /// CHECK: Validated as a auction house signer.
+    pub authority: UncheckedAccount<'info>,
+
+    /// Auction House instance PDA account.
+    #[account(
+        seeds = [
+            PREFIX,
+            auction_house.creator.as_ref(),
+            auction_house.treasury_mint.as_ref()
+        ],
+        bump,
+        has_one=authority,
+        has_one=treasury_mint
+    )]
+    pub auction_house: Box<Account<'info, AuctionHouse>>,
+
+    pub token_program: Program<'info, Token>,
+    pub system_program: Program<'info, System>,
+    pub ata_program: Program<'info, AssociatedToken>,
+    pub rent: Sysvar<'info, Rent>,
+}
+
+pub fn handle(ctx: Context<Withdraw>, amount: u64) -> Result<()> {
+    let wallet = &ctx.accounts.wallet;
+    let receipt_account = &ctx.accounts.receipt_account;
+    let escrow_payment_account = &ctx.accounts.escrow_payment_account;
+    let auction_house = &ctx.accounts.auction_house;
+    let treasury_mint = &ctx.accounts.treasury_mint;
+    let system_program = &ctx.accounts.system_program;
+    let token_program = &ctx.accounts.token_program;
+    let ata_program = &ctx.accounts.ata_program;
+    let rent = &ctx.accounts.rent;
+
+    let ah_key = auction_house.key();
+    let wallet_key = wallet.key();
+
+    let escrow_signer_seeds = [
+        PREFIX,
+        ah_key.as_ref(),
+        wallet_key.as_ref(),
+        &[bump(
+            &[PREFIX, ah_key.as_ref(), wallet_key.as_ref()],
+            ctx.program_id,
+        )],
+    ];
+
+    let ah_seeds = [
+        PREFIX,
+        auction_house.creator.as_ref(),
+        auction_house.treasury_mint.as_ref(),
+        &[bump(
+            &[
+                PREFIX,
+                auction_house.creator.as_ref(),
+                auction_house.treasury_mint.as_ref(),
+            ],
+            ctx.program_id,
+        )],
+    ];
+
+    let is_native = treasury_mint.key() == spl_token::native_mint::id();
+
+    // If first time to use the escrow ledger
+    let escrow_state = &mut ctx.accounts.escrow_state;
+    if is_zero_account(&escrow_state.to_account_info()) {
+        escrow_state.wallet = wallet_key;
+        escrow_state.auction_house = ah_key;
+    }
+    escrow_state.sync(escrow_balance(&escrow_payment_account.to_account_info(), is_native)?)?;
+
+    // with the native account, the escrow is its own owner,
+    // whereas with token, it is the auction house that is owner.
+    if !is_native {
+        if receipt_account.data_is_empty() {
+            make_ata(
+                receipt_account.to_account_info(),
+                wallet.to_account_info(),
+                treasury_mint.to_account_info(),
+                wallet.to_account_info(),
+                ata_program.to_account_info(),
+                token_program.to_account_info(),
+                system_program.to_account_info(),
+                rent.to_account_info(),
+                &[],
+            )?;
+        }
+
+        assert_is_ata(receipt_account, &wallet.key(), &treasury_mint.key())?;
+        invoke_signed(
+            &spl_token::instruction::transfer(
+                token_program.key,
+                &escrow_payment_account.key(),
+                &receipt_account.key(),
+                &auction_house.key(),
+                &[],
+                amount,
+            )?,
+            &[
+                escrow_payment_account.to_account_info(),
+                receipt_account.to_account_info(),
+                token_program.to_account_info(),
+                auction_house.to_account_info(),
+            ],
+            &[&ah_seeds],
+        )?;
+        escrow_state.withdraw(amount)?;
+    } else {
+        assert_keys_equal(receipt_account.key(), wallet.key())?;
+
+        // Never drain the escrow below rent exemption.
+        let checked_amount = rent_checked_sub(escrow_payment_account.to_account_info(), amount)?;
+        invoke_signed(
+            &system_instruction::transfer(
+                &escrow_payment_account.key(),
+                &receipt_account.key(),
+                checked_amount,
+            ),
+            &[
+                escrow_payment_account.to_account_info(),
+                receipt_account.to_account_info(),
+                system_program.to_account_info(),
+            ],
+            &[&escrow_signer_seeds],
+        )?;
+        escrow_state.withdraw(checked_amount)?;
+    }
+
+    Ok(())
+}
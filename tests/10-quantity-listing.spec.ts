@@ -0,0 +1,102 @@
+import * as anchor from '@project-serum/anchor';
+import { Program } from '@project-serum/anchor';
+import { getAccount, getAssociatedTokenAddress, NATIVE_MINT } from '@solana/spl-token';
+
+import AUTHORITY_WALLET from './keypairs/authority.json';
+
+import { Marketplace } from "../target/types/marketplace";
+
+import * as utils from './utils';
+import { BN } from 'bn.js';
+import { assert } from 'chai';
+import { mintSFT } from './actions/mintNft';
+import { listQuantity } from './actions/listQuantity';
+import { buyQuantity } from './actions/buyQuantity';
+import { unlistQuantity } from './actions/unlistQuantity';
+
+describe("quantity-listing", () => {
+
+  // Configure the client to use the local cluster.
+  const provider = anchor.AnchorProvider.env();
+  anchor.setProvider(provider);
+
+  const program = anchor.workspace.Marketplace as Program<Marketplace>;
+
+  // Create test keypairs
+  const authority = anchor.web3.Keypair.fromSecretKey(Buffer.from(AUTHORITY_WALLET));
+
+  it('Two buyers partially fill a quantity listing until sold out', async () => {
+
+    const seller = anchor.web3.Keypair.generate();
+    await utils.safeAirdrop(provider.connection, seller.publicKey, 1);
+
+    const buyer1 = anchor.web3.Keypair.generate();
+    await utils.safeAirdrop(provider.connection, buyer1.publicKey, 1);
+
+    const buyer2 = anchor.web3.Keypair.generate();
+    await utils.safeAirdrop(provider.connection, buyer2.publicKey, 1);
+
+    const { mint } = await mintSFT({
+      payer: seller,
+      connection: provider.connection,
+      amount: 10
+    });
+
+    const auctionHouse = utils.findAuctionHouse(authority.publicKey, NATIVE_MINT);
+    const listingAccount = utils.findQuantityListing(auctionHouse, mint, seller.publicKey);
+
+    const tx1 = await listQuantity(program, seller, authority.publicKey, NATIVE_MINT, mint, new BN(1_000), new BN(5), null);
+    const vaultInfo = await getAccount(provider.connection, utils.findVault(listingAccount));
+    assert(vaultInfo.amount == BigInt(5), "Units not moved into vault.");
+
+    // Buying more than listed fails
+    const tx2 = await buyQuantity(program, buyer1, seller.publicKey, authority.publicKey, NATIVE_MINT, mint, new BN(6));
+    let listing = await program.account.quantityListingAccount.fetch(listingAccount);
+    assert(listing.quantity.toNumber() == 5, "Oversized purchase accepted.");
+
+    const tx3 = await buyQuantity(program, buyer1, seller.publicKey, authority.publicKey, NATIVE_MINT, mint, new BN(2));
+    listing = await program.account.quantityListingAccount.fetch(listingAccount);
+    assert(listing.quantity.toNumber() == 3, "Quantity not decreased.");
+
+    const tx4 = await buyQuantity(program, buyer2, seller.publicKey, authority.publicKey, NATIVE_MINT, mint, new BN(3));
+    const listingInfo = await provider.connection.getAccountInfo(listingAccount);
+    assert(listingInfo == null, "Sold out listing not closed.");
+
+    // Check unit ownership
+    const buyer1Info = await getAccount(provider.connection, await getAssociatedTokenAddress(mint, buyer1.publicKey));
+    assert(buyer1Info.amount == BigInt(2), "Units not sent to first buyer.");
+    const buyer2Info = await getAccount(provider.connection, await getAssociatedTokenAddress(mint, buyer2.publicKey));
+    assert(buyer2Info.amount == BigInt(3), "Units not sent to second buyer.");
+  });
+
+  it('Unlist quantity returns remaining units to seller', async () => {
+
+    const seller = anchor.web3.Keypair.generate();
+    await utils.safeAirdrop(provider.connection, seller.publicKey, 1);
+
+    const buyer = anchor.web3.Keypair.generate();
+    await utils.safeAirdrop(provider.connection, buyer.publicKey, 1);
+
+    const { mint, tokenAccount } = await mintSFT({
+      payer: seller,
+      connection: provider.connection,
+      amount: 10
+    });
+
+    const auctionHouse = utils.findAuctionHouse(authority.publicKey, NATIVE_MINT);
+    const listingAccount = utils.findQuantityListing(auctionHouse, mint, seller.publicKey);
+
+    const tx1 = await listQuantity(program, seller, authority.publicKey, NATIVE_MINT, mint, new BN(1_000), new BN(4), null);
+    const tx2 = await buyQuantity(program, buyer, seller.publicKey, authority.publicKey, NATIVE_MINT, mint, new BN(1));
+    const tx3 = await unlistQuantity(program, seller, authority.publicKey, NATIVE_MINT, mint);
+
+    const listingInfo = await provider.connection.getAccountInfo(listingAccount);
+    assert(listingInfo == null, "Listing not closed.");
+
+    const vaultInfo = await provider.connection.getAccountInfo(utils.findVault(listingAccount));
+    assert(vaultInfo == null, "Vault not closed.");
+
+    const sellerInfo = await getAccount(provider.connection, tokenAccount);
+    assert(sellerInfo.amount == BigInt(9), "Remaining units not returned.");
+  });
+});
@@ -0,0 +1,118 @@
+import * as anchor from '@project-serum/anchor';
+import { Program } from '@project-serum/anchor';
+import { createAccount, getAccount, getAssociatedTokenAddress, NATIVE_MINT } from '@solana/spl-token';
+
+import AUTHORITY_WALLET from './keypairs/authority.json';
+
+import { Marketplace } from "../target/types/marketplace";
+
+import * as utils from './utils';
+import { BN } from 'bn.js';
+import { assert } from 'chai';
+import { mintNFT } from './actions/mintNft';
+import { listBundle } from './actions/listBundle';
+import { buyBundle } from './actions/buyBundle';
+import { unlistBundle } from './actions/unlistBundle';
+
+describe("bundle", () => {
+
+  // Configure the client to use the local cluster.
+  const provider = anchor.AnchorProvider.env();
+  anchor.setProvider(provider);
+
+  const program = anchor.workspace.Marketplace as Program<Marketplace>;
+
+  // Create test keypairs
+  const authority = anchor.web3.Keypair.fromSecretKey(Buffer.from(AUTHORITY_WALLET));
+
+  it('Buy bundle transfers every item and pro-rates royalties', async () => {
+
+    const seller = anchor.web3.Keypair.generate();
+    await utils.safeAirdrop(provider.connection, seller.publicKey, 1);
+
+    const buyer = anchor.web3.Keypair.generate();
+    await utils.safeAirdrop(provider.connection, buyer.publicKey, 3);
+
+    const creator1 = anchor.web3.Keypair.generate();
+    await utils.safeAirdrop(provider.connection, creator1.publicKey, 1);
+
+    const creator2 = anchor.web3.Keypair.generate();
+    await utils.safeAirdrop(provider.connection, creator2.publicKey, 1);
+
+    const { mint: nftMint1 } = await mintNFT({
+      payer: seller,
+      connection: provider.connection,
+      creators: [{ address: creator1.publicKey, verified: false, share: 100 }],
+      sellerFeeBasisPoints: 500
+    });
+    const { mint: nftMint2 } = await mintNFT({
+      payer: seller,
+      connection: provider.connection,
+      creators: [{ address: creator2.publicKey, verified: false, share: 100 }],
+      sellerFeeBasisPoints: 500
+    });
+    await utils.sleep(1);
+
+    const bundleId = new BN(1);
+    const auctionHouse = utils.findAuctionHouse(authority.publicKey, NATIVE_MINT);
+    const listingAccount = utils.findBundleListing(auctionHouse, seller.publicKey, bundleId);
+
+    const tx1 = await listBundle(program, seller, authority.publicKey, NATIVE_MINT, bundleId, [nftMint1, nftMint2], new BN(1_000_000_000), [7_000, 3_000], null);
+    const listing = await program.account.bundleListingAccount.fetch(listingAccount);
+    assert(listing.count == 2, "Bundle items not recorded.");
+
+    const creator1Before = await provider.connection.getBalance(creator1.publicKey);
+    const creator2Before = await provider.connection.getBalance(creator2.publicKey);
+
+    const tx2 = await buyBundle(program, buyer, seller.publicKey, authority.publicKey, NATIVE_MINT, bundleId, [nftMint1, nftMint2], [[creator1.publicKey], [creator2.publicKey]]);
+
+    const listingInfo = await provider.connection.getAccountInfo(listingAccount);
+    assert(listingInfo == null, "Bundle listing not closed.");
+
+    // Royalties follow the 70/30 allocation
+    const creator1After = await provider.connection.getBalance(creator1.publicKey);
+    const creator2After = await provider.connection.getBalance(creator2.publicKey);
+    assert(creator1After - creator1Before == 35_000_000, "First item royalty not pro-rated.");
+    assert(creator2After - creator2Before == 15_000_000, "Second item royalty not pro-rated.");
+
+    // Check NFT ownership
+    for (const nftMint of [nftMint1, nftMint2]) {
+      const buyerNftAccount = await getAssociatedTokenAddress(nftMint, buyer.publicKey);
+      const buyerNftInfo = await getAccount(provider.connection, buyerNftAccount);
+      assert(buyerNftInfo.amount == BigInt(1), "NFT not sent to buyer.");
+    }
+  });
+
+  it('Unlist bundle hands every item back to seller', async () => {
+
+    const seller = anchor.web3.Keypair.generate();
+    await utils.safeAirdrop(provider.connection, seller.publicKey, 1);
+
+    const { mint: nftMint1, tokenAccount: tokenAccount1 } = await mintNFT({
+      payer: seller,
+      connection: provider.connection
+    });
+    const { mint: nftMint2, tokenAccount: tokenAccount2 } = await mintNFT({
+      payer: seller,
+      connection: provider.connection
+    });
+    await utils.sleep(1);
+
+    const bundleId = new BN(2);
+    const tx1 = await listBundle(program, seller, authority.publicKey, NATIVE_MINT, bundleId, [nftMint1, nftMint2], new BN(1_000), [5_000, 5_000], null);
+
+    // Another treasury-owned account of the same mint is not the listed item
+    const auctionHouse = utils.findAuctionHouse(authority.publicKey, NATIVE_MINT);
+    const treasury = utils.findAuctionHouseTreasury(auctionHouse);
+    const decoyAccount = await createAccount(provider.connection, seller, nftMint1, treasury, anchor.web3.Keypair.generate());
+    const tx2 = await unlistBundle(program, seller, authority.publicKey, NATIVE_MINT, bundleId, [nftMint1, nftMint2], [decoyAccount, tokenAccount2]);
+    assert(tx2 == undefined, "Unlisted from an account the item was not listed from.");
+
+    const tx3 = await unlistBundle(program, seller, authority.publicKey, NATIVE_MINT, bundleId, [nftMint1, nftMint2]);
+
+    for (const tokenAccount of [tokenAccount1, tokenAccount2]) {
+      const nftInfo = await getAccount(provider.connection, tokenAccount);
+      assert(nftInfo.owner.equals(seller.publicKey), "NFT not handed back to seller.");
+    }
+  });
+});
@@ -0,0 +1,117 @@
+import * as anchor from '@project-serum/anchor';
+import { Program } from '@project-serum/anchor';
+import { createAccount, getAccount, getAssociatedTokenAddress, NATIVE_MINT } from '@solana/spl-token';
+
+import AUTHORITY_WALLET from './keypairs/authority.json';
+
+import { Marketplace } from "../target/types/marketplace";
+
+import * as utils from './utils';
+import { BN } from 'bn.js';
+import { assert } from 'chai';
+import { mintNFT } from './actions/mintNft';
+import { makeSwapOffer } from './actions/makeSwapOffer';
+import { cancelSwapOffer } from './actions/cancelSwapOffer';
+import { fillSwapOffer } from './actions/fillSwapOffer';
+
+describe("swap", () => {
+
+  // Configure the client to use the local cluster.
+  const provider = anchor.AnchorProvider.env();
+  anchor.setProvider(provider);
+
+  const program = anchor.workspace.Marketplace as Program<Marketplace>;
+
+  // Create test keypairs
+  const authority = anchor.web3.Keypair.fromSecretKey(Buffer.from(AUTHORITY_WALLET));
+
+  it('Swap NFT plus sweetener for requested NFT', async () => {
+
+    const maker = anchor.web3.Keypair.generate();
+    await utils.safeAirdrop(provider.connection, maker.publicKey, 2);
+
+    const taker = anchor.web3.Keypair.generate();
+    await utils.safeAirdrop(provider.connection, taker.publicKey, 1);
+
+    const { mint: offeredMint } = await mintNFT({
+      payer: maker,
+      connection: provider.connection
+    });
+    const { mint: requestedMint } = await mintNFT({
+      payer: taker,
+      connection: provider.connection
+    });
+    await utils.sleep(1);
+
+    const swapId = new BN(1);
+    const sweetener = 1_000_000;
+    const auctionHouse = utils.findAuctionHouse(authority.publicKey, NATIVE_MINT);
+    const treasury = utils.findAuctionHouseTreasury(auctionHouse);
+    const escrowState = utils.findEscrowState(maker.publicKey, auctionHouse);
+    const offerAccount = utils.findSwapOffer(auctionHouse, maker.publicKey, swapId);
+
+    const tx1 = await makeSwapOffer(program, maker, authority.publicKey, NATIVE_MINT, swapId, [offeredMint], { mints: { mints: [requestedMint] } }, new BN(sweetener), null);
+    let state = await program.account.buyerEscrowState.fetch(escrowState);
+    assert(state.committed.toNumber() == sweetener, "Sweetener not committed.");
+
+    // Wrong NFT is refused
+    const { mint: otherMint } = await mintNFT({
+      payer: taker,
+      connection: provider.connection
+    });
+    await utils.sleep(1);
+    const tx2 = await fillSwapOffer(program, taker, maker.publicKey, authority.publicKey, NATIVE_MINT, swapId, [offeredMint], [otherMint]);
+    assert(await provider.connection.getAccountInfo(offerAccount) != null, "Wrong NFT accepted.");
+
+    const treasuryBalanceBefore = await provider.connection.getBalance(treasury);
+
+    const tx3 = await fillSwapOffer(program, taker, maker.publicKey, authority.publicKey, NATIVE_MINT, swapId, [offeredMint], [requestedMint]);
+    assert(await provider.connection.getAccountInfo(offerAccount) == null, "Filled swap offer not closed.");
+
+    // Fee charged on the sweetener only
+    const treasuryBalanceAfter = await provider.connection.getBalance(treasury);
+    assert(treasuryBalanceAfter - treasuryBalanceBefore == sweetener * utils.MARKETPLACE_FEE_FACTOR, "Marketplace fee not matched.");
+
+    state = await program.account.buyerEscrowState.fetch(escrowState);
+    assert(state.committed.toNumber() == 0, "Sweetener still committed.");
+
+    // Check NFT ownership
+    const takerNftInfo = await getAccount(provider.connection, await getAssociatedTokenAddress(offeredMint, taker.publicKey));
+    assert(takerNftInfo.amount == BigInt(1), "Offered NFT not sent to taker.");
+    const makerNftInfo = await getAccount(provider.connection, await getAssociatedTokenAddress(requestedMint, maker.publicKey));
+    assert(makerNftInfo.amount == BigInt(1), "Requested NFT not sent to maker.");
+  });
+
+  it('Cancel swap offer hands NFTs back and releases sweetener', async () => {
+
+    const maker = anchor.web3.Keypair.generate();
+    await utils.safeAirdrop(provider.connection, maker.publicKey, 1);
+
+    const { mint: offeredMint, tokenAccount } = await mintNFT({
+      payer: maker,
+      connection: provider.connection
+    });
+    await utils.sleep(1);
+
+    const swapId = new BN(2);
+    const collection = anchor.web3.Keypair.generate().publicKey;
+    const auctionHouse = utils.findAuctionHouse(authority.publicKey, NATIVE_MINT);
+    const escrowState = utils.findEscrowState(maker.publicKey, auctionHouse);
+
+    const tx1 = await makeSwapOffer(program, maker, authority.publicKey, NATIVE_MINT, swapId, [offeredMint], { collection: { collection, quantity: 1 } }, new BN(1_000), null);
+
+    // Another treasury-owned account of the same mint is not the offered item
+    const treasury = utils.findAuctionHouseTreasury(auctionHouse);
+    const decoyAccount = await createAccount(provider.connection, maker, offeredMint, treasury, anchor.web3.Keypair.generate());
+    const tx2 = await cancelSwapOffer(program, maker, authority.publicKey, NATIVE_MINT, swapId, [offeredMint], [decoyAccount]);
+    assert(tx2 == undefined, "Cancelled from an account the item was not offered from.");
+
+    const tx3 = await cancelSwapOffer(program, maker, authority.publicKey, NATIVE_MINT, swapId, [offeredMint]);
+
+    const state = await program.account.buyerEscrowState.fetch(escrowState);
+    assert(state.committed.toNumber() == 0, "Sweetener not released.");
+
+    const nftInfo = await getAccount(provider.connection, tokenAccount);
+    assert(nftInfo.owner.equals(maker.publicKey), "NFT not handed back to maker.");
+  });
+});
@@ -0,0 +1,140 @@
+import * as anchor from '@project-serum/anchor';
+import { Program } from '@project-serum/anchor';
+import { createAccount, getAccount, getAssociatedTokenAddress, NATIVE_MINT, transfer } from '@solana/spl-token';
+
+import AUTHORITY_WALLET from './keypairs/authority.json';
+
+import { Marketplace } from "../target/types/marketplace";
+
+import * as utils from './utils';
+import { BN } from 'bn.js';
+import { assert } from 'chai';
+import { mintNFT } from './actions/mintNft';
+import { listDelegated } from './actions/listDelegated';
+import { unlisting } from './actions/unlisting';
+import { buy } from './actions/buy';
+import { executeSale } from './actions/executeSale';
+import { forceUnlisting } from './actions/forceUnlisting';
+
+describe("delegate-listing", () => {
+
+  // Configure the client to use the local cluster.
+  const provider = anchor.AnchorProvider.env();
+  anchor.setProvider(provider);
+
+  const program = anchor.workspace.Marketplace as Program<Marketplace>;
+
+  // Create test keypairs
+  const authority = anchor.web3.Keypair.fromSecretKey(Buffer.from(AUTHORITY_WALLET));
+
+  const auctionHouse = utils.findAuctionHouse(authority.publicKey, NATIVE_MINT);
+
+  it('Frozen delegated listing stays in seller wallet until sold', async () => {
+
+    const seller = anchor.web3.Keypair.generate();
+    await utils.safeAirdrop(provider.connection, seller.publicKey, 1);
+
+    const buyer = anchor.web3.Keypair.generate();
+    await utils.safeAirdrop(provider.connection, buyer.publicKey, 1);
+
+    const { mint: nftMint, tokenAccount } = await mintNFT({
+      payer: seller,
+      connection: provider.connection
+    });
+    await utils.sleep(1);
+
+    const price = 100_000;
+    const auctionHouse = utils.findAuctionHouse(authority.publicKey, NATIVE_MINT);
+    const treasury = utils.findAuctionHouseTreasury(auctionHouse);
+
+    const tx1 = await listDelegated(program, seller, authority.publicKey, NATIVE_MINT, nftMint, new BN(price), null, true);
+
+    // Seller keeps the NFT, delegated to the treasury and frozen
+    let nftInfo = await getAccount(provider.connection, tokenAccount);
+    assert(nftInfo.owner.equals(seller.publicKey) && nftInfo.amount == BigInt(1), "NFT left seller wallet.");
+    assert(nftInfo.delegate.equals(treasury) && nftInfo.isFrozen, "NFT not delegated and frozen.");
+
+    const tx2 = await buy(program, buyer, authority.publicKey, NATIVE_MINT, nftMint, new BN(price), null);
+    const tx3 = await executeSale(program, buyer, seller.publicKey, authority.publicKey, NATIVE_MINT, nftMint, []);
+
+    // Check NFT ownership
+    const buyerNftAccount = await getAssociatedTokenAddress(nftMint, buyer.publicKey);
+    const buyerNftInfo = await getAccount(provider.connection, buyerNftAccount);
+    assert(buyerNftInfo.amount == BigInt(1), "NFT not sent to buyer.");
+
+    nftInfo = await getAccount(provider.connection, tokenAccount);
+    assert(nftInfo.amount == BigInt(0) && !nftInfo.isFrozen && nftInfo.delegate == null, "Seller account not released.");
+  });
+
+  it('Unlisting delegated listing revokes the treasury delegate', async () => {
+
+    const seller = anchor.web3.Keypair.generate();
+    await utils.safeAirdrop(provider.connection, seller.publicKey, 1);
+
+    const { mint: nftMint, tokenAccount } = await mintNFT({
+      payer: seller,
+      connection: provider.connection
+    });
+    await utils.sleep(1);
+
+    const tx1 = await listDelegated(program, seller, authority.publicKey, NATIVE_MINT, nftMint, new BN(100_000), null, false);
+    let nftInfo = await getAccount(provider.connection, tokenAccount);
+    assert(nftInfo.delegate != null && !nftInfo.isFrozen, "NFT not delegated.");
+
+    const tx2 = await unlisting(program, seller, authority.publicKey, NATIVE_MINT, nftMint);
+
+    nftInfo = await getAccount(provider.connection, tokenAccount);
+    assert(nftInfo.delegate == null && nftInfo.amount == BigInt(1), "Delegate not revoked.");
+
+    const listingInfo = await provider.connection.getAccountInfo(utils.findListingAccount(auctionHouse, nftMint));
+    assert(listingInfo == null, "Listing not closed.");
+  });
+
+  it('Delegated listing requires the NFT in the seller ATA', async () => {
+
+    const seller = anchor.web3.Keypair.generate();
+    await utils.safeAirdrop(provider.connection, seller.publicKey, 1);
+
+    const { mint: nftMint, tokenAccount } = await mintNFT({
+      payer: seller,
+      connection: provider.connection
+    });
+    await utils.sleep(1);
+
+    // Move the NFT to another account owned by the seller
+    const otherAccount = await createAccount(provider.connection, seller, nftMint, seller.publicKey, anchor.web3.Keypair.generate());
+    await transfer(provider.connection, seller, tokenAccount, otherAccount, seller, 1);
+
+    const tx1 = await listDelegated(program, seller, authority.publicKey, NATIVE_MINT, nftMint, new BN(100_000), null, false, otherAccount);
+    assert(tx1 == undefined, "Listed from a non-ATA token account.");
+  });
+
+  it('Force unlisting a frozen delegated listing thaws the NFT', async () => {
+
+    const seller = anchor.web3.Keypair.generate();
+    await utils.safeAirdrop(provider.connection, seller.publicKey, 1);
+
+    const buyer = anchor.web3.Keypair.generate();
+    await utils.safeAirdrop(provider.connection, buyer.publicKey, 1);
+
+    const { mint: nftMint, tokenAccount } = await mintNFT({
+      payer: seller,
+      connection: provider.connection
+    });
+    await utils.sleep(1);
+
+    const price = 100_000;
+    const tx1 = await listDelegated(program, seller, authority.publicKey, NATIVE_MINT, nftMint, new BN(price), null, true);
+    const tx2 = await forceUnlisting(program, authority, seller.publicKey, NATIVE_MINT, nftMint, tokenAccount);
+
+    // Seller can move the NFT again, the leftover approval is only revocable by the seller
+    const nftInfo = await getAccount(provider.connection, tokenAccount);
+    assert(!nftInfo.isFrozen && nftInfo.amount == BigInt(1), "NFT not thawed.");
+    assert(await provider.connection.getAccountInfo(utils.findListingAccount(auctionHouse, nftMint)) == null, "Listing not closed.");
+
+    // Without a listing the treasury approval cannot be used to sell the NFT
+    const tx3 = await buy(program, buyer, authority.publicKey, NATIVE_MINT, nftMint, new BN(price), null);
+    const tx4 = await executeSale(program, buyer, seller.publicKey, authority.publicKey, NATIVE_MINT, nftMint, []);
+    assert(tx4 == undefined, "Sold without a listing.");
+  });
+});
@@ -0,0 +1,87 @@
+import * as anchor from '@project-serum/anchor';
+import { Program } from '@project-serum/anchor';
+import { getAccount, getAssociatedTokenAddress, NATIVE_MINT } from '@solana/spl-token';
+
+import AUTHORITY_WALLET from './keypairs/authority.json';
+
+import { Marketplace } from "../target/types/marketplace";
+
+import * as utils from './utils';
+import { BN } from 'bn.js';
+import { assert } from 'chai';
+import { mintNFT } from './actions/mintNft';
+import { listVaulted } from './actions/listVaulted';
+import { unlistVaulted } from './actions/unlistVaulted';
+import { buy } from './actions/buy';
+import { executeSale } from './actions/executeSale';
+
+describe("vault-listing", () => {
+
+  // Configure the client to use the local cluster.
+  const provider = anchor.AnchorProvider.env();
+  anchor.setProvider(provider);
+
+  const program = anchor.workspace.Marketplace as Program<Marketplace>;
+
+  // Create test keypairs
+  const authority = anchor.web3.Keypair.fromSecretKey(Buffer.from(AUTHORITY_WALLET));
+
+  const auctionHouse = utils.findAuctionHouse(authority.publicKey, NATIVE_MINT);
+
+  it('Sell vault listing and close the vault', async () => {
+
+    const seller = anchor.web3.Keypair.generate();
+    await utils.safeAirdrop(provider.connection, seller.publicKey, 1);
+
+    const buyer = anchor.web3.Keypair.generate();
+    await utils.safeAirdrop(provider.connection, buyer.publicKey, 1);
+
+    const { mint: nftMint, tokenAccount } = await mintNFT({
+      payer: seller,
+      connection: provider.connection
+    });
+    await utils.sleep(1);
+
+    const price = 100_000;
+    const vault = utils.findVault(utils.findListingAccount(auctionHouse, nftMint));
+
+    const tx1 = await listVaulted(program, seller, authority.publicKey, NATIVE_MINT, nftMint, new BN(price), null);
+
+    // Seller ATA keeps its owner, the NFT sits in the vault
+    const sellerNftInfo = await getAccount(provider.connection, tokenAccount);
+    assert(sellerNftInfo.owner.equals(seller.publicKey) && sellerNftInfo.amount == BigInt(0), "Seller ATA changed owner.");
+    const vaultInfo = await getAccount(provider.connection, vault);
+    assert(vaultInfo.amount == BigInt(1), "NFT not moved into vault.");
+
+    const tx2 = await buy(program, buyer, authority.publicKey, NATIVE_MINT, nftMint, new BN(price), null);
+    const tx3 = await executeSale(program, buyer, seller.publicKey, authority.publicKey, NATIVE_MINT, nftMint, [], null, null, null, vault);
+
+    const buyerNftInfo = await getAccount(provider.connection, await getAssociatedTokenAddress(nftMint, buyer.publicKey));
+    assert(buyerNftInfo.amount == BigInt(1), "NFT not sent to buyer.");
+
+    assert(await provider.connection.getAccountInfo(vault) == null, "Vault not closed.");
+  });
+
+  it('Unlist vault listing returns NFT to seller', async () => {
+
+    const seller = anchor.web3.Keypair.generate();
+    await utils.safeAirdrop(provider.connection, seller.publicKey, 1);
+
+    const { mint: nftMint, tokenAccount } = await mintNFT({
+      payer: seller,
+      connection: provider.connection
+    });
+    await utils.sleep(1);
+
+    const listingAccount = utils.findListingAccount(auctionHouse, nftMint);
+
+    const tx1 = await listVaulted(program, seller, authority.publicKey, NATIVE_MINT, nftMint, new BN(100_000), null);
+    const tx2 = await unlistVaulted(program, seller, authority.publicKey, NATIVE_MINT, nftMint);
+
+    const nftInfo = await getAccount(provider.connection, tokenAccount);
+    assert(nftInfo.amount == BigInt(1), "NFT not returned to seller.");
+
+    assert(await provider.connection.getAccountInfo(listingAccount) == null, "Listing not closed.");
+    assert(await provider.connection.getAccountInfo(utils.findVault(listingAccount)) == null, "Vault not closed.");
+  });
+});
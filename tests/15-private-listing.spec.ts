@@ -0,0 +1,110 @@
+import * as anchor from '@project-serum/anchor';
+import { Program } from '@project-serum/anchor';
+import { getAccount, getAssociatedTokenAddress, NATIVE_MINT } from '@solana/spl-token';
+
+import AUTHORITY_WALLET from './keypairs/authority.json';
+
+import { Marketplace } from "../target/types/marketplace";
+
+import * as utils from './utils';
+import { BN } from 'bn.js';
+import { assert } from 'chai';
+import { mintNFT } from './actions/mintNft';
+import { listing } from './actions/listing';
+import { buy } from './actions/buy';
+import { executeSale } from './actions/executeSale';
+import { listVaulted } from './actions/listVaulted';
+import { listDelegated } from './actions/listDelegated';
+
+describe("private-listing", () => {
+
+  // Configure the client to use the local cluster.
+  const provider = anchor.AnchorProvider.env();
+  anchor.setProvider(provider);
+
+  const program = anchor.workspace.Marketplace as Program<Marketplace>;
+
+  // Create test keypairs
+  const authority = anchor.web3.Keypair.fromSecretKey(Buffer.from(AUTHORITY_WALLET));
+
+  const auctionHouse = utils.findAuctionHouse(authority.publicKey, NATIVE_MINT);
+
+  it('Reserved listing only sells to its reserved buyer', async () => {
+
+    const seller = anchor.web3.Keypair.generate();
+    await utils.safeAirdrop(provider.connection, seller.publicKey, 1);
+
+    const buyer = anchor.web3.Keypair.generate();
+    await utils.safeAirdrop(provider.connection, buyer.publicKey, 1);
+
+    const outsider = anchor.web3.Keypair.generate();
+    await utils.safeAirdrop(provider.connection, outsider.publicKey, 1);
+
+    const { mint: nftMint } = await mintNFT({
+      payer: seller,
+      connection: provider.connection
+    });
+    await utils.sleep(1);
+
+    const price = 100_000;
+    const listingAccount = utils.findListingAccount(auctionHouse, nftMint);
+
+    const tx1 = await listing(program, seller, authority.publicKey, NATIVE_MINT, nftMint, new BN(price), null, buyer.publicKey);
+    const listingInfo = await program.account.listingAccount.fetch(listingAccount);
+    assert(listingInfo.reservedBuyer.equals(buyer.publicKey), "Reserved buyer not stored.");
+
+    // Anyone else is refused
+    const tx2 = await buy(program, outsider, authority.publicKey, NATIVE_MINT, nftMint, new BN(price), null);
+    const tx3 = await executeSale(program, outsider, seller.publicKey, authority.publicKey, NATIVE_MINT, nftMint, []);
+    assert(tx3 == undefined, "Sale to another buyer succeeded.");
+    assert(await provider.connection.getAccountInfo(listingAccount) != null, "Listing closed.");
+
+    // Reserved buyer fills the listing
+    const tx4 = await buy(program, buyer, authority.publicKey, NATIVE_MINT, nftMint, new BN(price), null);
+    const tx5 = await executeSale(program, buyer, seller.publicKey, authority.publicKey, NATIVE_MINT, nftMint, []);
+
+    const buyerNftInfo = await getAccount(provider.connection, await getAssociatedTokenAddress(nftMint, buyer.publicKey));
+    assert(buyerNftInfo.amount == BigInt(1), "NFT not sent to reserved buyer.");
+  });
+
+  it('Vault and delegated listings take the same sale terms', async () => {
+
+    const seller = anchor.web3.Keypair.generate();
+    await utils.safeAirdrop(provider.connection, seller.publicKey, 1);
+
+    const buyer = anchor.web3.Keypair.generate();
+    await utils.safeAirdrop(provider.connection, buyer.publicKey, 1);
+
+    const outsider = anchor.web3.Keypair.generate();
+    await utils.safeAirdrop(provider.connection, outsider.publicKey, 1);
+
+    const { mint: vaultedMint } = await mintNFT({
+      payer: seller,
+      connection: provider.connection
+    });
+    const { mint: delegatedMint } = await mintNFT({
+      payer: seller,
+      connection: provider.connection
+    });
+    await utils.sleep(1);
+
+    const price = 100_000;
+
+    // Reserved vault listing refuses anyone else
+    const vault = utils.findVault(utils.findListingAccount(auctionHouse, vaultedMint));
+    const tx1 = await listVaulted(program, seller, authority.publicKey, NATIVE_MINT, vaultedMint, new BN(price), null, buyer.publicKey);
+    const tx2 = await buy(program, outsider, authority.publicKey, NATIVE_MINT, vaultedMint, new BN(price), null);
+    const tx3 = await executeSale(program, outsider, seller.publicKey, authority.publicKey, NATIVE_MINT, vaultedMint, [], null, null, null, vault);
+    assert(tx3 == undefined, "Vault listing sold to another buyer.");
+
+    // Pre-staged delegated listing refuses to sell before its start time
+    const startTime = Math.floor(Date.now() / 1000) + 3600;
+    const tx4 = await listDelegated(program, seller, authority.publicKey, NATIVE_MINT, delegatedMint, new BN(price), null, false, null, null, new BN(startTime));
+    const listingInfo = await program.account.listingAccount.fetch(utils.findListingAccount(auctionHouse, delegatedMint));
+    assert(listingInfo.startTime.toNumber() == startTime, "Start time not stored.");
+
+    const tx5 = await buy(program, buyer, authority.publicKey, NATIVE_MINT, delegatedMint, new BN(price), null);
+    const tx6 = await executeSale(program, buyer, seller.publicKey, authority.publicKey, NATIVE_MINT, delegatedMint, []);
+    assert(tx6 == undefined, "Delegated listing sold before its start time.");
+  });
+});
@@ -0,0 +1,140 @@
+import * as anchor from '@project-serum/anchor';
+import { Program } from '@project-serum/anchor';
+import { getAccount, getAssociatedTokenAddress, NATIVE_MINT } from '@solana/spl-token';
+
+import AUTHORITY_WALLET from './keypairs/authority.json';
+
+import { Marketplace } from "../target/types/marketplace";
+
+import * as utils from './utils';
+import { BN } from 'bn.js';
+import { assert } from 'chai';
+import { mintNFT } from './actions/mintNft';
+import { listing } from './actions/listing';
+import { buy } from './actions/buy';
+import { executeSale } from './actions/executeSale';
+
+describe("sale-window", () => {
+
+  // Configure the client to use the local cluster.
+  const provider = anchor.AnchorProvider.env();
+  anchor.setProvider(provider);
+
+  const program = anchor.workspace.Marketplace as Program<Marketplace>;
+
+  // Create test keypairs
+  const authority = anchor.web3.Keypair.fromSecretKey(Buffer.from(AUTHORITY_WALLET));
+
+  const auctionHouse = utils.findAuctionHouse(authority.publicKey, NATIVE_MINT);
+
+  it('Pre-staged listing becomes purchasable at its start time', async () => {
+
+    const seller = anchor.web3.Keypair.generate();
+    await utils.safeAirdrop(provider.connection, seller.publicKey, 1);
+
+    const buyer = anchor.web3.Keypair.generate();
+    await utils.safeAirdrop(provider.connection, buyer.publicKey, 1);
+
+    const { mint: nftMint } = await mintNFT({
+      payer: seller,
+      connection: provider.connection
+    });
+    await utils.sleep(1);
+
+    const price = 100_000;
+    const listingAccount = utils.findListingAccount(auctionHouse, nftMint);
+    const startTime = Math.floor(Date.now() / 1000) + 5;
+
+    const tx1 = await listing(program, seller, authority.publicKey, NATIVE_MINT, nftMint, new BN(price), null, null, new BN(startTime));
+    const listingInfo = await program.account.listingAccount.fetch(listingAccount);
+    assert(listingInfo.startTime.eq(new BN(startTime)), "Start time not stored.");
+
+    const tx2 = await buy(program, buyer, authority.publicKey, NATIVE_MINT, nftMint, new BN(price), null);
+
+    // Sale before the start time is refused
+    const tx3 = await executeSale(program, buyer, seller.publicKey, authority.publicKey, NATIVE_MINT, nftMint, []);
+    assert(tx3 == undefined, "Sale before start time succeeded.");
+
+    await utils.sleep(6);
+
+    const tx4 = await executeSale(program, buyer, seller.publicKey, authority.publicKey, NATIVE_MINT, nftMint, []);
+    const buyerNftInfo = await getAccount(provider.connection, await getAssociatedTokenAddress(nftMint, buyer.publicKey));
+    assert(buyerNftInfo.amount == BigInt(1), "NFT not sent to buyer.");
+  });
+
+  it('Offer with a future start time cannot be filled yet', async () => {
+
+    const seller = anchor.web3.Keypair.generate();
+    await utils.safeAirdrop(provider.connection, seller.publicKey, 1);
+
+    const buyer = anchor.web3.Keypair.generate();
+    await utils.safeAirdrop(provider.connection, buyer.publicKey, 1);
+
+    const { mint: nftMint } = await mintNFT({
+      payer: seller,
+      connection: provider.connection
+    });
+    await utils.sleep(1);
+
+    const price = 100_000;
+    const startTime = Math.floor(Date.now() / 1000) + 60;
+
+    const tx1 = await listing(program, seller, authority.publicKey, NATIVE_MINT, nftMint, new BN(price), null);
+    const tx2 = await buy(program, buyer, authority.publicKey, NATIVE_MINT, nftMint, new BN(price), null, true, new BN(startTime));
+    const tx3 = await executeSale(program, buyer, seller.publicKey, authority.publicKey, NATIVE_MINT, nftMint, []);
+    assert(tx3 == undefined, "Offer filled before start time.");
+  });
+
+  it('Live listing with an expiry sells before it expires', async () => {
+
+    const seller = anchor.web3.Keypair.generate();
+    await utils.safeAirdrop(provider.connection, seller.publicKey, 1);
+
+    const buyer = anchor.web3.Keypair.generate();
+    await utils.safeAirdrop(provider.connection, buyer.publicKey, 1);
+
+    const { mint: nftMint } = await mintNFT({
+      payer: seller,
+      connection: provider.connection
+    });
+    await utils.sleep(1);
+
+    const price = 100_000;
+    const expiry = Math.floor(Date.now() / 1000) + 3600;
+
+    const tx1 = await listing(program, seller, authority.publicKey, NATIVE_MINT, nftMint, new BN(price), new BN(expiry));
+    const tx2 = await buy(program, buyer, authority.publicKey, NATIVE_MINT, nftMint, new BN(price), new BN(expiry));
+    const tx3 = await executeSale(program, buyer, seller.publicKey, authority.publicKey, NATIVE_MINT, nftMint, []);
+    assert(tx3 != undefined, "Live listing not sold.");
+
+    const buyerNftInfo = await getAccount(provider.connection, await getAssociatedTokenAddress(nftMint, buyer.publicKey));
+    assert(buyerNftInfo.amount == BigInt(1), "NFT not sent to buyer.");
+  });
+
+  it('Expired listing and offer cannot be settled', async () => {
+
+    const seller = anchor.web3.Keypair.generate();
+    await utils.safeAirdrop(provider.connection, seller.publicKey, 1);
+
+    const buyer = anchor.web3.Keypair.generate();
+    await utils.safeAirdrop(provider.connection, buyer.publicKey, 1);
+
+    const { mint: nftMint } = await mintNFT({
+      payer: seller,
+      connection: provider.connection
+    });
+    await utils.sleep(1);
+
+    const price = 100_000;
+    const expiry = Math.floor(Date.now() / 1000) + 3;
+
+    const tx1 = await listing(program, seller, authority.publicKey, NATIVE_MINT, nftMint, new BN(price), new BN(expiry));
+    const tx2 = await buy(program, buyer, authority.publicKey, NATIVE_MINT, nftMint, new BN(price), new BN(expiry));
+
+    await utils.sleep(5);
+
+    const tx3 = await executeSale(program, buyer, seller.publicKey, authority.publicKey, NATIVE_MINT, nftMint, []);
+    assert(tx3 == undefined, "Expired listing sold.");
+    assert(await provider.connection.getAccountInfo(utils.findListingAccount(auctionHouse, nftMint)) != null, "Listing closed.");
+  });
+});
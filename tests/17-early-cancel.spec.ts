@@ -0,0 +1,115 @@
+import * as anchor from '@project-serum/anchor';
+import { Program } from '@project-serum/anchor';
+import { getAccount, NATIVE_MINT } from '@solana/spl-token';
+
+import PAYER_WALLET from './keypairs/payer.json';
+import AUTHORITY_WALLET from './keypairs/authority.json';
+import TREASURY_WALLET from './keypairs/treasury.json';
+
+import { Marketplace } from "../target/types/marketplace";
+
+import * as utils from './utils';
+import { BN } from 'bn.js';
+import { assert } from 'chai';
+import { mintNFT } from './actions/mintNft';
+import { listing } from './actions/listing';
+import { unlisting } from './actions/unlisting';
+import { buy } from './actions/buy';
+import { cancelBuy } from './actions/cancelBuy';
+import { forceUnlisting } from './actions/forceUnlisting';
+import { forceCancelBuy } from './actions/forceCancelBuy';
+import { updateAuctionHouse } from './actions/updateAuctionHouse';
+
+describe("early-cancel", () => {
+
+  // Configure the client to use the local cluster.
+  const provider = anchor.AnchorProvider.env();
+  anchor.setProvider(provider);
+
+  const program = anchor.workspace.Marketplace as Program<Marketplace>;
+
+  // Create test keypairs
+  const payer = anchor.web3.Keypair.fromSecretKey(Buffer.from(PAYER_WALLET));
+  const authority = anchor.web3.Keypair.fromSecretKey(Buffer.from(AUTHORITY_WALLET));
+  const treasuryWithdraw = anchor.web3.Keypair.fromSecretKey(Buffer.from(TREASURY_WALLET));
+
+  const auctionHouse = utils.findAuctionHouse(authority.publicKey, NATIVE_MINT);
+  const treasury = utils.findAuctionHouseTreasury(auctionHouse);
+
+  it('Live listing and offer are cancelled early for a fee after the cooldown', async () => {
+
+    // 5% cancellation fee, 3 seconds cooldown
+    await updateAuctionHouse(program, payer, authority, authority.publicKey, authority.publicKey, NATIVE_MINT, treasuryWithdraw.publicKey, null, null, null, null, null, null, 500, new BN(3));
+
+    const seller = anchor.web3.Keypair.generate();
+    await utils.safeAirdrop(provider.connection, seller.publicKey, 1);
+
+    const buyer = anchor.web3.Keypair.generate();
+    await utils.safeAirdrop(provider.connection, buyer.publicKey, 1);
+
+    const { mint: nftMint, tokenAccount } = await mintNFT({
+      payer: seller,
+      connection: provider.connection
+    });
+    await utils.sleep(1);
+
+    const price = 1_000_000;
+    const expiry = Math.floor(Date.now() / 1000) + 30 * 24 * 3600;
+    const fee = price * 500 / 10000;
+
+    const tx1 = await listing(program, seller, authority.publicKey, NATIVE_MINT, nftMint, new BN(price), new BN(expiry));
+    const tx2 = await buy(program, buyer, authority.publicKey, NATIVE_MINT, nftMint, new BN(price), new BN(expiry));
+
+    // Cancelling inside the cooldown is refused
+    const tx3 = await unlisting(program, seller, authority.publicKey, NATIVE_MINT, nftMint);
+    assert(tx3 == undefined, "Listing cancelled during cooldown.");
+
+    await utils.sleep(4);
+
+    const treasuryBefore = await provider.connection.getBalance(treasury);
+    const tx4 = await unlisting(program, seller, authority.publicKey, NATIVE_MINT, nftMint);
+    const nftInfo = await getAccount(provider.connection, tokenAccount);
+    assert(nftInfo.owner.equals(seller.publicKey), "NFT not returned to seller.");
+
+    const tx5 = await cancelBuy(program, buyer, authority.publicKey, NATIVE_MINT, nftMint);
+    const treasuryAfter = await provider.connection.getBalance(treasury);
+    assert(treasuryAfter - treasuryBefore == 2 * fee, "Cancellation fees not paid to treasury.");
+
+    const escrowState = await program.account.buyerEscrowState.fetch(utils.findEscrowState(buyer.publicKey, auctionHouse));
+    assert(escrowState.committed.toNumber() == 0 && escrowState.free.toNumber() == price - fee, "Offer fee not taken from escrow.");
+
+    // Disable cancellation fee and cooldown for other tests
+    await updateAuctionHouse(program, payer, authority, authority.publicKey, authority.publicKey, NATIVE_MINT, treasuryWithdraw.publicKey, null, null, null, null, null, null, 0, new BN(0));
+  });
+
+  it('Auction house authority force-cancels a listing and an offer', async () => {
+
+    const seller = anchor.web3.Keypair.generate();
+    await utils.safeAirdrop(provider.connection, seller.publicKey, 1);
+
+    const buyer = anchor.web3.Keypair.generate();
+    await utils.safeAirdrop(provider.connection, buyer.publicKey, 1);
+
+    const { mint: nftMint, tokenAccount } = await mintNFT({
+      payer: seller,
+      connection: provider.connection
+    });
+    await utils.sleep(1);
+
+    const price = 100_000;
+
+    const tx1 = await listing(program, seller, authority.publicKey, NATIVE_MINT, nftMint, new BN(price), null);
+    const tx2 = await buy(program, buyer, authority.publicKey, NATIVE_MINT, nftMint, new BN(price), null);
+
+    const tx3 = await forceUnlisting(program, authority, seller.publicKey, NATIVE_MINT, nftMint);
+    const nftInfo = await getAccount(provider.connection, tokenAccount);
+    assert(nftInfo.owner.equals(seller.publicKey), "NFT not returned to seller.");
+    assert(await provider.connection.getAccountInfo(utils.findListingAccount(auctionHouse, nftMint)) == null, "Listing not closed.");
+
+    const tx4 = await forceCancelBuy(program, authority, buyer.publicKey, NATIVE_MINT, nftMint);
+    assert(await provider.connection.getAccountInfo(utils.findOfferAccount(auctionHouse, buyer.publicKey, nftMint)) == null, "Offer not closed.");
+
+    const escrowState = await program.account.buyerEscrowState.fetch(utils.findEscrowState(buyer.publicKey, auctionHouse));
+    assert(escrowState.committed.toNumber() == 0, "Offer escrow not released.");
+  });
+});
@@ -0,0 +1,89 @@
+import * as anchor from '@project-serum/anchor';
+import { Program } from '@project-serum/anchor';
+import { getAccount, NATIVE_MINT } from '@solana/spl-token';
+
+import AUTHORITY_WALLET from './keypairs/authority.json';
+import BT_MINT_WALLET from './keypairs/bt-mint.json';
+
+import { Marketplace } from "../target/types/marketplace";
+
+import * as utils from './utils';
+import { BN } from 'bn.js';
+import { assert } from 'chai';
+import { mintNFT } from './actions/mintNft';
+import { listing } from './actions/listing';
+import { unlisting } from './actions/unlisting';
+
+describe("listing-security", () => {
+
+  // Configure the client to use the local cluster.
+  const provider = anchor.AnchorProvider.env();
+  anchor.setProvider(provider);
+
+  const program = anchor.workspace.Marketplace as Program<Marketplace>;
+
+  // Create test keypairs
+  const authority = anchor.web3.Keypair.fromSecretKey(Buffer.from(AUTHORITY_WALLET));
+
+  const auctionHouse = utils.findAuctionHouse(authority.publicKey, NATIVE_MINT);
+  const btMint = anchor.web3.Keypair.fromSecretKey(Buffer.from(BT_MINT_WALLET)).publicKey;
+
+  const price = 100_000;
+
+  let seller: anchor.web3.Keypair;
+  let attacker: anchor.web3.Keypair;
+  let nftMint: anchor.web3.PublicKey;
+  let sellerNftAccount: anchor.web3.PublicKey;
+  let listingAccount: anchor.web3.PublicKey;
+
+  beforeEach(async () => {
+    seller = anchor.web3.Keypair.generate();
+    await utils.safeAirdrop(provider.connection, seller.publicKey, 1);
+
+    attacker = anchor.web3.Keypair.generate();
+    await utils.safeAirdrop(provider.connection, attacker.publicKey, 1);
+
+    const nft = await mintNFT({
+      payer: seller,
+      connection: provider.connection
+    });
+    nftMint = nft.mint;
+    sellerNftAccount = nft.tokenAccount;
+    listingAccount = utils.findListingAccount(auctionHouse, nftMint);
+    await utils.sleep(1);
+
+    await listing(program, seller, authority.publicKey, NATIVE_MINT, nftMint, new BN(price), null);
+  });
+
+  it('Attacker cannot relist someone else listing', async () => {
+    const tx1 = await listing(program, attacker, authority.publicKey, NATIVE_MINT, nftMint, new BN(1), null, null, null, sellerNftAccount);
+    assert(tx1 == undefined, "Attacker relisted the listing.");
+
+    const listingInfo = await program.account.listingAccount.fetch(listingAccount);
+    assert(listingInfo.owner.equals(seller.publicKey) && listingInfo.price.toNumber() == price, "Listing hijacked.");
+  });
+
+  it('Attacker cannot unlist someone else listing', async () => {
+    // Attacker ATA does not exist, target the seller one directly
+    const tx1 = await unlisting(program, attacker, authority.publicKey, NATIVE_MINT, nftMint, sellerNftAccount);
+    assert(tx1 == undefined, "Attacker unlisted the listing.");
+
+    const nftInfo = await getAccount(provider.connection, sellerNftAccount);
+    const treasury = utils.findAuctionHouseTreasury(utils.findAuctionHouse(authority.publicKey, NATIVE_MINT));
+    assert(nftInfo.owner.equals(treasury), "NFT authority handed to attacker.");
+    assert(await provider.connection.getAccountInfo(listingAccount) != null, "Listing closed.");
+  });
+
+  it('Seller cannot relist or unlist through another auction house', async () => {
+    const tx1 = await listing(program, seller, authority.publicKey, btMint, nftMint, new BN(1), null);
+    assert(tx1 == undefined, "Listing moved to another auction house.");
+
+    const tx2 = await unlisting(program, seller, authority.publicKey, btMint, nftMint);
+    assert(tx2 == undefined, "Listing unlisted through another auction house.");
+
+    // The owning auction house still works
+    const tx3 = await unlisting(program, seller, authority.publicKey, NATIVE_MINT, nftMint);
+    const nftInfo = await getAccount(provider.connection, sellerNftAccount);
+    assert(nftInfo.owner.equals(seller.publicKey), "NFT not returned to seller.");
+  });
+});
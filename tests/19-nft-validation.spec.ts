@@ -0,0 +1,75 @@
+import * as anchor from '@project-serum/anchor';
+import { Program } from '@project-serum/anchor';
+import { createMint, getAccount, getOrCreateAssociatedTokenAccount, mintTo, NATIVE_MINT } from '@solana/spl-token';
+
+import AUTHORITY_WALLET from './keypairs/authority.json';
+
+import { Marketplace } from "../target/types/marketplace";
+
+import * as utils from './utils';
+import { BN } from 'bn.js';
+import { assert } from 'chai';
+import { mintNFT, mintSFT } from './actions/mintNft';
+import { listing } from './actions/listing';
+
+describe("nft-validation", () => {
+
+  // Configure the client to use the local cluster.
+  const provider = anchor.AnchorProvider.env();
+  anchor.setProvider(provider);
+
+  const program = anchor.workspace.Marketplace as Program<Marketplace>;
+
+  // Create test keypairs
+  const authority = anchor.web3.Keypair.fromSecretKey(Buffer.from(AUTHORITY_WALLET));
+
+  const auctionHouse = utils.findAuctionHouse(authority.publicKey, NATIVE_MINT);
+
+  it('Genuine NFT is listed', async () => {
+
+    const seller = anchor.web3.Keypair.generate();
+    await utils.safeAirdrop(provider.connection, seller.publicKey, 1);
+
+    const { mint: nftMint } = await mintNFT({
+      payer: seller,
+      connection: provider.connection
+    });
+    await utils.sleep(1);
+
+    const tx = await listing(program, seller, authority.publicKey, NATIVE_MINT, nftMint, new BN(100_000), null);
+    assert(tx != undefined, "NFT not listed.");
+  });
+
+  it('Mint without metadata is rejected', async () => {
+
+    const seller = anchor.web3.Keypair.generate();
+    await utils.safeAirdrop(provider.connection, seller.publicKey, 1);
+
+    const mint = await createMint(provider.connection, seller, seller.publicKey, null, 0);
+    const tokenAccount = await getOrCreateAssociatedTokenAccount(provider.connection, seller, mint, seller.publicKey);
+    await mintTo(provider.connection, seller, mint, tokenAccount.address, seller, 1);
+
+    const tx = await listing(program, seller, authority.publicKey, NATIVE_MINT, mint, new BN(100_000), null);
+    assert(tx == undefined, "Mint without metadata listed.");
+
+    const nftInfo = await getAccount(provider.connection, tokenAccount.address);
+    assert(nftInfo.owner.equals(seller.publicKey), "Token account handed to treasury.");
+    assert(await provider.connection.getAccountInfo(utils.findListingAccount(auctionHouse, mint)) == null, "Listing created.");
+  });
+
+  it('Semi-fungible mint with a supply above one is rejected', async () => {
+
+    const seller = anchor.web3.Keypair.generate();
+    await utils.safeAirdrop(provider.connection, seller.publicKey, 1);
+
+    const { mint } = await mintSFT({
+      payer: seller,
+      connection: provider.connection,
+      amount: 10,
+    });
+    await utils.sleep(1);
+
+    const tx = await listing(program, seller, authority.publicKey, NATIVE_MINT, mint, new BN(100_000), null);
+    assert(tx == undefined, "Semi-fungible mint listed.");
+  });
+});
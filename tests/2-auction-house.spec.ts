@@ -12,6 +12,7 @@ import { Marketplace } from "../target/types/marketplace";
 import { createAuctionHouse } from './actions/createAuctionHouse';
 import { updateAuctionHouse } from './actions/updateAuctionHouse';
 import { withdrawFromTreasury } from './actions/withdrawFromTreasury';
+import { migrateAuctionHouse } from './actions/migrateAuctionHouse';
 
 import * as utils from './utils';
 import { assert } from 'chai';
@@ -62,4 +63,19 @@ describe("auction-house", () => {
     assert(afterBalance > beforeBalance, "Balance not updated.");
   });
 
+  it('Migrate auction house keeps a current layout untouched', async () => {
+    const ah = utils.findAuctionHouse(authority.publicKey, NATIVE_MINT);
+    const before = await provider.connection.getAccountInfo(ah);
+
+    // Only the authority can migrate
+    const tx1 = await migrateAuctionHouse(program, payer, authority.publicKey, NATIVE_MINT);
+    assert(tx1 == undefined, "Migration by non-authority succeeded.");
+
+    const tx2 = await migrateAuctionHouse(program, authority, authority.publicKey, NATIVE_MINT);
+    assert(tx2 != undefined, "Migration failed.");
+
+    const after = await provider.connection.getAccountInfo(ah);
+    assert(after.data.equals(before.data), "Auction house data changed.");
+  });
+
 });
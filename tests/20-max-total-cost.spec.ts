@@ -0,0 +1,80 @@
+import * as anchor from '@project-serum/anchor';
+import { Program } from '@project-serum/anchor';
+import { getAccount, getAssociatedTokenAddress, NATIVE_MINT } from '@solana/spl-token';
+
+import AUTHORITY_WALLET from './keypairs/authority.json';
+
+import { Marketplace } from "../target/types/marketplace";
+
+import * as utils from './utils';
+import { BN } from 'bn.js';
+import { assert } from 'chai';
+import { mintNFT } from './actions/mintNft';
+import { listing } from './actions/listing';
+import { buy } from './actions/buy';
+import { executeSale } from './actions/executeSale';
+
+describe("max-total-cost", () => {
+
+  // Configure the client to use the local cluster.
+  const provider = anchor.AnchorProvider.env();
+  anchor.setProvider(provider);
+
+  const program = anchor.workspace.Marketplace as Program<Marketplace>;
+
+  // Create test keypairs
+  const authority = anchor.web3.Keypair.fromSecretKey(Buffer.from(AUTHORITY_WALLET));
+
+  const auctionHouse = utils.findAuctionHouse(authority.publicKey, NATIVE_MINT);
+
+  it('Sale is refused when the total cost exceeds the buyer maximum', async () => {
+
+    const seller = anchor.web3.Keypair.generate();
+    await utils.safeAirdrop(provider.connection, seller.publicKey, 1);
+
+    const buyer = anchor.web3.Keypair.generate();
+    await utils.safeAirdrop(provider.connection, buyer.publicKey, 1);
+
+    const { mint: nftMint } = await mintNFT({
+      payer: seller,
+      connection: provider.connection
+    });
+    await utils.sleep(1);
+
+    const price = 100_000;
+
+    const tx1 = await listing(program, seller, authority.publicKey, NATIVE_MINT, nftMint, new BN(price), null);
+    const tx2 = await buy(program, buyer, authority.publicKey, NATIVE_MINT, nftMint, new BN(price), null);
+
+    // The total cost is at least the price
+    const tx3 = await executeSale(program, buyer, seller.publicKey, authority.publicKey, NATIVE_MINT, nftMint, [], null, null, null, null, new BN(price - 1));
+    assert(tx3 == undefined, "Sale above the buyer maximum succeeded.");
+    assert(await provider.connection.getAccountInfo(utils.findListingAccount(auctionHouse, nftMint)) != null, "Listing closed.");
+  });
+
+  it('Sale settles when the total cost is within the buyer maximum', async () => {
+
+    const seller = anchor.web3.Keypair.generate();
+    await utils.safeAirdrop(provider.connection, seller.publicKey, 1);
+
+    const buyer = anchor.web3.Keypair.generate();
+    await utils.safeAirdrop(provider.connection, buyer.publicKey, 1);
+
+    const { mint: nftMint } = await mintNFT({
+      payer: seller,
+      connection: provider.connection
+    });
+    await utils.sleep(1);
+
+    const price = 100_000;
+    const maxTotalCost = price + 10_000_000;
+
+    const tx1 = await listing(program, seller, authority.publicKey, NATIVE_MINT, nftMint, new BN(price), null);
+    const tx2 = await buy(program, buyer, authority.publicKey, NATIVE_MINT, nftMint, new BN(price), null);
+    const tx3 = await executeSale(program, buyer, seller.publicKey, authority.publicKey, NATIVE_MINT, nftMint, [], null, null, null, null, new BN(maxTotalCost));
+    assert(tx3 != undefined, "Sale within the buyer maximum failed.");
+
+    const buyerNftInfo = await getAccount(provider.connection, await getAssociatedTokenAddress(nftMint, buyer.publicKey));
+    assert(buyerNftInfo.amount == BigInt(1), "NFT not sent to buyer.");
+  });
+});
@@ -0,0 +1,90 @@
+import * as anchor from '@project-serum/anchor';
+import { Program } from '@project-serum/anchor';
+import { getAccount, getAssociatedTokenAddress, NATIVE_MINT } from '@solana/spl-token';
+
+import PAYER_WALLET from './keypairs/payer.json';
+import AUTHORITY_WALLET from './keypairs/authority.json';
+import TREASURY_WALLET from './keypairs/treasury.json';
+
+import { Marketplace } from "../target/types/marketplace";
+
+import * as utils from './utils';
+import { BN } from 'bn.js';
+import { assert } from 'chai';
+import { mintNFT } from './actions/mintNft';
+import { listing } from './actions/listing';
+import { buy } from './actions/buy';
+import { executeSale } from './actions/executeSale';
+import { updateAuctionHouse } from './actions/updateAuctionHouse';
+
+describe("min-net-proceeds", () => {
+
+  // Configure the client to use the local cluster.
+  const provider = anchor.AnchorProvider.env();
+  anchor.setProvider(provider);
+
+  const program = anchor.workspace.Marketplace as Program<Marketplace>;
+
+  // Create test keypairs
+  const payer = anchor.web3.Keypair.fromSecretKey(Buffer.from(PAYER_WALLET));
+  const authority = anchor.web3.Keypair.fromSecretKey(Buffer.from(AUTHORITY_WALLET));
+  const treasuryWithdraw = anchor.web3.Keypair.fromSecretKey(Buffer.from(TREASURY_WALLET));
+
+  const auctionHouse = utils.findAuctionHouse(authority.publicKey, NATIVE_MINT);
+
+  it('Sale is refused when a fee raise cuts the seller payout below the guard', async () => {
+
+    const seller = anchor.web3.Keypair.generate();
+    await utils.safeAirdrop(provider.connection, seller.publicKey, 1);
+
+    const buyer = anchor.web3.Keypair.generate();
+    await utils.safeAirdrop(provider.connection, buyer.publicKey, 1);
+
+    const { mint: nftMint } = await mintNFT({
+      payer: seller,
+      connection: provider.connection
+    });
+    await utils.sleep(1);
+
+    const price = 100_000;
+    const listingAccount = utils.findListingAccount(auctionHouse, nftMint);
+
+    const tx1 = await listing(program, seller, authority.publicKey, NATIVE_MINT, nftMint, new BN(price), null, null, null, null, new BN(price / 2));
+    const listingInfo = await program.account.listingAccount.fetch(listingAccount);
+    assert(listingInfo.minNetProceeds.eq(new BN(price / 2)), "Minimum net proceeds not stored.");
+
+    const tx2 = await buy(program, buyer, authority.publicKey, NATIVE_MINT, nftMint, new BN(price), null);
+
+    // Authority raises the seller fee to 100% while the listing is live
+    const { sellerFeeBasisPoints } = await program.account.auctionHouse.fetch(auctionHouse);
+    await updateAuctionHouse(program, payer, authority, authority.publicKey, authority.publicKey, NATIVE_MINT, treasuryWithdraw.publicKey, 10000, null, null);
+
+    const tx3 = await executeSale(program, buyer, seller.publicKey, authority.publicKey, NATIVE_MINT, nftMint, []);
+    assert(tx3 == undefined, "Sale below the seller minimum succeeded.");
+    assert(await provider.connection.getAccountInfo(listingAccount) != null, "Listing closed.");
+
+    // Restore the seller fee for other tests
+    await updateAuctionHouse(program, payer, authority, authority.publicKey, authority.publicKey, NATIVE_MINT, treasuryWithdraw.publicKey, sellerFeeBasisPoints, null, null);
+
+    const tx4 = await executeSale(program, buyer, seller.publicKey, authority.publicKey, NATIVE_MINT, nftMint, []);
+    const buyerNftInfo = await getAccount(provider.connection, await getAssociatedTokenAddress(nftMint, buyer.publicKey));
+    assert(buyerNftInfo.amount == BigInt(1), "NFT not sent to buyer.");
+  });
+
+  it('Minimum net proceeds above the price are rejected', async () => {
+
+    const seller = anchor.web3.Keypair.generate();
+    await utils.safeAirdrop(provider.connection, seller.publicKey, 1);
+
+    const { mint: nftMint } = await mintNFT({
+      payer: seller,
+      connection: provider.connection
+    });
+    await utils.sleep(1);
+
+    const price = 100_000;
+
+    const tx1 = await listing(program, seller, authority.publicKey, NATIVE_MINT, nftMint, new BN(price), null, null, null, null, new BN(price + 1));
+    assert(tx1 == undefined, "Listing with unreachable minimum succeeded.");
+  });
+});
@@ -11,6 +11,7 @@ import { Marketplace } from "../target/types/marketplace";
 
 import * as utils from './utils';
 import { deposit } from './actions/deposit';
+import { withdraw } from './actions/withdraw';
 import { BN } from 'bn.js';
 import { assert } from 'chai';
 
@@ -61,4 +62,45 @@ describe("deposit", () => {
     const balance = await provider.connection.getTokenAccountBalance(escrowWallet);
     assert(balance.value.amount == amount.toString(), "Deposit balance not matched.");
   });
+
+  it('Withdraw SOL from escrow wallet', async () => {
+    const user = anchor.web3.Keypair.generate();
+    await utils.safeAirdrop(provider.connection, user.publicKey, 1);
+
+    const amount = 1_000;
+
+    const auctionHouse = utils.findAuctionHouse(authority.publicKey, NATIVE_MINT);
+    const escrowWallet = utils.findEscrowWallet(user.publicKey, auctionHouse);
+
+    const tx1 = await deposit(program, user, authority.publicKey, NATIVE_MINT, new BN(amount));
+    const balance_before = await provider.connection.getBalance(escrowWallet);
+
+    const tx2 = await withdraw(program, user, authority.publicKey, NATIVE_MINT, new BN(amount));
+
+    const balance_after = await provider.connection.getBalance(escrowWallet);
+    assert(balance_before - balance_after == amount, "Withdraw balance not matched.");
+  });
+
+  it('Withdraw BT token from escrow wallet', async () => {
+    const user = anchor.web3.Keypair.generate();
+    await utils.safeAirdrop(provider.connection, user.publicKey, 1);
+
+    const amount = 1_000;
+
+    // Mint BT tokens to user
+    const userAta = (await getOrCreateAssociatedTokenAccount(provider.connection, payer, btMint, user.publicKey)).address;
+    await mintToChecked(provider.connection, payer, btMint, userAta, authority, amount, 9);
+
+    const auctionHouse = utils.findAuctionHouse(authority.publicKey, btMint);
+    const escrowWallet = utils.findEscrowWallet(user.publicKey, auctionHouse);
+
+    const tx1 = await deposit(program, user, authority.publicKey, btMint, new BN(amount));
+    const tx2 = await withdraw(program, user, authority.publicKey, btMint, new BN(amount));
+
+    const balance = await provider.connection.getTokenAccountBalance(escrowWallet);
+    assert(balance.value.amount == "0", "Withdraw balance not matched.");
+
+    const userBalance = await provider.connection.getTokenAccountBalance(userAta);
+    assert(userBalance.value.amount == amount.toString(), "Withdraw balance not matched.");
+  });
 });
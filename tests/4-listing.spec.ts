@@ -18,6 +18,7 @@ import { mintNFT } from './actions/mintNft';
 import { unlisting } from './actions/unlisting';
 import { buy } from './actions/buy';
 import { cancelBuy } from './actions/cancelBuy';
+import { withdraw } from './actions/withdraw';
 
 describe("lising", () => {
 
@@ -30,6 +31,8 @@ describe("lising", () => {
   // Create test keypairs
   const payer = anchor.web3.Keypair.fromSecretKey(Buffer.from(PAYER_WALLET));
   const authority = anchor.web3.Keypair.fromSecretKey(Buffer.from(AUTHORITY_WALLET));
+
+  const auctionHouse = utils.findAuctionHouse(authority.publicKey, NATIVE_MINT);
   const btMint = anchor.web3.Keypair.fromSecretKey(Buffer.from(BT_MINT_WALLET)).publicKey;
 
   it('Listing NFT with SOL without expiry', async () => {
@@ -84,7 +87,7 @@ describe("lising", () => {
     const tx2 = await listing(program, user, authority.publicKey, NATIVE_MINT, nftMint, new BN(8_000), new BN(time));
 
     // Check listing PDA
-    const listingAccount = await utils.findListingAccount(nftMint);
+    const listingAccount = await utils.findListingAccount(auctionHouse, nftMint);
     const listingAcc = await program.account.listingAccount.fetch(listingAccount);
     assert(listingAcc.price.toNumber() == 8_000, "Listing not update.");
   });
@@ -122,11 +125,101 @@ describe("lising", () => {
     const tx = await buy(program, user, authority.publicKey, NATIVE_MINT, nftMint, new BN(1_000), null);
 
     // Check offer PDA
-    const offerAccount = await utils.findOfferAccount(user.publicKey, nftMint);
+    const offerAccount = await utils.findOfferAccount(auctionHouse, user.publicKey, nftMint);
     const offerAcc = await program.account.offerAccount.fetch(offerAccount);
     assert(offerAcc.price.toNumber() == 1_000, "Offer not update.");
   });
 
+  it('Set offer without escrow funds', async () => {
+
+    const user = anchor.web3.Keypair.generate();
+    await utils.safeAirdrop(provider.connection, user.publicKey, 1);
+
+    const { tokenAccount, edition, mint: nftMint, metadata } = await mintNFT({
+      payer: user,
+      connection: provider.connection
+    });
+
+    const tx = await buy(program, user, authority.publicKey, NATIVE_MINT, nftMint, new BN(1_000), null, false);
+
+    // Offer PDA must not be created
+    const offerAccount = await utils.findOfferAccount(auctionHouse, user.publicKey, nftMint);
+    const offerInfo = await provider.connection.getAccountInfo(offerAccount);
+    assert(offerInfo == null, "Unfunded offer created.");
+  });
+
+  it('Set offer depositing escrow shortfall', async () => {
+
+    const user = anchor.web3.Keypair.generate();
+    await utils.safeAirdrop(provider.connection, user.publicKey, 1);
+
+    const { tokenAccount, edition, mint: nftMint, metadata } = await mintNFT({
+      payer: user,
+      connection: provider.connection
+    });
+
+    const tx1 = await deposit(program, user, authority.publicKey, NATIVE_MINT, new BN(400));
+    const tx2 = await buy(program, user, authority.publicKey, NATIVE_MINT, nftMint, new BN(1_000), null, true);
+
+    // Check escrow covers the offer
+    const auctionHouse = utils.findAuctionHouse(authority.publicKey, NATIVE_MINT);
+    const escrowWallet = utils.findEscrowWallet(user.publicKey, auctionHouse);
+    const rent = await provider.connection.getMinimumBalanceForRentExemption(0);
+    const balance = await provider.connection.getBalance(escrowWallet);
+    assert(balance - rent == 1_000, "Escrow shortfall not deposited.");
+
+    const offerAccount = await utils.findOfferAccount(auctionHouse, user.publicKey, nftMint);
+    const offerAcc = await program.account.offerAccount.fetch(offerAccount);
+    assert(offerAcc.escrowAmount.toNumber() == 1_000, "Offer escrow not reserved.");
+  });
+
+  it('Escrow ledger keeps offer funds committed', async () => {
+
+    const user = anchor.web3.Keypair.generate();
+    await utils.safeAirdrop(provider.connection, user.publicKey, 1);
+
+    const { tokenAccount, edition, mint: nftMint, metadata } = await mintNFT({
+      payer: user,
+      connection: provider.connection
+    });
+    const { mint: otherMint } = await mintNFT({
+      payer: user,
+      connection: provider.connection
+    });
+
+    const tx1 = await deposit(program, user, authority.publicKey, NATIVE_MINT, new BN(1_500));
+    const tx2 = await buy(program, user, authority.publicKey, NATIVE_MINT, nftMint, new BN(1_000), null, false);
+
+    const auctionHouse = utils.findAuctionHouse(authority.publicKey, NATIVE_MINT);
+    const escrowState = utils.findEscrowState(user.publicKey, auctionHouse);
+    let state = await program.account.buyerEscrowState.fetch(escrowState);
+    assert(state.totalDeposited.toNumber() == 1_500, "Deposit not recorded.");
+    assert(state.committed.toNumber() == 1_000, "Offer not committed.");
+    assert(state.free.toNumber() == 500, "Free balance not matched.");
+
+    // Committed funds cannot back a second offer
+    const tx3 = await buy(program, user, authority.publicKey, NATIVE_MINT, otherMint, new BN(1_000), null, false);
+    assert(tx3 == undefined, "Committed escrow reused.");
+
+    // Committed funds cannot be withdrawn
+    try {
+      await withdraw(program, user, authority.publicKey, NATIVE_MINT, new BN(1_000));
+      assert(false, "Committed escrow withdrawn.");
+    }
+    catch (ex) {
+      assert(ex.toString().includes("InsufficientEscrow"), "Unexpected error.");
+    }
+
+    // Cancelling the offer frees its commitment
+    const tx4 = await cancelBuy(program, user, authority.publicKey, NATIVE_MINT, nftMint);
+    state = await program.account.buyerEscrowState.fetch(escrowState);
+    assert(state.committed.toNumber() == 0 && state.free.toNumber() == 1_500, "Offer not released.");
+
+    const tx5 = await buy(program, user, authority.publicKey, NATIVE_MINT, otherMint, new BN(1_000), null, false);
+    state = await program.account.buyerEscrowState.fetch(escrowState);
+    assert(state.committed.toNumber() == 1_000, "Released escrow not reusable.");
+  });
+
   it('Cancel offer', async () => {
 
     const user = anchor.web3.Keypair.generate();
@@ -142,7 +235,7 @@ describe("lising", () => {
     const tx2 = await cancelBuy(program, user, authority.publicKey, NATIVE_MINT, nftMint);
 
     // Check offer PDA
-    const offerAccount = await utils.findOfferAccount(user.publicKey, nftMint);
+    const offerAccount = await utils.findOfferAccount(auctionHouse, user.publicKey, nftMint);
     try {
       const offerAcc = await program.account.offerAccount.fetch(offerAccount);
     }
@@ -20,6 +20,8 @@ import { unlisting } from './actions/unlisting';
 import { buy } from './actions/buy';
 import { cancelBuy } from './actions/cancelBuy';
 import { executeSale } from './actions/executeSale';
+import { listDutch } from './actions/listDutch';
+import { acceptOffer } from './actions/acceptOffer';
 
 describe("execute-sale", () => {
 
@@ -32,6 +34,8 @@ describe("execute-sale", () => {
   // Create test keypairs
   const payer = anchor.web3.Keypair.fromSecretKey(Buffer.from(PAYER_WALLET));
   const authority = anchor.web3.Keypair.fromSecretKey(Buffer.from(AUTHORITY_WALLET));
+
+  const auctionHouse = utils.findAuctionHouse(authority.publicKey, NATIVE_MINT);
   const btMint = anchor.web3.Keypair.fromSecretKey(Buffer.from(BT_MINT_WALLET)).publicKey;
   const discountCollection = anchor.web3.Keypair.fromSecretKey(Buffer.from(DISCOUNT_COLLECTION_WALLET)).publicKey;
 
@@ -476,4 +480,148 @@ describe("execute-sale", () => {
     const sellerNftInfo = await getAccount(provider.connection, sellerNftAccount);
     assert(sellerNftInfo.amount == BigInt(0), "NFT still in buyer.");
   });
+
+  it('Buy Dutch listing at current price', async () => {
+
+    const seller = anchor.web3.Keypair.generate();
+    await utils.safeAirdrop(provider.connection, seller.publicKey, 1);
+
+    const buyer = anchor.web3.Keypair.generate();
+    await utils.safeAirdrop(provider.connection, buyer.publicKey, 1);
+
+    const { tokenAccount, edition, mint: nftMint, metadata } = await mintNFT({
+      payer: seller,
+      connection: provider.connection
+    });
+
+    const startPrice = 0.2 * 1_000_000_000; // 0.2 SOL
+    const endPrice = 0.1 * 1_000_000_000; // 0.1 SOL
+
+    // Price halfway decayed since listing started 50 seconds ago over 100 seconds
+    const now = Math.floor(new Date().getTime() / 1000);
+    const tx1 = await listDutch(program, seller, authority.publicKey, NATIVE_MINT, nftMint, new BN(startPrice), new BN(endPrice), new BN(now - 50), new BN(100), null, null);
+
+    // Offer below current price is not enough
+    const tx2 = await buy(program, buyer, authority.publicKey, NATIVE_MINT, nftMint, new BN(endPrice), null);
+    const tx3 = await executeSale(program, buyer, seller.publicKey, authority.publicKey, NATIVE_MINT, nftMint, []);
+
+    const buyerNftAccount = await getAssociatedTokenAddress(nftMint, buyer.publicKey);
+    assert(await provider.connection.getAccountInfo(buyerNftAccount) == null, "NFT sold below current price.");
+
+    // Offer start price as the maximum acceptable price
+    const tx4 = await buy(program, buyer, authority.publicKey, NATIVE_MINT, nftMint, new BN(startPrice), null);
+
+    const ah = utils.findAuctionHouse(authority.publicKey, NATIVE_MINT);
+    const escrowState = utils.findEscrowState(buyer.publicKey, ah);
+    const deposited_before = (await program.account.buyerEscrowState.fetch(escrowState)).totalDeposited.toNumber();
+
+    const tx5 = await executeSale(program, buyer, seller.publicKey, authority.publicKey, NATIVE_MINT, nftMint, []);
+
+    // Buyer is charged the current price, not the offer price
+    const state = await program.account.buyerEscrowState.fetch(escrowState);
+    const charged = deposited_before - state.totalDeposited.toNumber();
+    assert(charged < startPrice && charged >= endPrice, "Current price not charged.");
+    assert(state.committed.toNumber() == 0 && state.free.toNumber() == startPrice - charged, "Offer leftover not freed.");
+
+    const buyerNftInfo = await getAccount(provider.connection, buyerNftAccount);
+    assert(buyerNftInfo.amount == BigInt(1), "NFT not sent to buyer.");
+  });
+
+  it('Stepped Dutch listing reaches its floor when steps do not divide the decay', async () => {
+
+    const seller = anchor.web3.Keypair.generate();
+    await utils.safeAirdrop(provider.connection, seller.publicKey, 1);
+
+    const buyer = anchor.web3.Keypair.generate();
+    await utils.safeAirdrop(provider.connection, buyer.publicKey, 1);
+
+    const { mint: nftMint } = await mintNFT({
+      payer: seller,
+      connection: provider.connection
+    });
+
+    const startPrice = 0.2 * 1_000_000_000; // 0.2 SOL
+    const endPrice = 0.1 * 1_000_000_000; // 0.1 SOL
+    const now = Math.floor(new Date().getTime() / 1000);
+
+    // Decay already over is rejected
+    const tx1 = await listDutch(program, seller, authority.publicKey, NATIVE_MINT, nftMint, new BN(startPrice), new BN(endPrice), new BN(now - 20), new BN(10), new BN(3), null);
+    assert(tx1 == undefined, "Listing with an ended decay succeeded.");
+
+    // 10 seconds decay in 3 seconds steps
+    const tx2 = await listDutch(program, seller, authority.publicKey, NATIVE_MINT, nftMint, new BN(startPrice), new BN(endPrice), new BN(now - 8), new BN(10), new BN(3), null);
+    const tx3 = await buy(program, buyer, authority.publicKey, NATIVE_MINT, nftMint, new BN(endPrice), null);
+
+    await utils.sleep(4);
+
+    const tx4 = await executeSale(program, buyer, seller.publicKey, authority.publicKey, NATIVE_MINT, nftMint, []);
+    const buyerNftInfo = await getAccount(provider.connection, await getAssociatedTokenAddress(nftMint, buyer.publicKey));
+    assert(buyerNftInfo.amount == BigInt(1), "Floor price not offered.");
+  });
+
+  it('Seller accepts offer below listing price', async () => {
+
+    const seller = anchor.web3.Keypair.generate();
+    await utils.safeAirdrop(provider.connection, seller.publicKey, 1);
+
+    const buyer = anchor.web3.Keypair.generate();
+    await utils.safeAirdrop(provider.connection, buyer.publicKey, 1);
+
+    const { tokenAccount, edition, mint: nftMint, metadata } = await mintNFT({
+      payer: seller,
+      connection: provider.connection
+    });
+
+    let price = 0.2 * 1_000_000_000; // O.2 SOL
+
+    // Listing above the offer price
+    const tx1 = await listing(program, seller, authority.publicKey, NATIVE_MINT, nftMint, new BN(0.5 * 1_000_000_000), null);
+    const tx2 = await buy(program, buyer, authority.publicKey, NATIVE_MINT, nftMint, new BN(price), null);
+
+    const ah = await utils.findAuctionHouse(authority.publicKey, NATIVE_MINT);
+    const treasury = await utils.findAuctionHouseTreasury(ah);
+    const treasury_balance_before = await provider.connection.getBalance(treasury);
+
+    const tx3 = await acceptOffer(program, seller, buyer.publicKey, authority.publicKey, NATIVE_MINT, nftMint);
+
+    // Check marketplace fee is charged on the offer price
+    const treasury_balance_after = await provider.connection.getBalance(treasury);
+    const marketplace_fee = price * utils.MARKETPLACE_FEE_FACTOR;
+    assert(marketplace_fee == (treasury_balance_after - treasury_balance_before), "Marketplace fee not matched.");
+
+    // Check NFT ownership and listing closed
+    const buyerNftAccount = await getAssociatedTokenAddress(nftMint, buyer.publicKey);
+    const buyerNftInfo = await getAccount(provider.connection, buyerNftAccount);
+    assert(buyerNftInfo.amount == BigInt(1), "NFT not sent to buyer.");
+
+    const listingInfo = await provider.connection.getAccountInfo(utils.findListingAccount(auctionHouse, nftMint));
+    assert(listingInfo == null, "Listing not closed.");
+  });
+
+  it('Seller accepts offer for unlisted NFT', async () => {
+
+    const seller = anchor.web3.Keypair.generate();
+    await utils.safeAirdrop(provider.connection, seller.publicKey, 1);
+
+    const buyer = anchor.web3.Keypair.generate();
+    await utils.safeAirdrop(provider.connection, buyer.publicKey, 1);
+
+    const { tokenAccount, edition, mint: nftMint, metadata } = await mintNFT({
+      payer: seller,
+      connection: provider.connection
+    });
+
+    let price = 0.2 * 1_000_000_000; // O.2 SOL
+
+    const tx1 = await buy(program, buyer, authority.publicKey, NATIVE_MINT, nftMint, new BN(price), null);
+    const tx2 = await acceptOffer(program, seller, buyer.publicKey, authority.publicKey, NATIVE_MINT, nftMint);
+
+    // Check NFT ownership
+    const buyerNftAccount = await getAssociatedTokenAddress(nftMint, buyer.publicKey);
+    const buyerNftInfo = await getAccount(provider.connection, buyerNftAccount);
+    assert(buyerNftInfo.amount == BigInt(1), "NFT not sent to buyer.");
+
+    const escrowState = await program.account.buyerEscrowState.fetch(utils.findEscrowState(buyer.publicKey, utils.findAuctionHouse(authority.publicKey, NATIVE_MINT)));
+    assert(escrowState.committed.toNumber() == 0, "Offer reservation not settled.");
+  });
 });
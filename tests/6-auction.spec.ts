@@ -0,0 +1,294 @@
+import * as anchor from '@project-serum/anchor';
+import { Program } from '@project-serum/anchor';
+import { createAccount, getAccount, getAssociatedTokenAddress, NATIVE_MINT, transfer } from '@solana/spl-token';
+
+import PAYER_WALLET from './keypairs/payer.json';
+import AUTHORITY_WALLET from './keypairs/authority.json';
+import TREASURY_WALLET from './keypairs/treasury.json';
+
+import { Marketplace } from "../target/types/marketplace";
+
+import * as utils from './utils';
+import { BN } from 'bn.js';
+import { assert } from 'chai';
+import { mintNFT } from './actions/mintNft';
+import { createAuction } from './actions/createAuction';
+import { bid } from './actions/bid';
+import { settleAuction } from './actions/settleAuction';
+import { updateAuctionHouse } from './actions/updateAuctionHouse';
+import { createSealedAuction } from './actions/createSealedAuction';
+import { commitBid } from './actions/commitBid';
+import { revealBid } from './actions/revealBid';
+import { refundSealedBid } from './actions/refundSealedBid';
+
+describe("auction", () => {
+
+  // Configure the client to use the local cluster.
+  const provider = anchor.AnchorProvider.env();
+  anchor.setProvider(provider);
+
+  const program = anchor.workspace.Marketplace as Program<Marketplace>;
+
+  // Create test keypairs
+  const payer = anchor.web3.Keypair.fromSecretKey(Buffer.from(PAYER_WALLET));
+  const authority = anchor.web3.Keypair.fromSecretKey(Buffer.from(AUTHORITY_WALLET));
+  const treasuryWithdraw = anchor.web3.Keypair.fromSecretKey(Buffer.from(TREASURY_WALLET));
+
+  it('English auction refunds outbid bidder and settles to winner', async () => {
+
+    const seller = anchor.web3.Keypair.generate();
+    await utils.safeAirdrop(provider.connection, seller.publicKey, 1);
+
+    const bidder1 = anchor.web3.Keypair.generate();
+    await utils.safeAirdrop(provider.connection, bidder1.publicKey, 1);
+
+    const bidder2 = anchor.web3.Keypair.generate();
+    await utils.safeAirdrop(provider.connection, bidder2.publicKey, 1);
+
+    const { tokenAccount, edition, mint: nftMint, metadata } = await mintNFT({
+      payer: seller,
+      connection: provider.connection
+    });
+
+    const now = Math.floor(new Date().getTime() / 1000);
+    const tx1 = await createAuction(program, seller, authority.publicKey, NATIVE_MINT, nftMint, new BN(now - 1), new BN(now + 10), new BN(1_000), new BN(100));
+
+    const auctionHouse = utils.findAuctionHouse(authority.publicKey, NATIVE_MINT);
+    const escrowState1 = utils.findEscrowState(bidder1.publicKey, auctionHouse);
+
+    const tx2 = await bid(program, bidder1, authority.publicKey, NATIVE_MINT, nftMint, new BN(1_000));
+    let state1 = await program.account.buyerEscrowState.fetch(escrowState1);
+    assert(state1.committed.toNumber() == 1_000, "Bid not committed.");
+
+    // Bid below minimum increment is rejected
+    const tx3 = await bid(program, bidder2, authority.publicKey, NATIVE_MINT, nftMint, new BN(1_050));
+    const auctionAccount = utils.findAuctionAccount(auctionHouse, nftMint);
+    let auction = await program.account.auctionAccount.fetch(auctionAccount);
+    assert(auction.highestBidder.equals(bidder1.publicKey), "Low bid accepted.");
+
+    // Outbid releases the previous bidder reservation
+    const tx4 = await bid(program, bidder2, authority.publicKey, NATIVE_MINT, nftMint, new BN(1_100));
+    auction = await program.account.auctionAccount.fetch(auctionAccount);
+    assert(auction.highestBidder.equals(bidder2.publicKey) && auction.highestBid.toNumber() == 1_100, "Bid not updated.");
+
+    state1 = await program.account.buyerEscrowState.fetch(escrowState1);
+    assert(state1.committed.toNumber() == 0 && state1.free.toNumber() == 1_000, "Outbid bidder not released.");
+
+    await utils.sleep(11);
+
+    const tx5 = await settleAuction(program, seller, seller.publicKey, authority.publicKey, NATIVE_MINT, nftMint);
+
+    // Check NFT ownership
+    const winnerNftAccount = await getAssociatedTokenAddress(nftMint, bidder2.publicKey);
+    const winnerNftInfo = await getAccount(provider.connection, winnerNftAccount);
+    assert(winnerNftInfo.amount == BigInt(1), "NFT not sent to winner.");
+  });
+
+  it('Auction without bids returns NFT to seller', async () => {
+
+    const seller = anchor.web3.Keypair.generate();
+    await utils.safeAirdrop(provider.connection, seller.publicKey, 1);
+
+    const { tokenAccount, edition, mint: nftMint, metadata } = await mintNFT({
+      payer: seller,
+      connection: provider.connection
+    });
+
+    const now = Math.floor(new Date().getTime() / 1000);
+    const tx1 = await createAuction(program, seller, authority.publicKey, NATIVE_MINT, nftMint, new BN(now - 1), new BN(now + 2), new BN(1_000), new BN(100));
+
+    await utils.sleep(3);
+
+    const tx2 = await settleAuction(program, seller, seller.publicKey, authority.publicKey, NATIVE_MINT, nftMint);
+
+    // Check NFT ownership
+    const nftAccount = await getAssociatedTokenAddress(nftMint, seller.publicKey);
+    const nftAccInfo = await getAccount(provider.connection, nftAccount);
+    assert(nftAccInfo.owner.equals(seller.publicKey), "NFT not returned to seller.");
+  });
+
+  it('Auction from a non-ATA token account is rejected', async () => {
+
+    const seller = anchor.web3.Keypair.generate();
+    await utils.safeAirdrop(provider.connection, seller.publicKey, 1);
+
+    const { tokenAccount, mint: nftMint } = await mintNFT({
+      payer: seller,
+      connection: provider.connection
+    });
+
+    // Move the NFT to a token account that is not the seller ATA
+    const otherAccount = await createAccount(provider.connection, seller, nftMint, seller.publicKey, anchor.web3.Keypair.generate());
+    await transfer(provider.connection, seller, tokenAccount, otherAccount, seller, 1);
+
+    const now = Math.floor(new Date().getTime() / 1000);
+    const tx1 = await createAuction(program, seller, authority.publicKey, NATIVE_MINT, nftMint, new BN(now - 1), new BN(now + 10), new BN(1_000), new BN(100), otherAccount);
+    assert(tx1 == undefined, "Auction created from a non-ATA token account.");
+  });
+
+  it('Auction without a minimum bid increment is rejected', async () => {
+
+    const seller = anchor.web3.Keypair.generate();
+    await utils.safeAirdrop(provider.connection, seller.publicKey, 1);
+
+    const { mint: nftMint } = await mintNFT({
+      payer: seller,
+      connection: provider.connection
+    });
+
+    const now = Math.floor(new Date().getTime() / 1000);
+    const tx1 = await createAuction(program, seller, authority.publicKey, NATIVE_MINT, nftMint, new BN(now - 1), new BN(now + 10), new BN(1_000), new BN(0));
+    assert(tx1 == undefined, "Auction created without a minimum bid increment.");
+  });
+
+  it('Late bids extend auction up to the maximum extension', async () => {
+
+    // Extend by 10 seconds for bids in the last 30 seconds, at most 15 seconds in total
+    await updateAuctionHouse(program, payer, authority, authority.publicKey, authority.publicKey, NATIVE_MINT, treasuryWithdraw.publicKey, null, null, null, new BN(30), new BN(10), new BN(15));
+
+    const seller = anchor.web3.Keypair.generate();
+    await utils.safeAirdrop(provider.connection, seller.publicKey, 1);
+
+    const bidder = anchor.web3.Keypair.generate();
+    await utils.safeAirdrop(provider.connection, bidder.publicKey, 1);
+
+    const { tokenAccount, edition, mint: nftMint, metadata } = await mintNFT({
+      payer: seller,
+      connection: provider.connection
+    });
+
+    const now = Math.floor(new Date().getTime() / 1000);
+    const endTime = now + 20;
+    const tx1 = await createAuction(program, seller, authority.publicKey, NATIVE_MINT, nftMint, new BN(now - 1), new BN(endTime), new BN(1_000), new BN(100));
+
+    const auctionHouse = utils.findAuctionHouse(authority.publicKey, NATIVE_MINT);
+    const auctionAccount = utils.findAuctionAccount(auctionHouse, nftMint);
+
+    const tx2 = await bid(program, bidder, authority.publicKey, NATIVE_MINT, nftMint, new BN(1_000));
+    let auction = await program.account.auctionAccount.fetch(auctionAccount);
+    assert(auction.endTime.toNumber() == endTime + 10, "Auction not extended.");
+
+    const tx3 = await bid(program, bidder, authority.publicKey, NATIVE_MINT, nftMint, new BN(1_100));
+    auction = await program.account.auctionAccount.fetch(auctionAccount);
+    assert(auction.endTime.toNumber() == endTime + 15, "Auction extension not capped.");
+
+    // Disable extensions for other tests
+    await updateAuctionHouse(program, payer, authority, authority.publicKey, authority.publicKey, NATIVE_MINT, treasuryWithdraw.publicKey, null, null, null, new BN(0), new BN(0), new BN(0));
+  });
+
+  it('Sealed second price auction settles winner at second highest bid', async () => {
+
+    const seller = anchor.web3.Keypair.generate();
+    await utils.safeAirdrop(provider.connection, seller.publicKey, 1);
+
+    const bidder1 = anchor.web3.Keypair.generate();
+    await utils.safeAirdrop(provider.connection, bidder1.publicKey, 1);
+
+    const bidder2 = anchor.web3.Keypair.generate();
+    await utils.safeAirdrop(provider.connection, bidder2.publicKey, 1);
+
+    const { tokenAccount, edition, mint: nftMint, metadata } = await mintNFT({
+      payer: seller,
+      connection: provider.connection
+    });
+
+    const now = Math.floor(new Date().getTime() / 1000);
+    const tx1 = await createSealedAuction(program, seller, authority.publicKey, NATIVE_MINT, nftMint, new BN(now - 1), new BN(now + 8), new BN(now + 16), new BN(1_000), true);
+
+    const auctionHouse = utils.findAuctionHouse(authority.publicKey, NATIVE_MINT);
+    const auctionAccount = utils.findAuctionAccount(auctionHouse, nftMint);
+    const escrowState1 = utils.findEscrowState(bidder1.publicKey, auctionHouse);
+    const escrowState2 = utils.findEscrowState(bidder2.publicKey, auctionHouse);
+
+    // Commit hidden bids backed by deposits
+    const salt1 = anchor.web3.Keypair.generate().publicKey.toBuffer();
+    const salt2 = anchor.web3.Keypair.generate().publicKey.toBuffer();
+    const tx2 = await commitBid(program, bidder1, authority.publicKey, NATIVE_MINT, nftMint, utils.sealBid(new BN(1_500), salt1), new BN(2_000));
+    const tx3 = await commitBid(program, bidder2, authority.publicKey, NATIVE_MINT, nftMint, utils.sealBid(new BN(1_200), salt2), new BN(2_000));
+
+    let state1 = await program.account.buyerEscrowState.fetch(escrowState1);
+    assert(state1.committed.toNumber() == 2_000, "Deposit not committed.");
+
+    // Reveals are rejected during the bid phase
+    const tx4 = await revealBid(program, bidder1, authority.publicKey, NATIVE_MINT, nftMint, new BN(1_500), salt1);
+    let auction = await program.account.auctionAccount.fetch(auctionAccount);
+    assert(auction.highestBid.toNumber() == 0, "Early reveal accepted.");
+
+    await utils.sleep(9);
+
+    // Reveal with the wrong amount is rejected
+    const tx5 = await revealBid(program, bidder2, authority.publicKey, NATIVE_MINT, nftMint, new BN(1_900), salt2);
+    auction = await program.account.auctionAccount.fetch(auctionAccount);
+    assert(auction.highestBid.toNumber() == 0, "Invalid reveal accepted.");
+
+    const tx6 = await revealBid(program, bidder1, authority.publicKey, NATIVE_MINT, nftMint, new BN(1_500), salt1);
+    const tx7 = await revealBid(program, bidder2, authority.publicKey, NATIVE_MINT, nftMint, new BN(1_200), salt2);
+    auction = await program.account.auctionAccount.fetch(auctionAccount);
+    assert(auction.highestBidder.equals(bidder1.publicKey) && auction.secondBid.toNumber() == 1_200, "Bids not ranked.");
+
+    await utils.sleep(8);
+
+    const nonce = auction.nonce;
+    const tx8 = await settleAuction(program, seller, seller.publicKey, authority.publicKey, NATIVE_MINT, nftMint);
+    const tx9 = await refundSealedBid(program, bidder2, authority.publicKey, NATIVE_MINT, nftMint, nonce);
+
+    // Winner pays the second highest bid, the rest of the deposit is free again
+    state1 = await program.account.buyerEscrowState.fetch(escrowState1);
+    assert(state1.committed.toNumber() == 0 && state1.free.toNumber() == 800, "Winner not charged second price.");
+
+    const state2 = await program.account.buyerEscrowState.fetch(escrowState2);
+    assert(state2.committed.toNumber() == 0 && state2.totalDeposited.toNumber() == 0, "Losing bidder not refunded.");
+
+    // Check NFT ownership
+    const winnerNftAccount = await getAssociatedTokenAddress(nftMint, bidder1.publicKey);
+    const winnerNftInfo = await getAccount(provider.connection, winnerNftAccount);
+    assert(winnerNftInfo.amount == BigInt(1), "NFT not sent to winner.");
+  });
+
+  it('Relaunched sealed auction starts afresh and unrevealed bids are refunded', async () => {
+
+    const seller = anchor.web3.Keypair.generate();
+    await utils.safeAirdrop(provider.connection, seller.publicKey, 1);
+
+    const bidder = anchor.web3.Keypair.generate();
+    await utils.safeAirdrop(provider.connection, bidder.publicKey, 1);
+
+    const { mint: nftMint } = await mintNFT({
+      payer: seller,
+      connection: provider.connection
+    });
+
+    const auctionHouse = utils.findAuctionHouse(authority.publicKey, NATIVE_MINT);
+    const auctionAccount = utils.findAuctionAccount(auctionHouse, nftMint);
+    const treasury = utils.findAuctionHouseTreasury(auctionHouse);
+    const escrowState = utils.findEscrowState(bidder.publicKey, auctionHouse);
+
+    let now = Math.floor(new Date().getTime() / 1000);
+    const tx1 = await createSealedAuction(program, seller, authority.publicKey, NATIVE_MINT, nftMint, new BN(now - 1), new BN(now + 4), new BN(now + 8), new BN(1_000), false);
+    const nonce = (await program.account.auctionAccount.fetch(auctionAccount)).nonce;
+
+    // Commit and never reveal
+    const salt = anchor.web3.Keypair.generate().publicKey.toBuffer();
+    const tx2 = await commitBid(program, bidder, authority.publicKey, NATIVE_MINT, nftMint, utils.sealBid(new BN(1_500), salt), new BN(2_000));
+
+    await utils.sleep(10);
+
+    // Nothing revealed, the NFT goes back to the seller
+    const tx3 = await settleAuction(program, seller, seller.publicKey, authority.publicKey, NATIVE_MINT, nftMint);
+
+    // The unrefunded bid does not block commits to a relaunched auction
+    now = Math.floor(new Date().getTime() / 1000);
+    const tx4 = await createSealedAuction(program, seller, authority.publicKey, NATIVE_MINT, nftMint, new BN(now - 1), new BN(now + 30), new BN(now + 60), new BN(1_000), false);
+    const tx5 = await commitBid(program, bidder, authority.publicKey, NATIVE_MINT, nftMint, utils.sealBid(new BN(1_200), salt), new BN(1_500));
+    assert(tx5 != undefined, "Commit to relaunched auction failed.");
+
+    // The unrevealed bid deposit is refunded to the bidder like a losing one
+    const treasuryBefore = await provider.connection.getBalance(treasury);
+    const tx6 = await refundSealedBid(program, bidder, authority.publicKey, NATIVE_MINT, nftMint, nonce);
+    const treasuryAfter = await provider.connection.getBalance(treasury);
+    assert(tx6 != undefined && treasuryAfter == treasuryBefore, "Unrevealed deposit not refunded.");
+
+    const state = await program.account.buyerEscrowState.fetch(escrowState);
+    assert(state.committed.toNumber() == 1_500 && state.totalDeposited.toNumber() == 1_500, "Relaunched auction deposit not kept.");
+  });
+});
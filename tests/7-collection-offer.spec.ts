@@ -0,0 +1,112 @@
+import * as anchor from '@project-serum/anchor';
+import { Program } from '@project-serum/anchor';
+import { getAccount, getAssociatedTokenAddress, NATIVE_MINT } from '@solana/spl-token';
+
+import AUTHORITY_WALLET from './keypairs/authority.json';
+
+import { Marketplace } from "../target/types/marketplace";
+
+import * as utils from './utils';
+import { BN } from 'bn.js';
+import { assert } from 'chai';
+import { mintNFT } from './actions/mintNft';
+import { makeCollectionOffer } from './actions/makeCollectionOffer';
+import { cancelCollectionOffer } from './actions/cancelCollectionOffer';
+import { sellIntoCollectionOffer } from './actions/sellIntoCollectionOffer';
+
+describe("collection-offer", () => {
+
+  // Configure the client to use the local cluster.
+  const provider = anchor.AnchorProvider.env();
+  anchor.setProvider(provider);
+
+  const program = anchor.workspace.Marketplace as Program<Marketplace>;
+
+  // Create test keypairs
+  const authority = anchor.web3.Keypair.fromSecretKey(Buffer.from(AUTHORITY_WALLET));
+
+  it('Verified collection members sell into collection offer until filled', async () => {
+
+    const creator = anchor.web3.Keypair.generate();
+    await utils.safeAirdrop(provider.connection, creator.publicKey, 1);
+
+    const seller = anchor.web3.Keypair.generate();
+    await utils.safeAirdrop(provider.connection, seller.publicKey, 1);
+
+    const buyer = anchor.web3.Keypair.generate();
+    await utils.safeAirdrop(provider.connection, buyer.publicKey, 1);
+
+    // Collection NFT and two verified members
+    const { mint: collectionMint } = await mintNFT({
+      payer: creator,
+      connection: provider.connection,
+      maxSupply: 0
+    });
+    await utils.sleep(1);
+    const { mint: nftMint1 } = await mintNFT({
+      payer: seller,
+      connection: provider.connection,
+      collectionMint,
+      collectionAuthority: creator
+    });
+    const { mint: nftMint2 } = await mintNFT({
+      payer: seller,
+      connection: provider.connection,
+      collectionMint,
+      collectionAuthority: creator
+    });
+
+    // Member claiming the collection without verification
+    const { mint: unverifiedMint } = await mintNFT({
+      payer: seller,
+      connection: provider.connection,
+      collectionMint
+    });
+
+    const auctionHouse = utils.findAuctionHouse(authority.publicKey, NATIVE_MINT);
+    const escrowState = utils.findEscrowState(buyer.publicKey, auctionHouse);
+    const offerAccount = utils.findCollectionOffer(auctionHouse, collectionMint, buyer.publicKey);
+
+    const tx1 = await makeCollectionOffer(program, buyer, authority.publicKey, NATIVE_MINT, collectionMint, new BN(1_000), new BN(2), null);
+    let state = await program.account.buyerEscrowState.fetch(escrowState);
+    assert(state.committed.toNumber() == 2_000, "Collection offer not committed.");
+
+    const tx2 = await sellIntoCollectionOffer(program, seller, buyer.publicKey, authority.publicKey, NATIVE_MINT, collectionMint, unverifiedMint);
+    let offer = await program.account.collectionOfferAccount.fetch(offerAccount);
+    assert(offer.quantity.toNumber() == 2, "Unverified NFT accepted.");
+
+    const tx3 = await sellIntoCollectionOffer(program, seller, buyer.publicKey, authority.publicKey, NATIVE_MINT, collectionMint, nftMint1);
+    offer = await program.account.collectionOfferAccount.fetch(offerAccount);
+    assert(offer.quantity.toNumber() == 1, "Quantity not decreased.");
+
+    state = await program.account.buyerEscrowState.fetch(escrowState);
+    assert(state.committed.toNumber() == 1_000, "Filled unit still committed.");
+
+    const tx4 = await sellIntoCollectionOffer(program, seller, buyer.publicKey, authority.publicKey, NATIVE_MINT, collectionMint, nftMint2);
+    const offerInfo = await provider.connection.getAccountInfo(offerAccount);
+    assert(offerInfo == null, "Filled offer not closed.");
+
+    // Check NFT ownership
+    for (const nftMint of [nftMint1, nftMint2]) {
+      const buyerNftAccount = await getAssociatedTokenAddress(nftMint, buyer.publicKey);
+      const buyerNftInfo = await getAccount(provider.connection, buyerNftAccount);
+      assert(buyerNftInfo.amount == BigInt(1), "NFT not sent to buyer.");
+    }
+  });
+
+  it('Cancel collection offer releases remaining reservation', async () => {
+
+    const buyer = anchor.web3.Keypair.generate();
+    await utils.safeAirdrop(provider.connection, buyer.publicKey, 1);
+
+    const collectionMint = anchor.web3.Keypair.generate().publicKey;
+    const auctionHouse = utils.findAuctionHouse(authority.publicKey, NATIVE_MINT);
+    const escrowState = utils.findEscrowState(buyer.publicKey, auctionHouse);
+
+    const tx1 = await makeCollectionOffer(program, buyer, authority.publicKey, NATIVE_MINT, collectionMint, new BN(1_000), new BN(3), null);
+    const tx2 = await cancelCollectionOffer(program, buyer, authority.publicKey, NATIVE_MINT, collectionMint);
+
+    const state = await program.account.buyerEscrowState.fetch(escrowState);
+    assert(state.committed.toNumber() == 0 && state.free.toNumber() == 3_000, "Reservation not released.");
+  });
+});
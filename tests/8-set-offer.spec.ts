@@ -0,0 +1,75 @@
+import * as anchor from '@project-serum/anchor';
+import { Program } from '@project-serum/anchor';
+import { getAccount, getAssociatedTokenAddress, NATIVE_MINT } from '@solana/spl-token';
+
+import AUTHORITY_WALLET from './keypairs/authority.json';
+
+import { Marketplace } from "../target/types/marketplace";
+
+import * as utils from './utils';
+import { BN } from 'bn.js';
+import { assert } from 'chai';
+import { mintNFT } from './actions/mintNft';
+import { makeSetOffer } from './actions/makeSetOffer';
+import { cancelSetOffer } from './actions/cancelSetOffer';
+import { sellIntoSetOffer } from './actions/sellIntoSetOffer';
+
+describe("set-offer", () => {
+
+  // Configure the client to use the local cluster.
+  const provider = anchor.AnchorProvider.env();
+  anchor.setProvider(provider);
+
+  const program = anchor.workspace.Marketplace as Program<Marketplace>;
+
+  // Create test keypairs
+  const authority = anchor.web3.Keypair.fromSecretKey(Buffer.from(AUTHORITY_WALLET));
+
+  it('Mints proven in the merkle set sell into set offer', async () => {
+
+    const seller = anchor.web3.Keypair.generate();
+    await utils.safeAirdrop(provider.connection, seller.publicKey, 1);
+
+    const buyer = anchor.web3.Keypair.generate();
+    await utils.safeAirdrop(provider.connection, buyer.publicKey, 1);
+
+    const { mint: nftMint } = await mintNFT({
+      payer: seller,
+      connection: provider.connection
+    });
+    const { mint: outsiderMint } = await mintNFT({
+      payer: seller,
+      connection: provider.connection
+    });
+
+    // Hand-picked set of mints
+    const mints = [nftMint, anchor.web3.Keypair.generate().publicKey, anchor.web3.Keypair.generate().publicKey];
+    const { root, proof } = utils.buildMerkleTree(mints);
+
+    const auctionHouse = utils.findAuctionHouse(authority.publicKey, NATIVE_MINT);
+    const escrowState = utils.findEscrowState(buyer.publicKey, auctionHouse);
+    const offerAccount = utils.findSetOffer(auctionHouse, root, buyer.publicKey);
+
+    const tx1 = await makeSetOffer(program, buyer, authority.publicKey, NATIVE_MINT, root, new BN(1_000), new BN(2), null);
+    let state = await program.account.buyerEscrowState.fetch(escrowState);
+    assert(state.committed.toNumber() == 2_000, "Set offer not committed.");
+
+    // Mint outside the set is rejected, even with another mint's proof
+    const tx2 = await sellIntoSetOffer(program, seller, buyer.publicKey, authority.publicKey, NATIVE_MINT, root, outsiderMint, proof(nftMint));
+    let offer = await program.account.setOfferAccount.fetch(offerAccount);
+    assert(offer.quantity.toNumber() == 2, "Mint outside the set accepted.");
+
+    const tx3 = await sellIntoSetOffer(program, seller, buyer.publicKey, authority.publicKey, NATIVE_MINT, root, nftMint, proof(nftMint));
+    offer = await program.account.setOfferAccount.fetch(offerAccount);
+    assert(offer.quantity.toNumber() == 1, "Quantity not decreased.");
+
+    // Check NFT ownership
+    const buyerNftAccount = await getAssociatedTokenAddress(nftMint, buyer.publicKey);
+    const buyerNftInfo = await getAccount(provider.connection, buyerNftAccount);
+    assert(buyerNftInfo.amount == BigInt(1), "NFT not sent to buyer.");
+
+    const tx4 = await cancelSetOffer(program, buyer, authority.publicKey, NATIVE_MINT, root);
+    state = await program.account.buyerEscrowState.fetch(escrowState);
+    assert(state.committed.toNumber() == 0, "Reservation not released.");
+  });
+});
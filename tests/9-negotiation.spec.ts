@@ -0,0 +1,186 @@
+import * as anchor from '@project-serum/anchor';
+import { Program } from '@project-serum/anchor';
+import { getAccount, getAssociatedTokenAddress, getOrCreateAssociatedTokenAccount, NATIVE_MINT, transfer } from '@solana/spl-token';
+
+import AUTHORITY_WALLET from './keypairs/authority.json';
+
+import { Marketplace } from "../target/types/marketplace";
+
+import * as utils from './utils';
+import { BN } from 'bn.js';
+import { assert } from 'chai';
+import { mintNFT } from './actions/mintNft';
+import { listing } from './actions/listing';
+import { unlisting } from './actions/unlisting';
+import { buy } from './actions/buy';
+import { counterOffer } from './actions/counterOffer';
+import { acceptCounter } from './actions/acceptCounter';
+
+describe("negotiation", () => {
+
+  // Configure the client to use the local cluster.
+  const provider = anchor.AnchorProvider.env();
+  anchor.setProvider(provider);
+
+  const program = anchor.workspace.Marketplace as Program<Marketplace>;
+
+  // Create test keypairs
+  const authority = anchor.web3.Keypair.fromSecretKey(Buffer.from(AUTHORITY_WALLET));
+
+  it('Counter-offers alternate and buyer accepts seller counter', async () => {
+
+    const seller = anchor.web3.Keypair.generate();
+    await utils.safeAirdrop(provider.connection, seller.publicKey, 1);
+
+    const buyer = anchor.web3.Keypair.generate();
+    await utils.safeAirdrop(provider.connection, buyer.publicKey, 1);
+
+    const { tokenAccount, edition, mint: nftMint, metadata } = await mintNFT({
+      payer: seller,
+      connection: provider.connection
+    });
+
+    const tx1 = await listing(program, seller, authority.publicKey, NATIVE_MINT, nftMint, new BN(10_000), null);
+    const tx2 = await buy(program, buyer, authority.publicKey, NATIVE_MINT, nftMint, new BN(6_000), null);
+
+    const auctionHouse = utils.findAuctionHouse(authority.publicKey, NATIVE_MINT);
+    const escrowState = utils.findEscrowState(buyer.publicKey, auctionHouse);
+    const offerAccount = utils.findOfferAccount(auctionHouse, buyer.publicKey, nftMint);
+    const counterAccount = utils.findCounterOffer(offerAccount);
+
+    // Buyer cannot open the negotiation
+    const tx3 = await counterOffer(program, buyer, seller.publicKey, buyer.publicKey, authority.publicKey, NATIVE_MINT, nftMint, new BN(7_000), null);
+    assert(await provider.connection.getAccountInfo(counterAccount) == null, "Buyer opened negotiation.");
+
+    const tx4 = await counterOffer(program, seller, seller.publicKey, buyer.publicKey, authority.publicKey, NATIVE_MINT, nftMint, new BN(9_000), null);
+
+    // Buyer counter moves the offer and its reservation
+    const tx5 = await counterOffer(program, buyer, seller.publicKey, buyer.publicKey, authority.publicKey, NATIVE_MINT, nftMint, new BN(7_000), null);
+    let state = await program.account.buyerEscrowState.fetch(escrowState);
+    assert(state.committed.toNumber() == 7_000, "Buyer counter not reserved.");
+
+    const tx6 = await counterOffer(program, seller, seller.publicKey, buyer.publicKey, authority.publicKey, NATIVE_MINT, nftMint, new BN(8_000), null);
+    const counter = await program.account.counterOfferAccount.fetch(counterAccount);
+    assert(counter.round == 3 && counter.bySeller && counter.price.toNumber() == 8_000, "Counter not recorded.");
+
+    // Buyer accepts, topping up escrow to the counter price
+    const tx7 = await acceptCounter(program, buyer, seller.publicKey, buyer.publicKey, authority.publicKey, NATIVE_MINT, nftMint);
+
+    state = await program.account.buyerEscrowState.fetch(escrowState);
+    assert(state.committed.toNumber() == 0, "Offer reservation not settled.");
+
+    // Check NFT ownership
+    const buyerNftAccount = await getAssociatedTokenAddress(nftMint, buyer.publicKey);
+    const buyerNftInfo = await getAccount(provider.connection, buyerNftAccount);
+    assert(buyerNftInfo.amount == BigInt(1), "NFT not sent to buyer.");
+
+    assert(await provider.connection.getAccountInfo(counterAccount) == null, "Counter not closed.");
+  });
+
+  it('Re-pricing the offer restarts the negotiation', async () => {
+
+    const seller = anchor.web3.Keypair.generate();
+    await utils.safeAirdrop(provider.connection, seller.publicKey, 1);
+
+    const buyer = anchor.web3.Keypair.generate();
+    await utils.safeAirdrop(provider.connection, buyer.publicKey, 1);
+
+    const { mint: nftMint } = await mintNFT({
+      payer: seller,
+      connection: provider.connection
+    });
+
+    const tx1 = await listing(program, seller, authority.publicKey, NATIVE_MINT, nftMint, new BN(10_000), null);
+    const tx2 = await buy(program, buyer, authority.publicKey, NATIVE_MINT, nftMint, new BN(6_000), null);
+
+    const auctionHouse = utils.findAuctionHouse(authority.publicKey, NATIVE_MINT);
+    const offerAccount = utils.findOfferAccount(auctionHouse, buyer.publicKey, nftMint);
+    const counterAccount = utils.findCounterOffer(offerAccount);
+
+    const tx3 = await counterOffer(program, seller, seller.publicKey, buyer.publicKey, authority.publicKey, NATIVE_MINT, nftMint, new BN(9_000), null);
+
+    // Buyer re-prices the offer, the stale seller counter can no longer be accepted
+    const tx4 = await buy(program, buyer, authority.publicKey, NATIVE_MINT, nftMint, new BN(5_000), null);
+    const counter = await program.account.counterOfferAccount.fetch(counterAccount);
+    assert(counter.round == 0 && counter.price.toNumber() == 0, "Counter not reset.");
+
+    const tx5 = await acceptCounter(program, buyer, seller.publicKey, buyer.publicKey, authority.publicKey, NATIVE_MINT, nftMint);
+    assert(tx5 == undefined, "Stale counter accepted.");
+
+    // Seller opens a new negotiation on the new terms
+    const tx6 = await counterOffer(program, seller, seller.publicKey, buyer.publicKey, authority.publicKey, NATIVE_MINT, nftMint, new BN(8_000), null);
+    const reopened = await program.account.counterOfferAccount.fetch(counterAccount);
+    assert(reopened.round == 1 && reopened.offer.equals(offerAccount), "Negotiation not reopened.");
+  });
+
+  it('Accepting a counter respects the listing reservation', async () => {
+
+    const seller = anchor.web3.Keypair.generate();
+    await utils.safeAirdrop(provider.connection, seller.publicKey, 1);
+
+    const buyer = anchor.web3.Keypair.generate();
+    await utils.safeAirdrop(provider.connection, buyer.publicKey, 1);
+
+    const { mint: nftMint } = await mintNFT({
+      payer: seller,
+      connection: provider.connection
+    });
+
+    const reservedBuyer = anchor.web3.Keypair.generate();
+    const tx1 = await listing(program, seller, authority.publicKey, NATIVE_MINT, nftMint, new BN(10_000), null, reservedBuyer.publicKey);
+    const tx2 = await buy(program, buyer, authority.publicKey, NATIVE_MINT, nftMint, new BN(6_000), null);
+    const tx3 = await counterOffer(program, seller, seller.publicKey, buyer.publicKey, authority.publicKey, NATIVE_MINT, nftMint, new BN(9_000), null);
+
+    const tx4 = await acceptCounter(program, buyer, seller.publicKey, buyer.publicKey, authority.publicKey, NATIVE_MINT, nftMint);
+    assert(tx4 == undefined, "Counter settled with a buyer other than the reserved one.");
+  });
+
+  it('Counter of a previous owner cannot be accepted after the NFT is relisted', async () => {
+
+    const seller = anchor.web3.Keypair.generate();
+    await utils.safeAirdrop(provider.connection, seller.publicKey, 1);
+
+    const newOwner = anchor.web3.Keypair.generate();
+    await utils.safeAirdrop(provider.connection, newOwner.publicKey, 1);
+
+    const buyer = anchor.web3.Keypair.generate();
+    await utils.safeAirdrop(provider.connection, buyer.publicKey, 1);
+
+    const { tokenAccount, mint: nftMint } = await mintNFT({
+      payer: seller,
+      connection: provider.connection
+    });
+
+    const tx1 = await listing(program, seller, authority.publicKey, NATIVE_MINT, nftMint, new BN(10_000), null);
+    const tx2 = await buy(program, buyer, authority.publicKey, NATIVE_MINT, nftMint, new BN(6_000), null);
+    const tx3 = await counterOffer(program, seller, seller.publicKey, buyer.publicKey, authority.publicKey, NATIVE_MINT, nftMint, new BN(7_000), null);
+
+    // Seller unlists and hands the NFT to a new owner, who lists it again
+    const tx4 = await unlisting(program, seller, authority.publicKey, NATIVE_MINT, nftMint);
+    const newOwnerNftAccount = await getOrCreateAssociatedTokenAccount(provider.connection, newOwner, nftMint, newOwner.publicKey);
+    await transfer(provider.connection, seller, tokenAccount, newOwnerNftAccount.address, seller, 1);
+    const tx5 = await listing(program, newOwner, authority.publicKey, NATIVE_MINT, nftMint, new BN(10_000), null);
+
+    // The previous owner counter does not sell the new owner NFT
+    const tx6 = await acceptCounter(program, buyer, newOwner.publicKey, buyer.publicKey, authority.publicKey, NATIVE_MINT, nftMint);
+    assert(tx6 == undefined, "Previous owner counter accepted.");
+
+    const auctionHouse = utils.findAuctionHouse(authority.publicKey, NATIVE_MINT);
+    const offerAccount = utils.findOfferAccount(auctionHouse, buyer.publicKey, nftMint);
+    const counterAccount = utils.findCounterOffer(offerAccount);
+
+    // Buyer cannot continue the stale negotiation, the new owner opens a new one
+    const tx7 = await counterOffer(program, buyer, newOwner.publicKey, buyer.publicKey, authority.publicKey, NATIVE_MINT, nftMint, new BN(6_500), null);
+    assert(tx7 == undefined, "Buyer continued a stale negotiation.");
+
+    const tx8 = await counterOffer(program, newOwner, newOwner.publicKey, buyer.publicKey, authority.publicKey, NATIVE_MINT, nftMint, new BN(9_000), null);
+    const counter = await program.account.counterOfferAccount.fetch(counterAccount);
+    assert(counter.round == 1 && counter.seller.equals(newOwner.publicKey) && counter.price.toNumber() == 9_000, "Negotiation not reopened.");
+
+    const tx9 = await acceptCounter(program, buyer, newOwner.publicKey, buyer.publicKey, authority.publicKey, NATIVE_MINT, nftMint);
+
+    const buyerNftAccount = await getAssociatedTokenAddress(nftMint, buyer.publicKey);
+    const buyerNftInfo = await getAccount(provider.connection, buyerNftAccount);
+    assert(buyerNftInfo.amount == BigInt(1), "NFT not sent to buyer.");
+  });
+});
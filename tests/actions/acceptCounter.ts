@@ -0,0 +1,74 @@
+import * as anchor from '@project-serum/anchor';
+import { SYSVAR_RENT_PUBKEY, Keypair, PublicKey } from '@solana/web3.js';
+import { TOKEN_PROGRAM_ID, ASSOCIATED_TOKEN_PROGRAM_ID, NATIVE_MINT, getAssociatedTokenAddress } from "@solana/spl-token";
+import { Program } from '@project-serum/anchor';
+
+import { Marketplace } from "../../target/types/marketplace";
+import { METADATA_PROGRAM_ID, findAuctionHouse, findAuctionHouseTreasury, findCounterOffer, findEditionPda, findEscrowState, findEscrowWallet, findListingAccount, findMetadataPda, findOfferAccount } from '../utils';
+
+export async function acceptCounter(
+    program: Program<Marketplace>,
+    wallet: Keypair,
+    seller: PublicKey,
+    buyer: PublicKey,
+    authority: PublicKey,
+    treasuryMint: PublicKey,
+    nftMint: PublicKey,
+    depositShortfall: boolean = true,
+    creators: Array<PublicKey> = [],
+) {
+    const isNative = treasuryMint == NATIVE_MINT;
+    const auctionHouse = findAuctionHouse(authority, treasuryMint);
+    const offerAccount = findOfferAccount(auctionHouse, buyer, nftMint);
+
+    const sellerNftAccount = await getAssociatedTokenAddress(nftMint, seller);
+    const buyerReceiptTokenAccount = await getAssociatedTokenAddress(nftMint, buyer);
+    const buyerAta = await getAssociatedTokenAddress(treasuryMint, buyer);
+    const sellerPaymentReceiptAccount = isNative ? seller : (await getAssociatedTokenAddress(treasuryMint, seller));
+
+    const remainingAccounts = creators.map(creator => {
+        return {
+            pubkey: creator,
+            isSigner: false,
+            isWritable: true
+        };
+    });
+
+    try {
+        const tx = await program.methods.acceptCounter(depositShortfall)
+            .accounts({
+                wallet: wallet.publicKey,
+                seller: seller,
+                buyer: buyer,
+                treasuryMint: treasuryMint,
+                paymentAccount: isNative ? buyer : buyerAta,
+                escrowPaymentAccount: findEscrowWallet(buyer, auctionHouse),
+                escrowState: findEscrowState(buyer, auctionHouse),
+                sellerPaymentReceiptAccount: sellerPaymentReceiptAccount,
+                buyerReceiptTokenAccount: buyerReceiptTokenAccount,
+                authority: authority,
+                auctionHouseTreasury: findAuctionHouseTreasury(auctionHouse),
+                auctionHouse: auctionHouse,
+                nftMint: nftMint,
+                nftAccount: sellerNftAccount,
+                metadata: await findMetadataPda(nftMint),
+                edition: await findEditionPda(nftMint),
+                listingAccount: findListingAccount(auctionHouse, nftMint),
+                offerAccount: offerAccount,
+                counterAccount: findCounterOffer(offerAccount),
+                tokenMetadataProgram: METADATA_PROGRAM_ID,
+                tokenProgram: TOKEN_PROGRAM_ID,
+                systemProgram: anchor.web3.SystemProgram.programId,
+                ataProgram: ASSOCIATED_TOKEN_PROGRAM_ID,
+                rent: SYSVAR_RENT_PUBKEY,
+            })
+            .remainingAccounts(remainingAccounts)
+            .signers([wallet])
+            .rpc();
+        return tx;
+    }
+    catch (ex) {
+        console.log(ex);
+    }
+
+};
@@ -0,0 +1,71 @@
+import * as anchor from '@project-serum/anchor';
+import { SYSVAR_RENT_PUBKEY, Keypair, PublicKey } from '@solana/web3.js';
+import { TOKEN_PROGRAM_ID, ASSOCIATED_TOKEN_PROGRAM_ID, NATIVE_MINT, getAssociatedTokenAddress } from "@solana/spl-token";
+import { Program } from '@project-serum/anchor';
+
+import { Marketplace } from "../../target/types/marketplace";
+import { METADATA_PROGRAM_ID, findAuctionHouse, findAuctionHouseTreasury, findCounterOffer, findEditionPda, findEscrowState, findEscrowWallet, findListingAccount, findMetadataPda, findOfferAccount } from '../utils';
+
+export async function acceptOffer(
+    program: Program<Marketplace>,
+    seller: Keypair,
+    buyer: PublicKey,
+    authority: PublicKey,
+    treasuryMint: PublicKey,
+    nftMint: PublicKey,
+    creators: Array<PublicKey> = [],
+) {
+    const isNative = treasuryMint == NATIVE_MINT;
+
+    const sellerNftAccount = await getAssociatedTokenAddress(nftMint, seller.publicKey);
+    const nftMetadata = await findMetadataPda(nftMint);
+    const buyerReceiptTokenAccount = await getAssociatedTokenAddress(nftMint, buyer);
+    const sellerPaymentReceiptAccount = isNative ? seller.publicKey : (await getAssociatedTokenAddress(treasuryMint, seller.publicKey));
+
+    const auctionHouse = findAuctionHouse(authority, treasuryMint);
+    const offerAccount = findOfferAccount(auctionHouse, buyer, nftMint);
+
+    const remainingAccounts = creators.map(creator => {
+        return {
+            pubkey: creator,
+            isSigner: false,
+            isWritable: true
+        };
+    });
+
+    try {
+        const tx = await program.methods.acceptOffer()
+            .accounts({
+                seller: seller.publicKey,
+                buyer: buyer,
+                treasuryMint: treasuryMint,
+                escrowPaymentAccount: findEscrowWallet(buyer, auctionHouse),
+                escrowState: findEscrowState(buyer, auctionHouse),
+                sellerPaymentReceiptAccount: sellerPaymentReceiptAccount,
+                buyerReceiptTokenAccount: buyerReceiptTokenAccount,
+                authority: authority,
+                auctionHouseTreasury: findAuctionHouseTreasury(auctionHouse),
+                auctionHouse: auctionHouse,
+                nftMint: nftMint,
+                nftAccount: sellerNftAccount,
+                metadata: nftMetadata,
+                edition: await findEditionPda(nftMint),
+                listingAccount: findListingAccount(auctionHouse, nftMint),
+                offerAccount: offerAccount,
+                counterAccount: findCounterOffer(offerAccount),
+                tokenMetadataProgram: METADATA_PROGRAM_ID,
+                tokenProgram: TOKEN_PROGRAM_ID,
+                systemProgram: anchor.web3.SystemProgram.programId,
+                ataProgram: ASSOCIATED_TOKEN_PROGRAM_ID,
+                rent: SYSVAR_RENT_PUBKEY,
+            })
+            .remainingAccounts(remainingAccounts)
+            .signers([seller])
+            .rpc();
+        return tx;
+    }
+    catch (ex) {
+        console.log(ex);
+    }
+
+};
@@ -0,0 +1,54 @@
+import * as anchor from '@project-serum/anchor';
+import { SYSVAR_RENT_PUBKEY, Keypair, PublicKey } from '@solana/web3.js';
+import { TOKEN_PROGRAM_ID, NATIVE_MINT, getAssociatedTokenAddress } from "@solana/spl-token";
+import { Program } from '@project-serum/anchor';
+
+import { Marketplace } from "../../target/types/marketplace";
+import { findAuctionAccount, findAuctionHouse, findEscrowState, findEscrowWallet } from '../utils';
+
+export async function bid(
+    program: Program<Marketplace>,
+    wallet: Keypair,
+    authority: PublicKey,
+    treasuryMint: PublicKey,
+    nftMint: PublicKey,
+    amount: anchor.BN,
+    depositShortfall: boolean = true,
+) {
+    const isNative = treasuryMint == NATIVE_MINT;
+    const auctionHouse = findAuctionHouse(authority, treasuryMint);
+    const auctionAccount = findAuctionAccount(auctionHouse, nftMint);
+    const escrowWallet = findEscrowWallet(wallet.publicKey, auctionHouse);
+    const escrowState = findEscrowState(wallet.publicKey, auctionHouse);
+    const walletAta = await getAssociatedTokenAddress(treasuryMint, wallet.publicKey);
+
+    // Previous highest bidder reservation gets released
+    const auction = await program.account.auctionAccount.fetch(auctionAccount);
+    const previousBidder = auction.highestBidder;
+
+    try {
+        const tx = await program.methods.bid(amount, depositShortfall)
+            .accounts({
+                bidder: wallet.publicKey,
+                authority: authority,
+                treasuryMint: treasuryMint,
+                auctionHouse: auctionHouse,
+                paymentAccount: isNative ? wallet.publicKey : walletAta,
+                escrowPaymentAccount: escrowWallet,
+                escrowState: escrowState,
+                nftMint: nftMint,
+                auctionAccount: auctionAccount,
+                previousBidderEscrowState: findEscrowState(previousBidder, auctionHouse),
+                systemProgram: anchor.web3.SystemProgram.programId,
+                tokenProgram: TOKEN_PROGRAM_ID,
+                rent: SYSVAR_RENT_PUBKEY,
+            })
+            .signers([wallet])
+            .rpc();
+        return tx;
+    }
+    catch (ex) {
+        console.log(ex);
+    }
+
+};
@@ -4,7 +4,7 @@ import { TOKEN_PROGRAM_ID, ASSOCIATED_TOKEN_PROGRAM_ID, NATIVE_MINT, getAssociat
 import { Program } from '@project-serum/anchor';
 
 import { Marketplace } from "../../target/types/marketplace";
-import { findAuctionHouse, findOfferAccount } from '../utils';
+import { findAuctionHouse, findCounterOffer, findEscrowState, findEscrowWallet, findOfferAccount } from '../utils';
 
 export async function buy(
     program: Program<Marketplace>,
@@ -14,19 +14,29 @@ export async function buy(
     nftMint: PublicKey,
     price: anchor.BN,
     expiry: anchor.BN | null,
+    depositShortfall: boolean = true,
+    startTime: anchor.BN | null = null,
 ) {
+    const isNative = treasuryMint == NATIVE_MINT;
     const auctionHouse = findAuctionHouse(authority, treasuryMint);
-    const offerAccount = findOfferAccount(wallet.publicKey, nftMint);
+    const escrowState = findEscrowState(wallet.publicKey, auctionHouse);
+    const escrowWallet = findEscrowWallet(wallet.publicKey, auctionHouse);
+    const offerAccount = findOfferAccount(auctionHouse, wallet.publicKey, nftMint);
+    const walletAta = await getAssociatedTokenAddress(treasuryMint, wallet.publicKey);
 
     try {
-        const tx = await program.methods.buy(price, expiry)
+        const tx = await program.methods.buy(price, expiry, depositShortfall, startTime)
             .accounts({
                 buyer: wallet.publicKey,
                 authority: authority,
                 treasuryMint: treasuryMint,
                 auctionHouse: auctionHouse,
+                paymentAccount: isNative ? wallet.publicKey : walletAta,
+                escrowPaymentAccount: escrowWallet,
+                escrowState: escrowState,
                 nftMint: nftMint,
                 offerAccount: offerAccount,
+                counterAccount: findCounterOffer(offerAccount),
                 systemProgram: anchor.web3.SystemProgram.programId,
                 tokenProgram: TOKEN_PROGRAM_ID,
                 rent: SYSVAR_RENT_PUBKEY,
@@ -0,0 +1,66 @@
+import * as anchor from '@project-serum/anchor';
+import { SYSVAR_RENT_PUBKEY, Keypair, PublicKey } from '@solana/web3.js';
+import { TOKEN_PROGRAM_ID, ASSOCIATED_TOKEN_PROGRAM_ID, NATIVE_MINT, getAssociatedTokenAddress } from "@solana/spl-token";
+import { Program } from '@project-serum/anchor';
+
+import { Marketplace } from "../../target/types/marketplace";
+import { findAuctionHouse, findAuctionHouseTreasury, findBundleListing, findEscrowState, findEscrowWallet, findMetadataPda } from '../utils';
+
+export async function buyBundle(
+    program: Program<Marketplace>,
+    wallet: Keypair,
+    seller: PublicKey,
+    authority: PublicKey,
+    treasuryMint: PublicKey,
+    bundleId: anchor.BN,
+    nftMints: Array<PublicKey>,
+    creators: Array<Array<PublicKey>> = [],
+    depositShortfall: boolean = true,
+) {
+    const isNative = treasuryMint == NATIVE_MINT;
+    const auctionHouse = findAuctionHouse(authority, treasuryMint);
+
+    const paymentAccount = isNative ? wallet.publicKey : (await getAssociatedTokenAddress(treasuryMint, wallet.publicKey));
+    const sellerPaymentReceiptAccount = isNative ? seller : (await getAssociatedTokenAddress(treasuryMint, seller));
+
+    // Per item: mint, metadata, treasury held token account, buyer receipt, then the item creators
+    const remainingAccounts = [];
+    for (const [index, nftMint] of nftMints.entries()) {
+        remainingAccounts.push({ pubkey: nftMint, isSigner: false, isWritable: false });
+        remainingAccounts.push({ pubkey: await findMetadataPda(nftMint), isSigner: false, isWritable: false });
+        remainingAccounts.push({ pubkey: await getAssociatedTokenAddress(nftMint, seller), isSigner: false, isWritable: true });
+        remainingAccounts.push({ pubkey: await getAssociatedTokenAddress(nftMint, wallet.publicKey), isSigner: false, isWritable: true });
+        for (const creator of creators[index] ?? []) {
+            remainingAccounts.push({ pubkey: creator, isSigner: false, isWritable: true });
+        }
+    }
+
+    try {
+        const tx = await program.methods.buyBundle(depositShortfall)
+            .accounts({
+                buyer: wallet.publicKey,
+                seller: seller,
+                treasuryMint: treasuryMint,
+                paymentAccount: paymentAccount,
+                escrowPaymentAccount: findEscrowWallet(wallet.publicKey, auctionHouse),
+                escrowState: findEscrowState(wallet.publicKey, auctionHouse),
+                sellerPaymentReceiptAccount: sellerPaymentReceiptAccount,
+                authority: authority,
+                auctionHouseTreasury: findAuctionHouseTreasury(auctionHouse),
+                auctionHouse: auctionHouse,
+                listingAccount: findBundleListing(auctionHouse, seller, bundleId),
+                tokenProgram: TOKEN_PROGRAM_ID,
+                systemProgram: anchor.web3.SystemProgram.programId,
+                ataProgram: ASSOCIATED_TOKEN_PROGRAM_ID,
+                rent: SYSVAR_RENT_PUBKEY,
+            })
+            .remainingAccounts(remainingAccounts)
+            .signers([wallet])
+            .rpc();
+        return tx;
+    }
+    catch (ex) {
+        console.log(ex);
+    }
+
+};
@@ -0,0 +1,67 @@
+import * as anchor from '@project-serum/anchor';
+import { SYSVAR_RENT_PUBKEY, Keypair, PublicKey } from '@solana/web3.js';
+import { TOKEN_PROGRAM_ID, ASSOCIATED_TOKEN_PROGRAM_ID, NATIVE_MINT, getAssociatedTokenAddress } from "@solana/spl-token";
+import { Program } from '@project-serum/anchor';
+
+import { Marketplace } from "../../target/types/marketplace";
+import { findAuctionHouse, findAuctionHouseTreasury, findEscrowState, findEscrowWallet, findMetadataPda, findQuantityListing, findVault } from '../utils';
+
+export async function buyQuantity(
+    program: Program<Marketplace>,
+    wallet: Keypair,
+    seller: PublicKey,
+    authority: PublicKey,
+    treasuryMint: PublicKey,
+    nftMint: PublicKey,
+    quantity: anchor.BN,
+    depositShortfall: boolean = true,
+    creators: Array<PublicKey> = [],
+) {
+    const isNative = treasuryMint == NATIVE_MINT;
+    const auctionHouse = findAuctionHouse(authority, treasuryMint);
+    const listingAccount = findQuantityListing(auctionHouse, nftMint, seller);
+
+    const paymentAccount = isNative ? wallet.publicKey : (await getAssociatedTokenAddress(treasuryMint, wallet.publicKey));
+    const sellerPaymentReceiptAccount = isNative ? seller : (await getAssociatedTokenAddress(treasuryMint, seller));
+
+    const remainingAccounts = creators.map(creator => {
+        return {
+            pubkey: creator,
+            isSigner: false,
+            isWritable: true
+        };
+    });
+
+    try {
+        const tx = await program.methods.buyQuantity(quantity, depositShortfall)
+            .accounts({
+                buyer: wallet.publicKey,
+                seller: seller,
+                treasuryMint: treasuryMint,
+                paymentAccount: paymentAccount,
+                escrowPaymentAccount: findEscrowWallet(wallet.publicKey, auctionHouse),
+                escrowState: findEscrowState(wallet.publicKey, auctionHouse),
+                sellerPaymentReceiptAccount: sellerPaymentReceiptAccount,
+                buyerReceiptTokenAccount: await getAssociatedTokenAddress(nftMint, wallet.publicKey),
+                authority: authority,
+                auctionHouseTreasury: findAuctionHouseTreasury(auctionHouse),
+                auctionHouse: auctionHouse,
+                nftMint: nftMint,
+                metadata: await findMetadataPda(nftMint),
+                listingAccount: listingAccount,
+                vault: findVault(listingAccount),
+                tokenProgram: TOKEN_PROGRAM_ID,
+                systemProgram: anchor.web3.SystemProgram.programId,
+                ataProgram: ASSOCIATED_TOKEN_PROGRAM_ID,
+                rent: SYSVAR_RENT_PUBKEY,
+            })
+            .remainingAccounts(remainingAccounts)
+            .signers([wallet])
+            .rpc();
+        return tx;
+    }
+    catch (ex) {
+        console.log(ex);
+    }
+
+};
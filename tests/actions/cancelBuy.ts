@@ -4,7 +4,7 @@ import { TOKEN_PROGRAM_ID, ASSOCIATED_TOKEN_PROGRAM_ID, NATIVE_MINT, getAssociat
 import { Program } from '@project-serum/anchor';
 
 import { Marketplace } from "../../target/types/marketplace";
-import { findAuctionHouse, findAuctionHouseTreasury, findEscrowWallet, findListingAccount, findOfferAccount } from '../utils';
+import { findAuctionHouse, findAuctionHouseTreasury, findCounterOffer, findEscrowState, findEscrowWallet, findListingAccount, findOfferAccount } from '../utils';
 
 export async function cancelBuy(
     program: Program<Marketplace>,
@@ -14,7 +14,10 @@ export async function cancelBuy(
     nftMint: PublicKey,
 ) {
     const auctionHouse = findAuctionHouse(authority, treasuryMint);
-    const offerAccount = findOfferAccount(wallet.publicKey, nftMint);
+    const auctionHouseTreasury = findAuctionHouseTreasury(auctionHouse);
+    const escrowState = findEscrowState(wallet.publicKey, auctionHouse);
+    const escrowWallet = findEscrowWallet(wallet.publicKey, auctionHouse);
+    const offerAccount = findOfferAccount(auctionHouse, wallet.publicKey, nftMint);
 
     try {
         const tx = await program.methods.cancelBuy()
@@ -23,8 +26,12 @@ export async function cancelBuy(
                 authority: authority,
                 treasuryMint: treasuryMint,
                 auctionHouse: auctionHouse,
+                auctionHouseTreasury: auctionHouseTreasury,
+                escrowPaymentAccount: escrowWallet,
                 nftMint: nftMint,
                 offerAccount: offerAccount,
+                counterAccount: findCounterOffer(offerAccount),
+                escrowState: escrowState,
                 systemProgram: anchor.web3.SystemProgram.programId,
                 tokenProgram: TOKEN_PROGRAM_ID,
                 rent: SYSVAR_RENT_PUBKEY,
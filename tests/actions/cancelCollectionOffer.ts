@@ -0,0 +1,35 @@
+import { Keypair, PublicKey } from '@solana/web3.js';
+import { Program } from '@project-serum/anchor';
+
+import { Marketplace } from "../../target/types/marketplace";
+import { findAuctionHouse, findCollectionOffer, findEscrowState } from '../utils';
+
+export async function cancelCollectionOffer(
+    program: Program<Marketplace>,
+    wallet: Keypair,
+    authority: PublicKey,
+    treasuryMint: PublicKey,
+    collection: PublicKey,
+) {
+    const auctionHouse = findAuctionHouse(authority, treasuryMint);
+
+    try {
+        const tx = await program.methods.cancelCollectionOffer()
+            .accounts({
+                buyer: wallet.publicKey,
+                authority: authority,
+                treasuryMint: treasuryMint,
+                auctionHouse: auctionHouse,
+                collection: collection,
+                offerAccount: findCollectionOffer(auctionHouse, collection, wallet.publicKey),
+                escrowState: findEscrowState(wallet.publicKey, auctionHouse),
+            })
+            .signers([wallet])
+            .rpc();
+        return tx;
+    }
+    catch (ex) {
+        console.log(ex);
+    }
+
+};
@@ -0,0 +1,34 @@
+import { Keypair, PublicKey } from '@solana/web3.js';
+import { Program } from '@project-serum/anchor';
+
+import { Marketplace } from "../../target/types/marketplace";
+import { findAuctionHouse, findSetOffer, findEscrowState } from '../utils';
+
+export async function cancelSetOffer(
+    program: Program<Marketplace>,
+    wallet: Keypair,
+    authority: PublicKey,
+    treasuryMint: PublicKey,
+    merkleRoot: Buffer,
+) {
+    const auctionHouse = findAuctionHouse(authority, treasuryMint);
+
+    try {
+        const tx = await program.methods.cancelSetOffer(Array.from(merkleRoot))
+            .accounts({
+                buyer: wallet.publicKey,
+                authority: authority,
+                treasuryMint: treasuryMint,
+                auctionHouse: auctionHouse,
+                offerAccount: findSetOffer(auctionHouse, merkleRoot, wallet.publicKey),
+                escrowState: findEscrowState(wallet.publicKey, auctionHouse),
+            })
+            .signers([wallet])
+            .rpc();
+        return tx;
+    }
+    catch (ex) {
+        console.log(ex);
+    }
+
+};
@@ -0,0 +1,47 @@
+import * as anchor from '@project-serum/anchor';
+import { Keypair, PublicKey } from '@solana/web3.js';
+import { TOKEN_PROGRAM_ID, getAssociatedTokenAddress } from "@solana/spl-token";
+import { Program } from '@project-serum/anchor';
+
+import { Marketplace } from "../../target/types/marketplace";
+import { findAuctionHouse, findAuctionHouseTreasury, findEscrowState, findSwapOffer } from '../utils';
+
+export async function cancelSwapOffer(
+    program: Program<Marketplace>,
+    wallet: Keypair,
+    authority: PublicKey,
+    treasuryMint: PublicKey,
+    swapId: anchor.BN,
+    offeredMints: Array<PublicKey>,
+    nftAccounts: Array<PublicKey> | null = null,
+) {
+    const auctionHouse = findAuctionHouse(authority, treasuryMint);
+
+    const remainingAccounts = [];
+    for (const [index, nftMint] of offeredMints.entries()) {
+        const nftAccount = nftAccounts ? nftAccounts[index] : await getAssociatedTokenAddress(nftMint, wallet.publicKey);
+        remainingAccounts.push({ pubkey: nftAccount, isSigner: false, isWritable: true });
+    }
+
+    try {
+        const tx = await program.methods.cancelSwapOffer()
+            .accounts({
+                maker: wallet.publicKey,
+                treasuryMint: treasuryMint,
+                authority: authority,
+                auctionHouseTreasury: findAuctionHouseTreasury(auctionHouse),
+                auctionHouse: auctionHouse,
+                offerAccount: findSwapOffer(auctionHouse, wallet.publicKey, swapId),
+                escrowState: findEscrowState(wallet.publicKey, auctionHouse),
+                tokenProgram: TOKEN_PROGRAM_ID,
+            })
+            .remainingAccounts(remainingAccounts)
+            .signers([wallet])
+            .rpc();
+        return tx;
+    }
+    catch (ex) {
+        console.log(ex);
+    }
+
+};
@@ -0,0 +1,50 @@
+import * as anchor from '@project-serum/anchor';
+import { SYSVAR_RENT_PUBKEY, Keypair, PublicKey } from '@solana/web3.js';
+import { TOKEN_PROGRAM_ID, NATIVE_MINT, getAssociatedTokenAddress } from "@solana/spl-token";
+import { Program } from '@project-serum/anchor';
+
+import { Marketplace } from "../../target/types/marketplace";
+import { findAuctionAccount, findAuctionHouse, findEscrowState, findEscrowWallet, findSealedBid } from '../utils';
+
+export async function commitBid(
+    program: Program<Marketplace>,
+    wallet: Keypair,
+    authority: PublicKey,
+    treasuryMint: PublicKey,
+    nftMint: PublicKey,
+    commitment: number[],
+    deposit: anchor.BN,
+    depositShortfall: boolean = true,
+) {
+    const isNative = treasuryMint == NATIVE_MINT;
+    const auctionHouse = findAuctionHouse(authority, treasuryMint);
+    const auctionAccount = findAuctionAccount(auctionHouse, nftMint);
+    const auction = await program.account.auctionAccount.fetch(auctionAccount);
+    const walletAta = await getAssociatedTokenAddress(treasuryMint, wallet.publicKey);
+
+    try {
+        const tx = await program.methods.commitBid(commitment, deposit, depositShortfall)
+            .accounts({
+                bidder: wallet.publicKey,
+                authority: authority,
+                treasuryMint: treasuryMint,
+                auctionHouse: auctionHouse,
+                paymentAccount: isNative ? wallet.publicKey : walletAta,
+                escrowPaymentAccount: findEscrowWallet(wallet.publicKey, auctionHouse),
+                escrowState: findEscrowState(wallet.publicKey, auctionHouse),
+                nftMint: nftMint,
+                auctionAccount: auctionAccount,
+                bidAccount: findSealedBid(auctionAccount, auction.nonce, wallet.publicKey),
+                systemProgram: anchor.web3.SystemProgram.programId,
+                tokenProgram: TOKEN_PROGRAM_ID,
+                rent: SYSVAR_RENT_PUBKEY,
+            })
+            .signers([wallet])
+            .rpc();
+        return tx;
+    }
+    catch (ex) {
+        console.log(ex);
+    }
+
+};
@@ -0,0 +1,54 @@
+import * as anchor from '@project-serum/anchor';
+import { SYSVAR_RENT_PUBKEY, Keypair, PublicKey } from '@solana/web3.js';
+import { TOKEN_PROGRAM_ID, NATIVE_MINT, getAssociatedTokenAddress } from "@solana/spl-token";
+import { Program } from '@project-serum/anchor';
+
+import { Marketplace } from "../../target/types/marketplace";
+import { findAuctionHouse, findCounterOffer, findEscrowState, findEscrowWallet, findListingAccount, findOfferAccount } from '../utils';
+
+export async function counterOffer(
+    program: Program<Marketplace>,
+    wallet: Keypair,
+    seller: PublicKey,
+    buyer: PublicKey,
+    authority: PublicKey,
+    treasuryMint: PublicKey,
+    nftMint: PublicKey,
+    price: anchor.BN,
+    expiry: anchor.BN | null,
+    depositShortfall: boolean = true,
+) {
+    const isNative = treasuryMint == NATIVE_MINT;
+    const auctionHouse = findAuctionHouse(authority, treasuryMint);
+    const offerAccount = findOfferAccount(auctionHouse, buyer, nftMint);
+    const buyerAta = await getAssociatedTokenAddress(treasuryMint, buyer);
+
+    try {
+        const tx = await program.methods.counterOffer(price, expiry, depositShortfall)
+            .accounts({
+                wallet: wallet.publicKey,
+                seller: seller,
+                buyer: buyer,
+                authority: authority,
+                treasuryMint: treasuryMint,
+                auctionHouse: auctionHouse,
+                paymentAccount: isNative ? buyer : buyerAta,
+                escrowPaymentAccount: findEscrowWallet(buyer, auctionHouse),
+                escrowState: findEscrowState(buyer, auctionHouse),
+                nftMint: nftMint,
+                listingAccount: findListingAccount(auctionHouse, nftMint),
+                offerAccount: offerAccount,
+                counterAccount: findCounterOffer(offerAccount),
+                systemProgram: anchor.web3.SystemProgram.programId,
+                tokenProgram: TOKEN_PROGRAM_ID,
+                rent: SYSVAR_RENT_PUBKEY,
+            })
+            .signers([wallet])
+            .rpc();
+        return tx;
+    }
+    catch (ex) {
+        console.log(ex);
+    }
+
+};
@@ -14,7 +14,12 @@ export async function createAuctionHouse(
     treasuryWithdrawOwner: PublicKey,
     discountCollection: PublicKey,
     sellerFeeBasispoints: number,
-    discountBasisPoints: number
+    discountBasisPoints: number,
+    extensionWindow: anchor.BN = new anchor.BN(0),
+    extensionPeriod: anchor.BN = new anchor.BN(0),
+    maxExtension: anchor.BN = new anchor.BN(0),
+    cancelFeeBasisPoints: number = 0,
+    cancelCooldown: anchor.BN = new anchor.BN(0),
 ) {
     const auctionHouse = findAuctionHouse(authority, treasuryMint);
     const auctionHouseTreasury = findAuctionHouseTreasury(auctionHouse);
@@ -24,7 +29,7 @@ export async function createAuctionHouse(
         treasuryWithdraw = await getAssociatedTokenAddress(treasuryMint, treasuryWithdrawOwner);
     }
 
-    const tx = await program.methods.createAuctionHouse(sellerFeeBasispoints, discountCollection, discountBasisPoints)
+    const tx = await program.methods.createAuctionHouse(sellerFeeBasispoints, discountCollection, discountBasisPoints, extensionWindow, extensionPeriod, maxExtension, cancelFeeBasisPoints, cancelCooldown)
         .accounts({
             payer: payer.publicKey,
             authority: authority,
@@ -0,0 +1,49 @@
+import * as anchor from '@project-serum/anchor';
+import { SYSVAR_RENT_PUBKEY, Keypair, PublicKey } from '@solana/web3.js';
+import { TOKEN_PROGRAM_ID, getAssociatedTokenAddress } from "@solana/spl-token";
+import { Program } from '@project-serum/anchor';
+
+import { Marketplace } from "../../target/types/marketplace";
+import { findAuctionAccount, findAuctionHouse, findAuctionHouseTreasury } from '../utils';
+
+export async function createSealedAuction(
+    program: Program<Marketplace>,
+    wallet: Keypair,
+    authority: PublicKey,
+    treasuryMint: PublicKey,
+    nftMint: PublicKey,
+    startTime: anchor.BN,
+    endTime: anchor.BN,
+    revealEndTime: anchor.BN,
+    reservePrice: anchor.BN,
+    secondPrice: boolean,
+) {
+    const auctionHouse = findAuctionHouse(authority, treasuryMint);
+    const auctionHouseTreasury = findAuctionHouseTreasury(auctionHouse);
+    const auctionAccount = findAuctionAccount(auctionHouse, nftMint);
+    const nftAccount = await getAssociatedTokenAddress(nftMint, wallet.publicKey);
+
+    try {
+        const tx = await program.methods.createSealedAuction(startTime, endTime, revealEndTime, reservePrice, secondPrice)
+            .accounts({
+                seller: wallet.publicKey,
+                authority: authority,
+                treasuryMint: treasuryMint,
+                auctionHouse: auctionHouse,
+                auctionHouseTreasury: auctionHouseTreasury,
+                nftMint: nftMint,
+                nftAccount: nftAccount,
+                auctionAccount: auctionAccount,
+                systemProgram: anchor.web3.SystemProgram.programId,
+                tokenProgram: TOKEN_PROGRAM_ID,
+                rent: SYSVAR_RENT_PUBKEY,
+            })
+            .signers([wallet])
+            .rpc();
+        return tx;
+    }
+    catch (ex) {
+        console.log(ex);
+    }
+
+};
@@ -4,7 +4,7 @@ import { TOKEN_PROGRAM_ID, ASSOCIATED_TOKEN_PROGRAM_ID, NATIVE_MINT, getAssociat
 import { Program } from '@project-serum/anchor';
 
 import { Marketplace } from "../../target/types/marketplace";
-import { findAuctionHouse, findEscrowWallet } from '../utils';
+import { findAuctionHouse, findEscrowState, findEscrowWallet } from '../utils';
 
 export async function deposit(
     program: Program<Marketplace>,
@@ -15,6 +15,7 @@ export async function deposit(
 ) {
     const isNative = treasuryMint == NATIVE_MINT;
     const auctionHouse = findAuctionHouse(authority, treasuryMint);
+    const escrowState = findEscrowState(wallet.publicKey, auctionHouse);
     const escrowWallet = findEscrowWallet(wallet.publicKey, auctionHouse);
     const walletAta = (await getOrCreateAssociatedTokenAccount(program.provider.connection, wallet, treasuryMint, wallet.publicKey)).address;
 
@@ -25,6 +26,7 @@ export async function deposit(
             treasuryMint: treasuryMint,
             paymentAccount: isNative ? wallet.publicKey : walletAta,
             escrowPaymentAccount: escrowWallet,
+            escrowState: escrowState,
             auctionHouse: auctionHouse,
             systemProgram: anchor.web3.SystemProgram.programId,
             rent: SYSVAR_RENT_PUBKEY,
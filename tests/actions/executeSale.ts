@@ -4,7 +4,7 @@ import { TOKEN_PROGRAM_ID, ASSOCIATED_TOKEN_PROGRAM_ID, NATIVE_MINT, getAssociat
 import { Program } from '@project-serum/anchor';
 
 import { Marketplace } from "../../target/types/marketplace";
-import { findAuctionHouse, findAuctionHouseTreasury, findEscrowWallet, findListingAccount, findMetadataPda, findOfferAccount } from '../utils';
+import { METADATA_PROGRAM_ID, findAuctionHouse, findAuctionHouseTreasury, findCounterOffer, findEditionPda, findEscrowState, findEscrowWallet, findListingAccount, findMetadataPda, findOfferAccount } from '../utils';
 
 export async function executeSale(
     program: Program<Marketplace>,
@@ -16,20 +16,24 @@ export async function executeSale(
     creators: Array<PublicKey> | null = [],
     discountMint: PublicKey | null = null,
     discountTokenAccount: PublicKey | null = null,
-    discountMetadata: PublicKey | null = null
+    discountMetadata: PublicKey | null = null,
+    nftAccount: PublicKey | null = null,
+    maxTotalCost: anchor.BN | null = null,
 ) {
     const isNative = treasuryMint == NATIVE_MINT;
 
-    const sellerNftAccount = await getAssociatedTokenAddress(nftMint, seller);
+    // Vault listings sell out of the listing vault instead of the seller ATA
+    const sellerNftAccount = nftAccount ?? await getAssociatedTokenAddress(nftMint, seller);
     const nftMetadata = await findMetadataPda(nftMint);
     const buyerReceiptTokenAccount = await getAssociatedTokenAddress(nftMint, buyer.publicKey);
     const sellerPaymentReceiptAccount = isNative ? seller : (await getAssociatedTokenAddress(treasuryMint, seller));
 
     const auctionHouse = findAuctionHouse(authority, treasuryMint);
+    const escrowState = findEscrowState(buyer.publicKey, auctionHouse);
     const auctionHouseTreasury = findAuctionHouseTreasury(auctionHouse);
     const escrowWallet = findEscrowWallet(buyer.publicKey, auctionHouse);
-    const listingAccount = findListingAccount(nftMint);
-    const offerAccount = findOfferAccount(buyer.publicKey, nftMint);
+    const listingAccount = findListingAccount(auctionHouse, nftMint);
+    const offerAccount = findOfferAccount(auctionHouse, buyer.publicKey, nftMint);
 
     const remainingAccounts = creators ?
         creators.map(creator => {
@@ -62,11 +66,12 @@ export async function executeSale(
     }
 
     try {
-        const tx = await program.methods.executeSale()
+        const tx = await program.methods.executeSale(maxTotalCost)
             .accounts({
                 buyer: buyer.publicKey,
                 seller: seller,
                 escrowPaymentAccount: escrowWallet,
+                escrowState: escrowState,
                 sellerPaymentReceiptAccount: sellerPaymentReceiptAccount,
                 buyerReceiptTokenAccount: buyerReceiptTokenAccount,
                 authority: authority,
@@ -75,10 +80,13 @@ export async function executeSale(
                 auctionHouseTreasury: auctionHouseTreasury,
                 nftMint: nftMint,
                 metadata: nftMetadata,
+                edition: await findEditionPda(nftMint),
                 nftAccount: sellerNftAccount,
                 offerAccount: offerAccount,
+                counterAccount: findCounterOffer(offerAccount),
                 listingAccount: listingAccount,
                 systemProgram: anchor.web3.SystemProgram.programId,
+                tokenMetadataProgram: METADATA_PROGRAM_ID,
                 tokenProgram: TOKEN_PROGRAM_ID,
                 ataProgram: ASSOCIATED_TOKEN_PROGRAM_ID,
                 rent: SYSVAR_RENT_PUBKEY,
@@ -0,0 +1,63 @@
+import * as anchor from '@project-serum/anchor';
+import { SYSVAR_RENT_PUBKEY, Keypair, PublicKey } from '@solana/web3.js';
+import { TOKEN_PROGRAM_ID, ASSOCIATED_TOKEN_PROGRAM_ID, NATIVE_MINT, getAssociatedTokenAddress } from "@solana/spl-token";
+import { Program } from '@project-serum/anchor';
+
+import { Marketplace } from "../../target/types/marketplace";
+import { findAuctionHouse, findAuctionHouseTreasury, findEscrowState, findEscrowWallet, findMetadataPda, findSwapOffer } from '../utils';
+
+export async function fillSwapOffer(
+    program: Program<Marketplace>,
+    wallet: Keypair,
+    maker: PublicKey,
+    authority: PublicKey,
+    treasuryMint: PublicKey,
+    swapId: anchor.BN,
+    offeredMints: Array<PublicKey>,
+    requestedMints: Array<PublicKey>,
+) {
+    const isNative = treasuryMint == NATIVE_MINT;
+    const auctionHouse = findAuctionHouse(authority, treasuryMint);
+    const takerPaymentReceiptAccount = isNative ? wallet.publicKey : (await getAssociatedTokenAddress(treasuryMint, wallet.publicKey));
+
+    const remainingAccounts = [];
+    for (const nftMint of offeredMints) {
+        remainingAccounts.push({ pubkey: nftMint, isSigner: false, isWritable: false });
+        remainingAccounts.push({ pubkey: await getAssociatedTokenAddress(nftMint, maker), isSigner: false, isWritable: true });
+        remainingAccounts.push({ pubkey: await getAssociatedTokenAddress(nftMint, wallet.publicKey), isSigner: false, isWritable: true });
+    }
+    for (const nftMint of requestedMints) {
+        remainingAccounts.push({ pubkey: nftMint, isSigner: false, isWritable: false });
+        remainingAccounts.push({ pubkey: await findMetadataPda(nftMint), isSigner: false, isWritable: false });
+        remainingAccounts.push({ pubkey: await getAssociatedTokenAddress(nftMint, wallet.publicKey), isSigner: false, isWritable: true });
+        remainingAccounts.push({ pubkey: await getAssociatedTokenAddress(nftMint, maker), isSigner: false, isWritable: true });
+    }
+
+    try {
+        const tx = await program.methods.fillSwapOffer()
+            .accounts({
+                taker: wallet.publicKey,
+                maker: maker,
+                treasuryMint: treasuryMint,
+                escrowPaymentAccount: findEscrowWallet(maker, auctionHouse),
+                escrowState: findEscrowState(maker, auctionHouse),
+                takerPaymentReceiptAccount: takerPaymentReceiptAccount,
+                authority: authority,
+                auctionHouseTreasury: findAuctionHouseTreasury(auctionHouse),
+                auctionHouse: auctionHouse,
+                offerAccount: findSwapOffer(auctionHouse, maker, swapId),
+                tokenProgram: TOKEN_PROGRAM_ID,
+                systemProgram: anchor.web3.SystemProgram.programId,
+                ataProgram: ASSOCIATED_TOKEN_PROGRAM_ID,
+                rent: SYSVAR_RENT_PUBKEY,
+            })
+            .remainingAccounts(remainingAccounts)
+            .signers([wallet])
+            .rpc();
+        return tx;
+    }
+    catch (ex) {
+        console.log(ex);
+    }
+
+};
@@ -0,0 +1,37 @@
+import { Keypair, PublicKey } from '@solana/web3.js';
+import { Program } from '@project-serum/anchor';
+
+import { Marketplace } from "../../target/types/marketplace";
+import { findAuctionHouse, findCounterOffer, findEscrowState, findOfferAccount } from '../utils';
+
+export async function forceCancelBuy(
+    program: Program<Marketplace>,
+    authority: Keypair,
+    buyer: PublicKey,
+    treasuryMint: PublicKey,
+    nftMint: PublicKey,
+) {
+    const auctionHouse = findAuctionHouse(authority.publicKey, treasuryMint);
+    const offerAccount = findOfferAccount(auctionHouse, buyer, nftMint);
+
+    try {
+        const tx = await program.methods.forceCancelBuy()
+            .accounts({
+                authority: authority.publicKey,
+                buyer: buyer,
+                treasuryMint: treasuryMint,
+                auctionHouse: auctionHouse,
+                nftMint: nftMint,
+                offerAccount: offerAccount,
+                counterAccount: findCounterOffer(offerAccount),
+                escrowState: findEscrowState(buyer, auctionHouse),
+            })
+            .signers([authority])
+            .rpc();
+        return tx;
+    }
+    catch (ex) {
+        console.log(ex);
+    }
+
+};
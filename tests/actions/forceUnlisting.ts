@@ -0,0 +1,46 @@
+import { Keypair, PublicKey } from '@solana/web3.js';
+import { TOKEN_PROGRAM_ID, getAssociatedTokenAddress } from "@solana/spl-token";
+import { Program } from '@project-serum/anchor';
+
+import { Marketplace } from "../../target/types/marketplace";
+import { METADATA_PROGRAM_ID, findAuctionHouse, findAuctionHouseTreasury, findEditionPda, findListingAccount } from '../utils';
+
+export async function forceUnlisting(
+    program: Program<Marketplace>,
+    authority: Keypair,
+    seller: PublicKey,
+    treasuryMint: PublicKey,
+    nftMint: PublicKey,
+    nftAccount: PublicKey | null = null,
+) {
+    const auctionHouse = findAuctionHouse(authority.publicKey, treasuryMint);
+    const auctionHouseTreasury = findAuctionHouseTreasury(auctionHouse);
+    const listingAccount = findListingAccount(auctionHouse, nftMint);
+    const sellerNftAccount = await getAssociatedTokenAddress(nftMint, seller);
+
+    try {
+        const tx = await program.methods.forceUnlisting()
+            .accounts({
+                authority: authority.publicKey,
+                seller: seller,
+                treasuryMint: treasuryMint,
+                auctionHouseTreasury: auctionHouseTreasury,
+                auctionHouse: auctionHouse,
+                nftMint: nftMint,
+                // Vault listings hold the NFT in the listing vault instead of the seller ATA
+                nftAccount: nftAccount ?? sellerNftAccount,
+                sellerNftAccount: sellerNftAccount,
+                edition: await findEditionPda(nftMint),
+                listingAccount: listingAccount,
+                tokenMetadataProgram: METADATA_PROGRAM_ID,
+                tokenProgram: TOKEN_PROGRAM_ID,
+            })
+            .signers([authority])
+            .rpc();
+        return tx;
+    }
+    catch (ex) {
+        console.log(ex);
+    }
+
+};
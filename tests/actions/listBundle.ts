@@ -0,0 +1,52 @@
+import * as anchor from '@project-serum/anchor';
+import { SYSVAR_RENT_PUBKEY, Keypair, PublicKey } from '@solana/web3.js';
+import { TOKEN_PROGRAM_ID, getAssociatedTokenAddress } from "@solana/spl-token";
+import { Program } from '@project-serum/anchor';
+
+import { Marketplace } from "../../target/types/marketplace";
+import { findAuctionHouse, findAuctionHouseTreasury, findBundleListing, findEditionPda, findMetadataPda } from '../utils';
+
+export async function listBundle(
+    program: Program<Marketplace>,
+    wallet: Keypair,
+    authority: PublicKey,
+    treasuryMint: PublicKey,
+    bundleId: anchor.BN,
+    nftMints: Array<PublicKey>,
+    price: anchor.BN,
+    allocations: Array<number>,
+    sellerExpiry: anchor.BN | null,
+) {
+    const auctionHouse = findAuctionHouse(authority, treasuryMint);
+
+    const remainingAccounts = [];
+    for (const nftMint of nftMints) {
+        remainingAccounts.push({ pubkey: nftMint, isSigner: false, isWritable: false });
+        remainingAccounts.push({ pubkey: await getAssociatedTokenAddress(nftMint, wallet.publicKey), isSigner: false, isWritable: true });
+        remainingAccounts.push({ pubkey: await findMetadataPda(nftMint), isSigner: false, isWritable: false });
+        remainingAccounts.push({ pubkey: await findEditionPda(nftMint), isSigner: false, isWritable: false });
+    }
+
+    try {
+        const tx = await program.methods.listBundle(bundleId, price, allocations, sellerExpiry)
+            .accounts({
+                seller: wallet.publicKey,
+                treasuryMint: treasuryMint,
+                authority: authority,
+                auctionHouseTreasury: findAuctionHouseTreasury(auctionHouse),
+                auctionHouse: auctionHouse,
+                listingAccount: findBundleListing(auctionHouse, wallet.publicKey, bundleId),
+                tokenProgram: TOKEN_PROGRAM_ID,
+                systemProgram: anchor.web3.SystemProgram.programId,
+                rent: SYSVAR_RENT_PUBKEY,
+            })
+            .remainingAccounts(remainingAccounts)
+            .signers([wallet])
+            .rpc();
+        return tx;
+    }
+    catch (ex) {
+        console.log(ex);
+    }
+
+};
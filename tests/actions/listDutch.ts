@@ -0,0 +1,56 @@
+import * as anchor from '@project-serum/anchor';
+import { SYSVAR_RENT_PUBKEY, Keypair, PublicKey } from '@solana/web3.js';
+import { TOKEN_PROGRAM_ID, getAssociatedTokenAddress } from "@solana/spl-token";
+import { Program } from '@project-serum/anchor';
+
+import { Marketplace } from "../../target/types/marketplace";
+import { findAuctionHouse, findAuctionHouseTreasury, findEditionPda, findListingAccount, findMetadataPda } from '../utils';
+
+export async function listDutch(
+    program: Program<Marketplace>,
+    wallet: Keypair,
+    authority: PublicKey,
+    treasuryMint: PublicKey,
+    nftMint: PublicKey,
+    startPrice: anchor.BN,
+    endPrice: anchor.BN,
+    startTime: anchor.BN,
+    decayDuration: anchor.BN,
+    stepDuration: anchor.BN | null,
+    expiry: anchor.BN | null,
+    reservedBuyer: PublicKey | null = null,
+    sellerStartTime: anchor.BN | null = null,
+    minNetProceeds: anchor.BN | null = null,
+) {
+    const auctionHouse = findAuctionHouse(authority, treasuryMint);
+    const auctionHouseTreasury = findAuctionHouseTreasury(auctionHouse);
+    const listingAccount = findListingAccount(auctionHouse, nftMint);
+    const nftAccount = await getAssociatedTokenAddress(nftMint, wallet.publicKey);
+    const curve = stepDuration ? { step: { stepDuration } } : { linear: {} };
+
+    try {
+        const tx = await program.methods.listDutch(startPrice, endPrice, startTime, decayDuration, curve, { expiry, reservedBuyer, startTime: sellerStartTime, minNetProceeds })
+            .accounts({
+                seller: wallet.publicKey,
+                authority: authority,
+                treasuryMint: treasuryMint,
+                auctionHouse: auctionHouse,
+                auctionHouseTreasury: auctionHouseTreasury,
+                nftMint: nftMint,
+                nftAccount: nftAccount,
+                metadata: await findMetadataPda(nftMint),
+                edition: await findEditionPda(nftMint),
+                listingAccount: listingAccount,
+                systemProgram: anchor.web3.SystemProgram.programId,
+                tokenProgram: TOKEN_PROGRAM_ID,
+                rent: SYSVAR_RENT_PUBKEY,
+            })
+            .signers([wallet])
+            .rpc();
+        return tx;
+    }
+    catch (ex) {
+        console.log(ex);
+    }
+
+};
@@ -4,7 +4,7 @@ import { TOKEN_PROGRAM_ID, ASSOCIATED_TOKEN_PROGRAM_ID, NATIVE_MINT, getAssociat
 import { Program } from '@project-serum/anchor';
 
 import { Marketplace } from "../../target/types/marketplace";
-import { findAuctionHouse, findAuctionHouseTreasury, findEscrowWallet, findListingAccount } from '../utils';
+import { findAuctionHouse, findAuctionHouseTreasury, findEditionPda, findEscrowWallet, findListingAccount, findMetadataPda } from '../utils';
 
 export async function listing(
     program: Program<Marketplace>,
@@ -14,14 +14,18 @@ export async function listing(
     nftMint: PublicKey,
     price: anchor.BN,
     expiry: anchor.BN | null,
+    reservedBuyer: PublicKey | null = null,
+    startTime: anchor.BN | null = null,
+    nftAccount: PublicKey | null = null,
+    minNetProceeds: anchor.BN | null = null,
 ) {
     const auctionHouse = findAuctionHouse(authority, treasuryMint);
     const auctionHouseTreasury = findAuctionHouseTreasury(auctionHouse);
-    const listingAccount = findListingAccount(nftMint);
-    const nftAccount = await getAssociatedTokenAddress(nftMint, wallet.publicKey);
+    const listingAccount = findListingAccount(auctionHouse, nftMint);
+    nftAccount = nftAccount ?? await getAssociatedTokenAddress(nftMint, wallet.publicKey);
 
     try {
-        const tx = await program.methods.list(price, expiry)
+        const tx = await program.methods.list(price, { expiry, reservedBuyer, startTime, minNetProceeds })
             .accounts({
                 seller: wallet.publicKey,
                 authority: authority,
@@ -30,6 +34,8 @@ export async function listing(
                 auctionHouseTreasury: auctionHouseTreasury,
                 nftMint: nftMint,
                 nftAccount: nftAccount,
+                metadata: await findMetadataPda(nftMint),
+                edition: await findEditionPda(nftMint),
                 listingAccount: listingAccount,
                 systemProgram: anchor.web3.SystemProgram.programId,
                 tokenProgram: TOKEN_PROGRAM_ID,
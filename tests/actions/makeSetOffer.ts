@@ -0,0 +1,47 @@
+import * as anchor from '@project-serum/anchor';
+import { SYSVAR_RENT_PUBKEY, Keypair, PublicKey } from '@solana/web3.js';
+import { TOKEN_PROGRAM_ID, NATIVE_MINT, getAssociatedTokenAddress } from "@solana/spl-token";
+import { Program } from '@project-serum/anchor';
+
+import { Marketplace } from "../../target/types/marketplace";
+import { findAuctionHouse, findSetOffer, findEscrowState, findEscrowWallet } from '../utils';
+
+export async function makeSetOffer(
+    program: Program<Marketplace>,
+    wallet: Keypair,
+    authority: PublicKey,
+    treasuryMint: PublicKey,
+    merkleRoot: Buffer,
+    price: anchor.BN,
+    quantity: anchor.BN,
+    expiry: anchor.BN | null,
+    depositShortfall: boolean = true,
+) {
+    const isNative = treasuryMint == NATIVE_MINT;
+    const auctionHouse = findAuctionHouse(authority, treasuryMint);
+    const walletAta = await getAssociatedTokenAddress(treasuryMint, wallet.publicKey);
+
+    try {
+        const tx = await program.methods.makeSetOffer(Array.from(merkleRoot), price, quantity, expiry, depositShortfall)
+            .accounts({
+                buyer: wallet.publicKey,
+                authority: authority,
+                treasuryMint: treasuryMint,
+                auctionHouse: auctionHouse,
+                paymentAccount: isNative ? wallet.publicKey : walletAta,
+                escrowPaymentAccount: findEscrowWallet(wallet.publicKey, auctionHouse),
+                escrowState: findEscrowState(wallet.publicKey, auctionHouse),
+                offerAccount: findSetOffer(auctionHouse, merkleRoot, wallet.publicKey),
+                systemProgram: anchor.web3.SystemProgram.programId,
+                tokenProgram: TOKEN_PROGRAM_ID,
+                rent: SYSVAR_RENT_PUBKEY,
+            })
+            .signers([wallet])
+            .rpc();
+        return tx;
+    }
+    catch (ex) {
+        console.log(ex);
+    }
+
+};
@@ -0,0 +1,59 @@
+import * as anchor from '@project-serum/anchor';
+import { SYSVAR_RENT_PUBKEY, Keypair, PublicKey } from '@solana/web3.js';
+import { TOKEN_PROGRAM_ID, NATIVE_MINT, getAssociatedTokenAddress } from "@solana/spl-token";
+import { Program } from '@project-serum/anchor';
+
+import { Marketplace } from "../../target/types/marketplace";
+import { findAuctionHouse, findAuctionHouseTreasury, findEditionPda, findEscrowState, findEscrowWallet, findMetadataPda, findSwapOffer } from '../utils';
+
+// request is either { mints: { mints: PublicKey[] } } or { collection: { collection: PublicKey, quantity: number } }
+export async function makeSwapOffer(
+    program: Program<Marketplace>,
+    wallet: Keypair,
+    authority: PublicKey,
+    treasuryMint: PublicKey,
+    swapId: anchor.BN,
+    offeredMints: Array<PublicKey>,
+    request: any,
+    sweetener: anchor.BN,
+    makerExpiry: anchor.BN | null,
+    depositShortfall: boolean = true,
+) {
+    const isNative = treasuryMint == NATIVE_MINT;
+    const auctionHouse = findAuctionHouse(authority, treasuryMint);
+    const paymentAccount = isNative ? wallet.publicKey : (await getAssociatedTokenAddress(treasuryMint, wallet.publicKey));
+
+    const remainingAccounts = [];
+    for (const nftMint of offeredMints) {
+        remainingAccounts.push({ pubkey: nftMint, isSigner: false, isWritable: false });
+        remainingAccounts.push({ pubkey: await getAssociatedTokenAddress(nftMint, wallet.publicKey), isSigner: false, isWritable: true });
+        remainingAccounts.push({ pubkey: await findMetadataPda(nftMint), isSigner: false, isWritable: false });
+        remainingAccounts.push({ pubkey: await findEditionPda(nftMint), isSigner: false, isWritable: false });
+    }
+
+    try {
+        const tx = await program.methods.makeSwapOffer(swapId, request, sweetener, makerExpiry, depositShortfall)
+            .accounts({
+                maker: wallet.publicKey,
+                treasuryMint: treasuryMint,
+                authority: authority,
+                auctionHouseTreasury: findAuctionHouseTreasury(auctionHouse),
+                auctionHouse: auctionHouse,
+                paymentAccount: paymentAccount,
+                escrowPaymentAccount: findEscrowWallet(wallet.publicKey, auctionHouse),
+                escrowState: findEscrowState(wallet.publicKey, auctionHouse),
+                offerAccount: findSwapOffer(auctionHouse, wallet.publicKey, swapId),
+                tokenProgram: TOKEN_PROGRAM_ID,
+                systemProgram: anchor.web3.SystemProgram.programId,
+                rent: SYSVAR_RENT_PUBKEY,
+            })
+            .remainingAccounts(remainingAccounts)
+            .signers([wallet])
+            .rpc();
+        return tx;
+    }
+    catch (ex) {
+        console.log(ex);
+    }
+
+};
@@ -0,0 +1,31 @@
+import * as anchor from '@project-serum/anchor';
+import { Keypair, PublicKey } from '@solana/web3.js';
+import { Program } from '@project-serum/anchor';
+
+import { Marketplace } from "../../target/types/marketplace";
+import { findAuctionHouse } from '../utils';
+
+export async function migrateAuctionHouse(
+    program: Program<Marketplace>,
+    authority: Keypair,
+    creator: PublicKey,
+    treasuryMint: PublicKey,
+) {
+    const auctionHouse = findAuctionHouse(creator, treasuryMint);
+
+    try {
+        const tx = await program.methods.migrateAuctionHouse()
+            .accounts({
+                authority: authority.publicKey,
+                auctionHouse: auctionHouse,
+                systemProgram: anchor.web3.SystemProgram.programId
+            })
+            .signers([authority])
+            .rpc();
+        return tx;
+    }
+    catch (ex) {
+        console.log(ex);
+    }
+
+};
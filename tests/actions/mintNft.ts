@@ -5,6 +5,7 @@ import {
     Creator,
     DataV2,
     createCreateMetadataAccountV2Instruction,
+    createVerifyCollectionInstruction,
     Metadata,
 } from '@metaplex-foundation/mpl-token-metadata';
 // eslint-disable-next-line @typescript-eslint/ban-ts-comment
@@ -19,6 +20,7 @@ type MintNFTParams = {
     creators?: Creator[];
     sellerFeeBasisPoints?: number;
     collectionMint?: PublicKey;
+    collectionAuthority?: Keypair;
 };
 
 const URI = 'https://arweave.net/Rmg4pcIv-0FQ7M7X838p2r592Q4NU63Fj7o7XsvBHEE';
@@ -30,6 +32,7 @@ export async function mintNFT({
     connection,
     creators,
     collectionMint,
+    collectionAuthority,
     sellerFeeBasisPoints = 10,
     maxSupply = 100,
 }: MintNFTParams) {
@@ -84,8 +87,72 @@ export async function mintNFT({
     );
     transaction.add(masterEditionInstruction);
 
-    const tx = await connection.sendTransaction(transaction, [payer]);
+    // Verify membership of the collection when its update authority signs
+    const signers = [payer];
+    if (collectionMint && collectionAuthority) {
+        const verifyCollectionInstruction = createVerifyCollectionInstruction({
+            metadata,
+            collectionAuthority: collectionAuthority.publicKey,
+            payer: payer.publicKey,
+            collectionMint,
+            collection: await findMetadataPda(collectionMint),
+            collectionMasterEditionAccount: await findEditionPda(collectionMint),
+        });
+        transaction.add(verifyCollectionInstruction);
+        signers.push(collectionAuthority);
+    }
+
+    const tx = await connection.sendTransaction(transaction, signers);
     // console.log(tx);
 
     return { tokenAccount, edition, mint, metadata };
 }
+
+type MintSFTParams = {
+    payer: Keypair;
+    connection: Connection;
+    amount: number;
+    creators?: Creator[];
+    sellerFeeBasisPoints?: number;
+};
+
+// Semi-fungible token, metadata without a master edition so the supply stays open
+export async function mintSFT({
+    payer,
+    connection,
+    amount,
+    creators,
+    sellerFeeBasisPoints = 10,
+}: MintSFTParams) {
+    const mint = await createMint(connection, payer, payer.publicKey, null, 0);
+
+    const tokenAccount = await createAssociatedTokenAccount(connection, payer, mint, payer.publicKey);
+    await mintToChecked(connection, payer, mint, tokenAccount, payer, amount, 0);
+
+    const data: DataV2 = {
+        uri: URI,
+        name: NAME,
+        symbol: SYMBOL,
+        sellerFeeBasisPoints: sellerFeeBasisPoints,
+        creators: creators ?? null,
+        collection: null,
+        uses: null,
+    };
+
+    const metadata = await findMetadataPda(mint);
+    const transaction = new Transaction().add(createCreateMetadataAccountV2Instruction(
+        {
+            metadata,
+            mint: mint,
+            updateAuthority: payer.publicKey,
+            mintAuthority: payer.publicKey,
+            payer: payer.publicKey,
+        },
+        { createMetadataAccountArgsV2: { isMutable: true, data } },
+    ));
+
+    const tx = await connection.sendTransaction(transaction, [payer]);
+    await connection.confirmTransaction(tx, "confirmed");
+
+    return { tokenAccount, mint, metadata };
+}
@@ -0,0 +1,49 @@
+import * as anchor from '@project-serum/anchor';
+import { Keypair, PublicKey } from '@solana/web3.js';
+import { TOKEN_PROGRAM_ID, NATIVE_MINT, getAssociatedTokenAddress } from "@solana/spl-token";
+import { Program } from '@project-serum/anchor';
+
+import { Marketplace } from "../../target/types/marketplace";
+import { findAuctionAccount, findAuctionHouse, findEscrowState, findEscrowWallet, findSealedBid } from '../utils';
+
+export async function refundSealedBid(
+    program: Program<Marketplace>,
+    wallet: Keypair,
+    authority: PublicKey,
+    treasuryMint: PublicKey,
+    nftMint: PublicKey,
+    nonce: anchor.BN | null = null,
+) {
+    const isNative = treasuryMint == NATIVE_MINT;
+    const auctionHouse = findAuctionHouse(authority, treasuryMint);
+    const auctionAccount = findAuctionAccount(auctionHouse, nftMint);
+
+    // The auction may already be settled, then the bid nonce must be passed
+    const auctionNonce = nonce ?? (await program.account.auctionAccount.fetch(auctionAccount)).nonce;
+    const walletAta = await getAssociatedTokenAddress(treasuryMint, wallet.publicKey);
+
+    try {
+        const tx = await program.methods.refundSealedBid()
+            .accounts({
+                bidder: wallet.publicKey,
+                receiptAccount: isNative ? wallet.publicKey : walletAta,
+                authority: authority,
+                treasuryMint: treasuryMint,
+                auctionHouse: auctionHouse,
+                escrowPaymentAccount: findEscrowWallet(wallet.publicKey, auctionHouse),
+                escrowState: findEscrowState(wallet.publicKey, auctionHouse),
+                nftMint: nftMint,
+                auctionAccount: auctionAccount,
+                bidAccount: findSealedBid(auctionAccount, auctionNonce, wallet.publicKey),
+                systemProgram: anchor.web3.SystemProgram.programId,
+                tokenProgram: TOKEN_PROGRAM_ID,
+            })
+            .signers([wallet])
+            .rpc();
+        return tx;
+    }
+    catch (ex) {
+        console.log(ex);
+    }
+
+};
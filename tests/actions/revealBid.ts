@@ -0,0 +1,38 @@
+import * as anchor from '@project-serum/anchor';
+import { Keypair, PublicKey } from '@solana/web3.js';
+import { Program } from '@project-serum/anchor';
+
+import { Marketplace } from "../../target/types/marketplace";
+import { findAuctionAccount, findAuctionHouse, findSealedBid } from '../utils';
+
+export async function revealBid(
+    program: Program<Marketplace>,
+    wallet: Keypair,
+    authority: PublicKey,
+    treasuryMint: PublicKey,
+    nftMint: PublicKey,
+    amount: anchor.BN,
+    salt: Buffer,
+) {
+    const auctionHouse = findAuctionHouse(authority, treasuryMint);
+    const auctionAccount = findAuctionAccount(auctionHouse, nftMint);
+    const auction = await program.account.auctionAccount.fetch(auctionAccount);
+
+    try {
+        const tx = await program.methods.revealBid(amount, Array.from(salt))
+            .accounts({
+                bidder: wallet.publicKey,
+                auctionHouse: auctionHouse,
+                nftMint: nftMint,
+                auctionAccount: auctionAccount,
+                bidAccount: findSealedBid(auctionAccount, auction.nonce, wallet.publicKey),
+            })
+            .signers([wallet])
+            .rpc();
+        return tx;
+    }
+    catch (ex) {
+        console.log(ex);
+    }
+
+};
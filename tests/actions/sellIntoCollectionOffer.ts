@@ -0,0 +1,68 @@
+import * as anchor from '@project-serum/anchor';
+import { SYSVAR_RENT_PUBKEY, Keypair, PublicKey } from '@solana/web3.js';
+import { TOKEN_PROGRAM_ID, ASSOCIATED_TOKEN_PROGRAM_ID, NATIVE_MINT, getAssociatedTokenAddress } from "@solana/spl-token";
+import { Program } from '@project-serum/anchor';
+
+import { Marketplace } from "../../target/types/marketplace";
+import { findAuctionHouse, findAuctionHouseTreasury, findCollectionOffer, findEscrowState, findEscrowWallet, findMetadataPda } from '../utils';
+
+export async function sellIntoCollectionOffer(
+    program: Program<Marketplace>,
+    wallet: Keypair,
+    buyer: PublicKey,
+    authority: PublicKey,
+    treasuryMint: PublicKey,
+    collection: PublicKey,
+    nftMint: PublicKey,
+    creators: Array<PublicKey> = [],
+) {
+    const isNative = treasuryMint == NATIVE_MINT;
+    const auctionHouse = findAuctionHouse(authority, treasuryMint);
+    const auctionHouseTreasury = findAuctionHouseTreasury(auctionHouse);
+
+    const sellerNftAccount = await getAssociatedTokenAddress(nftMint, wallet.publicKey);
+    const nftMetadata = await findMetadataPda(nftMint);
+    const buyerReceiptTokenAccount = await getAssociatedTokenAddress(nftMint, buyer);
+    const sellerPaymentReceiptAccount = isNative ? wallet.publicKey : (await getAssociatedTokenAddress(treasuryMint, wallet.publicKey));
+
+    const remainingAccounts = creators.map(creator => {
+        return {
+            pubkey: creator,
+            isSigner: false,
+            isWritable: true
+        };
+    });
+
+    try {
+        const tx = await program.methods.sellIntoCollectionOffer()
+            .accounts({
+                seller: wallet.publicKey,
+                buyer: buyer,
+                treasuryMint: treasuryMint,
+                escrowPaymentAccount: findEscrowWallet(buyer, auctionHouse),
+                escrowState: findEscrowState(buyer, auctionHouse),
+                sellerPaymentReceiptAccount: sellerPaymentReceiptAccount,
+                buyerReceiptTokenAccount: buyerReceiptTokenAccount,
+                authority: authority,
+                auctionHouseTreasury: auctionHouseTreasury,
+                auctionHouse: auctionHouse,
+                collection: collection,
+                offerAccount: findCollectionOffer(auctionHouse, collection, buyer),
+                nftMint: nftMint,
+                nftAccount: sellerNftAccount,
+                metadata: nftMetadata,
+                tokenProgram: TOKEN_PROGRAM_ID,
+                systemProgram: anchor.web3.SystemProgram.programId,
+                ataProgram: ASSOCIATED_TOKEN_PROGRAM_ID,
+                rent: SYSVAR_RENT_PUBKEY,
+            })
+            .remainingAccounts(remainingAccounts)
+            .signers([wallet])
+            .rpc();
+        return tx;
+    }
+    catch (ex) {
+        console.log(ex);
+    }
+
+};
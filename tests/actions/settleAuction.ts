@@ -0,0 +1,72 @@
+import * as anchor from '@project-serum/anchor';
+import { SYSVAR_RENT_PUBKEY, Keypair, PublicKey } from '@solana/web3.js';
+import { TOKEN_PROGRAM_ID, ASSOCIATED_TOKEN_PROGRAM_ID, NATIVE_MINT, getAssociatedTokenAddress } from "@solana/spl-token";
+import { Program } from '@project-serum/anchor';
+
+import { Marketplace } from "../../target/types/marketplace";
+import { findAuctionAccount, findAuctionHouse, findAuctionHouseTreasury, findEscrowState, findEscrowWallet, findMetadataPda, findSealedBid } from '../utils';
+
+export async function settleAuction(
+    program: Program<Marketplace>,
+    payer: Keypair,
+    seller: PublicKey,
+    authority: PublicKey,
+    treasuryMint: PublicKey,
+    nftMint: PublicKey,
+    creators: Array<PublicKey> = [],
+) {
+    const isNative = treasuryMint == NATIVE_MINT;
+    const auctionHouse = findAuctionHouse(authority, treasuryMint);
+    const auctionHouseTreasury = findAuctionHouseTreasury(auctionHouse);
+    const auctionAccount = findAuctionAccount(auctionHouse, nftMint);
+
+    const auction = await program.account.auctionAccount.fetch(auctionAccount);
+    const winner = auction.highestBidder;
+
+    const sellerNftAccount = await getAssociatedTokenAddress(nftMint, seller);
+    const nftMetadata = await findMetadataPda(nftMint);
+    const winnerReceiptTokenAccount = await getAssociatedTokenAddress(nftMint, winner, true);
+    const sellerPaymentReceiptAccount = isNative ? seller : (await getAssociatedTokenAddress(treasuryMint, seller));
+
+    const remainingAccounts = creators.map(creator => {
+        return {
+            pubkey: creator,
+            isSigner: false,
+            isWritable: true
+        };
+    });
+
+    try {
+        const tx = await program.methods.settleAuction()
+            .accounts({
+                payer: payer.publicKey,
+                seller: seller,
+                winner: winner,
+                treasuryMint: treasuryMint,
+                escrowPaymentAccount: findEscrowWallet(winner, auctionHouse),
+                escrowState: findEscrowState(winner, auctionHouse),
+                sellerPaymentReceiptAccount: sellerPaymentReceiptAccount,
+                winnerReceiptTokenAccount: winnerReceiptTokenAccount,
+                authority: authority,
+                auctionHouseTreasury: auctionHouseTreasury,
+                auctionHouse: auctionHouse,
+                nftMint: nftMint,
+                nftAccount: sellerNftAccount,
+                metadata: nftMetadata,
+                auctionAccount: auctionAccount,
+                winnerBidAccount: findSealedBid(auctionAccount, auction.nonce, winner),
+                tokenProgram: TOKEN_PROGRAM_ID,
+                systemProgram: anchor.web3.SystemProgram.programId,
+                ataProgram: ASSOCIATED_TOKEN_PROGRAM_ID,
+                rent: SYSVAR_RENT_PUBKEY,
+            })
+            .remainingAccounts(remainingAccounts)
+            .signers([payer])
+            .rpc();
+        return tx;
+    }
+    catch (ex) {
+        console.log(ex);
+    }
+
+};
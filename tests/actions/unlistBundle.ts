@@ -0,0 +1,46 @@
+import * as anchor from '@project-serum/anchor';
+import { Keypair, PublicKey } from '@solana/web3.js';
+import { TOKEN_PROGRAM_ID, getAssociatedTokenAddress } from "@solana/spl-token";
+import { Program } from '@project-serum/anchor';
+
+import { Marketplace } from "../../target/types/marketplace";
+import { findAuctionHouse, findAuctionHouseTreasury, findBundleListing } from '../utils';
+
+export async function unlistBundle(
+    program: Program<Marketplace>,
+    wallet: Keypair,
+    authority: PublicKey,
+    treasuryMint: PublicKey,
+    bundleId: anchor.BN,
+    nftMints: Array<PublicKey>,
+    nftAccounts: Array<PublicKey> | null = null,
+) {
+    const auctionHouse = findAuctionHouse(authority, treasuryMint);
+
+    const remainingAccounts = [];
+    for (const [index, nftMint] of nftMints.entries()) {
+        const nftAccount = nftAccounts ? nftAccounts[index] : await getAssociatedTokenAddress(nftMint, wallet.publicKey);
+        remainingAccounts.push({ pubkey: nftAccount, isSigner: false, isWritable: true });
+    }
+
+    try {
+        const tx = await program.methods.unlistBundle()
+            .accounts({
+                seller: wallet.publicKey,
+                treasuryMint: treasuryMint,
+                authority: authority,
+                auctionHouseTreasury: findAuctionHouseTreasury(auctionHouse),
+                auctionHouse: auctionHouse,
+                listingAccount: findBundleListing(auctionHouse, wallet.publicKey, bundleId),
+                tokenProgram: TOKEN_PROGRAM_ID,
+            })
+            .remainingAccounts(remainingAccounts)
+            .signers([wallet])
+            .rpc();
+        return tx;
+    }
+    catch (ex) {
+        console.log(ex);
+    }
+
+};
@@ -0,0 +1,40 @@
+import { Keypair, PublicKey } from '@solana/web3.js';
+import { TOKEN_PROGRAM_ID, getAssociatedTokenAddress } from "@solana/spl-token";
+import { Program } from '@project-serum/anchor';
+
+import { Marketplace } from "../../target/types/marketplace";
+import { findAuctionHouse, findAuctionHouseTreasury, findQuantityListing, findVault } from '../utils';
+
+export async function unlistQuantity(
+    program: Program<Marketplace>,
+    wallet: Keypair,
+    authority: PublicKey,
+    treasuryMint: PublicKey,
+    nftMint: PublicKey,
+) {
+    const auctionHouse = findAuctionHouse(authority, treasuryMint);
+    const listingAccount = findQuantityListing(auctionHouse, nftMint, wallet.publicKey);
+
+    try {
+        const tx = await program.methods.unlistQuantity()
+            .accounts({
+                seller: wallet.publicKey,
+                treasuryMint: treasuryMint,
+                authority: authority,
+                auctionHouseTreasury: findAuctionHouseTreasury(auctionHouse),
+                auctionHouse: auctionHouse,
+                nftMint: nftMint,
+                nftAccount: await getAssociatedTokenAddress(nftMint, wallet.publicKey),
+                listingAccount: listingAccount,
+                vault: findVault(listingAccount),
+                tokenProgram: TOKEN_PROGRAM_ID,
+            })
+            .signers([wallet])
+            .rpc();
+        return tx;
+    }
+    catch (ex) {
+        console.log(ex);
+    }
+
+};
@@ -0,0 +1,47 @@
+import * as anchor from '@project-serum/anchor';
+import { Keypair, PublicKey } from '@solana/web3.js';
+import { TOKEN_PROGRAM_ID, NATIVE_MINT, getAssociatedTokenAddress } from "@solana/spl-token";
+import { Program } from '@project-serum/anchor';
+
+import { Marketplace } from "../../target/types/marketplace";
+import { findAuctionHouse, findAuctionHouseTreasury, findListingAccount, findVault } from '../utils';
+
+export async function unlistVaulted(
+    program: Program<Marketplace>,
+    wallet: Keypair,
+    authority: PublicKey,
+    treasuryMint: PublicKey,
+    nftMint: PublicKey,
+) {
+    const auctionHouse = findAuctionHouse(authority, treasuryMint);
+    const auctionHouseTreasury = findAuctionHouseTreasury(auctionHouse);
+    const listingAccount = findListingAccount(auctionHouse, nftMint);
+    const nftAccount = await getAssociatedTokenAddress(nftMint, wallet.publicKey);
+    const isNative = treasuryMint == NATIVE_MINT;
+    const walletAta = await getAssociatedTokenAddress(treasuryMint, wallet.publicKey);
+
+    try {
+        const tx = await program.methods.unlistVaulted()
+            .accounts({
+                seller: wallet.publicKey,
+                authority: authority,
+                treasuryMint: treasuryMint,
+                auctionHouse: auctionHouse,
+                auctionHouseTreasury: auctionHouseTreasury,
+                paymentAccount: isNative ? wallet.publicKey : walletAta,
+                nftMint: nftMint,
+                nftAccount: nftAccount,
+                listingAccount: listingAccount,
+                vault: findVault(listingAccount),
+                systemProgram: anchor.web3.SystemProgram.programId,
+                tokenProgram: TOKEN_PROGRAM_ID,
+            })
+            .signers([wallet])
+            .rpc();
+        return tx;
+    }
+    catch (ex) {
+        console.log(ex);
+    }
+
+};
@@ -4,7 +4,7 @@ import { TOKEN_PROGRAM_ID, ASSOCIATED_TOKEN_PROGRAM_ID, NATIVE_MINT, getAssociat
 import { Program } from '@project-serum/anchor';
 
 import { Marketplace } from "../../target/types/marketplace";
-import { findAuctionHouse, findAuctionHouseTreasury, findEscrowWallet, findListingAccount } from '../utils';
+import { METADATA_PROGRAM_ID, findAuctionHouse, findAuctionHouseTreasury, findEditionPda, findEscrowWallet, findListingAccount } from '../utils';
 
 export async function unlisting(
     program: Program<Marketplace>,
@@ -12,11 +12,14 @@ export async function unlisting(
     authority: PublicKey,
     treasuryMint: PublicKey,
     nftMint: PublicKey,
+    nftAccount: PublicKey | null = null,
 ) {
     const auctionHouse = findAuctionHouse(authority, treasuryMint);
     const auctionHouseTreasury = findAuctionHouseTreasury(auctionHouse);
-    const listingAccount = findListingAccount(nftMint);
-    const nftAccount = await getAssociatedTokenAddress(nftMint, wallet.publicKey);
+    const listingAccount = findListingAccount(auctionHouse, nftMint);
+    nftAccount = nftAccount ?? await getAssociatedTokenAddress(nftMint, wallet.publicKey);
+    const isNative = treasuryMint == NATIVE_MINT;
+    const walletAta = await getAssociatedTokenAddress(treasuryMint, wallet.publicKey);
 
     try {
         const tx = await program.methods.unlisting()
@@ -26,9 +29,12 @@ export async function unlisting(
                 treasuryMint: treasuryMint,
                 auctionHouse: auctionHouse,
                 auctionHouseTreasury: auctionHouseTreasury,
+                paymentAccount: isNative ? wallet.publicKey : walletAta,
                 nftMint: nftMint,
                 nftAccount: nftAccount,
+                edition: await findEditionPda(nftMint),
                 listingAccount: listingAccount,
+                tokenMetadataProgram: METADATA_PROGRAM_ID,
                 systemProgram: anchor.web3.SystemProgram.programId,
                 tokenProgram: TOKEN_PROGRAM_ID
             })
@@ -17,6 +17,11 @@ export async function updateAuctionHouse(
     seller_fee_basis_points: number | null,
     discount_basis_points: number | null,
     discount_collection: PublicKey | null,
+    extension_window: anchor.BN | null = null,
+    extension_period: anchor.BN | null = null,
+    max_extension: anchor.BN | null = null,
+    cancel_fee_basis_points: number | null = null,
+    cancel_cooldown: anchor.BN | null = null,
 ) {
     const auctionHouse = findAuctionHouse(creator, treasuryMint);
 
@@ -29,6 +34,11 @@ export async function updateAuctionHouse(
         seller_fee_basis_points,
         discount_collection,
         discount_basis_points,
+        extension_window,
+        extension_period,
+        max_extension,
+        cancel_fee_basis_points,
+        cancel_cooldown,
     )
         .accounts({
             payer: payer.publicKey,
@@ -0,0 +1,40 @@
+import * as anchor from '@project-serum/anchor';
+import { SYSVAR_RENT_PUBKEY, Keypair, PublicKey } from '@solana/web3.js';
+import { TOKEN_PROGRAM_ID, ASSOCIATED_TOKEN_PROGRAM_ID, NATIVE_MINT, getAssociatedTokenAddress } from "@solana/spl-token";
+import { Program } from '@project-serum/anchor';
+
+import { Marketplace } from "../../target/types/marketplace";
+import { findAuctionHouse, findEscrowState, findEscrowWallet } from '../utils';
+
+export async function withdraw(
+    program: Program<Marketplace>,
+    wallet: Keypair,
+    authority: PublicKey,
+    treasuryMint: PublicKey,
+    amount: anchor.BN
+) {
+    const isNative = treasuryMint == NATIVE_MINT;
+    const auctionHouse = findAuctionHouse(authority, treasuryMint);
+    const escrowState = findEscrowState(wallet.publicKey, auctionHouse);
+    const escrowWallet = findEscrowWallet(wallet.publicKey, auctionHouse);
+    const walletAta = await getAssociatedTokenAddress(treasuryMint, wallet.publicKey);
+
+    const tx = await program.methods.withdraw(amount)
+        .accounts({
+            wallet: wallet.publicKey,
+            receiptAccount: isNative ? wallet.publicKey : walletAta,
+            escrowPaymentAccount: escrowWallet,
+            escrowState: escrowState,
+            treasuryMint: treasuryMint,
+            authority: authority,
+            auctionHouse: auctionHouse,
+            tokenProgram: TOKEN_PROGRAM_ID,
+            systemProgram: anchor.web3.SystemProgram.programId,
+            ataProgram: ASSOCIATED_TOKEN_PROGRAM_ID,
+            rent: SYSVAR_RENT_PUBKEY,
+        })
+        .signers([wallet])
+        .rpc();
+    return tx;
+
+};
@@ -2,12 +2,24 @@ import { Metadata } from '@metaplex-foundation/mpl-token-metadata';
 import * as anchor from '@project-serum/anchor';
 import { findProgramAddressSync } from '@project-serum/anchor/dist/cjs/utils/pubkey';
 import { PublicKey, LAMPORTS_PER_SOL } from '@solana/web3.js';
+import { createHash } from 'crypto';
+import { keccak_256 } from 'js-sha3';
 
 export const PREFIX = 'marketplace';
 export const TREASURY = 'treasury';
 export const SIGNER = 'signer';
 export const LISTING = 'listing';
+export const QUANTITY_LISTING = 'quantity_listing';
+export const VAULT = 'vault';
+export const BUNDLE = 'bundle';
 export const OFFER = 'offer';
+export const SWAP = 'swap';
+export const ESCROW_STATE = 'escrow_state';
+export const COLLECTION_OFFER = 'collection_offer';
+export const SET_OFFER = 'set_offer';
+export const COUNTER_OFFER = 'counter_offer';
+export const AUCTION = 'auction';
+export const BID = 'bid';
 
 export const METADATA_PROGRAM_ID = new PublicKey("metaqbxxUerdq28cj1RbAWkYQm3ybzjb6a8bt518x1s");
 export const PROGRAM_ID = new PublicKey("GG2v349mCx2DUL2Pu3aFtKgjxdNjxYYZjUXVhLSbFt8Q");
@@ -65,12 +77,25 @@ export const findEscrowWallet = (
   return pubkey;
 }
 
+export const findEscrowState = (
+  wallet: PublicKey,
+  auctionHouse: PublicKey
+): PublicKey => {
+  let [pubkey, bump] = findProgramAddressSync(
+    [Buffer.from(PREFIX), auctionHouse.toBuffer(), wallet.toBuffer(), Buffer.from(ESCROW_STATE)],
+    PROGRAM_ID,
+  );
+
+  return pubkey;
+}
+
 export const findOfferAccount = (
+  auctionHouse: PublicKey,
   wallet: PublicKey,
   nftMint: PublicKey
 ): PublicKey => {
   let [pubkey, bump] = findProgramAddressSync(
-    [Buffer.from(PREFIX), nftMint.toBuffer(), wallet.toBuffer(), Buffer.from(OFFER)],
+    [Buffer.from(PREFIX), auctionHouse.toBuffer(), nftMint.toBuffer(), wallet.toBuffer(), Buffer.from(OFFER)],
     PROGRAM_ID,
   );
 
@@ -78,16 +103,173 @@ export const findOfferAccount = (
 }
 
 export const findListingAccount = (
+  auctionHouse: PublicKey,
+  nftMint: PublicKey
+): PublicKey => {
+  let [pubkey, bump] = findProgramAddressSync(
+    [Buffer.from(PREFIX), auctionHouse.toBuffer(), nftMint.toBuffer(), Buffer.from(LISTING)],
+    PROGRAM_ID,
+  );
+
+  return pubkey;
+}
+
+export const findQuantityListing = (
+  auctionHouse: PublicKey,
+  nftMint: PublicKey,
+  wallet: PublicKey
+): PublicKey => {
+  let [pubkey, bump] = findProgramAddressSync(
+    [Buffer.from(PREFIX), auctionHouse.toBuffer(), nftMint.toBuffer(), wallet.toBuffer(), Buffer.from(QUANTITY_LISTING)],
+    PROGRAM_ID,
+  );
+
+  return pubkey;
+}
+
+export const findVault = (
+  listingAccount: PublicKey
+): PublicKey => {
+  let [pubkey, bump] = findProgramAddressSync(
+    [Buffer.from(PREFIX), listingAccount.toBuffer(), Buffer.from(VAULT)],
+    PROGRAM_ID,
+  );
+
+  return pubkey;
+}
+
+export const findBundleListing = (
+  auctionHouse: PublicKey,
+  wallet: PublicKey,
+  bundleId: anchor.BN
+): PublicKey => {
+  let [pubkey, bump] = findProgramAddressSync(
+    [Buffer.from(PREFIX), auctionHouse.toBuffer(), wallet.toBuffer(), bundleId.toArrayLike(Buffer, 'le', 8), Buffer.from(BUNDLE)],
+    PROGRAM_ID,
+  );
+
+  return pubkey;
+}
+
+export const findSwapOffer = (
+  auctionHouse: PublicKey,
+  wallet: PublicKey,
+  swapId: anchor.BN
+): PublicKey => {
+  let [pubkey, bump] = findProgramAddressSync(
+    [Buffer.from(PREFIX), auctionHouse.toBuffer(), wallet.toBuffer(), swapId.toArrayLike(Buffer, 'le', 8), Buffer.from(SWAP)],
+    PROGRAM_ID,
+  );
+
+  return pubkey;
+}
+
+export const findCounterOffer = (
+  offerAccount: PublicKey
+): PublicKey => {
+  let [pubkey, bump] = findProgramAddressSync(
+    [Buffer.from(PREFIX), offerAccount.toBuffer(), Buffer.from(COUNTER_OFFER)],
+    PROGRAM_ID,
+  );
+
+  return pubkey;
+}
+
+export const findCollectionOffer = (
+  auctionHouse: PublicKey,
+  collection: PublicKey,
+  wallet: PublicKey
+): PublicKey => {
+  let [pubkey, bump] = findProgramAddressSync(
+    [Buffer.from(PREFIX), auctionHouse.toBuffer(), collection.toBuffer(), wallet.toBuffer(), Buffer.from(COLLECTION_OFFER)],
+    PROGRAM_ID,
+  );
+
+  return pubkey;
+}
+
+export const findSetOffer = (
+  auctionHouse: PublicKey,
+  merkleRoot: Buffer,
+  wallet: PublicKey
+): PublicKey => {
+  let [pubkey, bump] = findProgramAddressSync(
+    [Buffer.from(PREFIX), auctionHouse.toBuffer(), merkleRoot, wallet.toBuffer(), Buffer.from(SET_OFFER)],
+    PROGRAM_ID,
+  );
+
+  return pubkey;
+}
+
+// Merkle tree of mints, leaves are keccak(mint) and nodes hash their children in ascending order
+const hashPair = (a: Buffer, b: Buffer): Buffer => {
+  const [first, second] = Buffer.compare(a, b) <= 0 ? [a, b] : [b, a];
+  return Buffer.from(keccak_256.arrayBuffer(Buffer.concat([first, second])));
+}
+
+export const buildMerkleTree = (mints: PublicKey[]) => {
+  let levels: Buffer[][] = [mints.map(mint => Buffer.from(keccak_256.arrayBuffer(mint.toBuffer())))];
+  while (levels[levels.length - 1].length > 1) {
+    const level = levels[levels.length - 1];
+    const next: Buffer[] = [];
+    for (let i = 0; i < level.length; i += 2) {
+      next.push(i + 1 < level.length ? hashPair(level[i], level[i + 1]) : level[i]);
+    }
+    levels.push(next);
+  }
+
+  const root = levels[levels.length - 1][0];
+  const proof = (mint: PublicKey): number[][] => {
+    let index = mints.findIndex(m => m.equals(mint));
+    const path: number[][] = [];
+    for (const level of levels.slice(0, -1)) {
+      const sibling = index % 2 == 0 ? index + 1 : index - 1;
+      if (sibling < level.length) {
+        path.push(Array.from(level[sibling]));
+      }
+      index = Math.floor(index / 2);
+    }
+    return path;
+  }
+
+  return { root, proof };
+}
+
+export const findAuctionAccount = (
+  auctionHouse: PublicKey,
   nftMint: PublicKey
 ): PublicKey => {
   let [pubkey, bump] = findProgramAddressSync(
-    [Buffer.from(PREFIX), nftMint.toBuffer(), Buffer.from(LISTING)],
+    [Buffer.from(PREFIX), auctionHouse.toBuffer(), nftMint.toBuffer(), Buffer.from(AUCTION)],
     PROGRAM_ID,
   );
 
   return pubkey;
 }
 
+export const findSealedBid = (
+  auctionAccount: PublicKey,
+  nonce: anchor.BN,
+  bidder: PublicKey
+): PublicKey => {
+  let [pubkey, bump] = findProgramAddressSync(
+    [Buffer.from(PREFIX), auctionAccount.toBuffer(), nonce.toArrayLike(Buffer, 'le', 8), bidder.toBuffer(), Buffer.from(BID)],
+    PROGRAM_ID,
+  );
+
+  return pubkey;
+}
+
+// Sealed bid commitment, sha256 of the little endian amount followed by the salt
+export const sealBid = (amount: anchor.BN, salt: Buffer): number[] => {
+  const hash = createHash('sha256')
+    .update(amount.toArrayLike(Buffer, 'le', 8))
+    .update(salt)
+    .digest();
+
+  return Array.from(hash);
+}
+
 export const findMetadataPda = async (
   mint: PublicKey
 ): Promise<PublicKey> => {